@@ -0,0 +1,69 @@
+//! Adds a custom output format next to the built-in ones.
+//!
+//! The `VCardRenderer` below writes the contact details from the resume to a vCard file. It's
+//! registered with the built-in renderers, so the usual command line arguments work as-is:
+//!
+//! ```bash
+//! cargo run --example custom_renderer -- -i tst/test_resume.toml -o resume -d output
+//! ```
+extern crate resume_generator;
+
+use resume_generator::config::Config;
+use resume_generator::data::Resume;
+use resume_generator::error::Error;
+use resume_generator::renderer::{Renderer, RendererRegistry};
+use resume_generator::util::{add_https_to_url, write_string_to_file};
+use std::path::PathBuf;
+use std::process;
+
+struct VCardRenderer;
+
+impl Renderer<Resume, String> for VCardRenderer {
+    fn render(&self, element: &Resume, _config: &Config) -> Result<String, Error> {
+        let mut lines = vec![
+            String::from("BEGIN:VCARD"),
+            String::from("VERSION:4.0"),
+            format!("FN:{}", element.name),
+            format!("EMAIL:{}", element.personal_info.email),
+            format!("URL:{}", add_https_to_url(&element.personal_info.github)),
+        ];
+        if let Some(other) = &element.personal_info.other {
+            lines.extend(
+                other
+                    .iter()
+                    .filter_map(|x| x.url.as_ref())
+                    .map(|url| format!("URL:{}", add_https_to_url(url))),
+            );
+        }
+        lines.push(String::from("END:VCARD"));
+        Ok(lines.join("\r\n"))
+    }
+}
+
+impl Renderer<Resume, PathBuf> for VCardRenderer {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
+        let s: String = self.render(element, config)?;
+
+        write_string_to_file(
+            &s,
+            config.args.output_dir.as_ref(),
+            config.args.output_name(),
+            Some(String::from("vcf")).as_ref(),
+        )
+    }
+}
+
+fn main() {
+    let r = Config::new_and_parse_args().and_then(|config| {
+        let resume = Resume::read_from_config_file(&config.args.resume_input, &config)?;
+        let report = RendererRegistry::from_config(&config)
+            .with_renderer("vcard", VCardRenderer)
+            .render(&resume, &config);
+        println!("{}", report);
+        report.into_result()
+    });
+    if let Err(e) = r {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,817 @@
+//! Checking the resume for common problems, e.g. bullets that don't start with an action verb.
+//!
+//! Each rule can be turned off, or made an error that fails the check, in the `check_config`
+//! table of the format config. The resume is checked as it's written in the file, before any
+//! variant is applied or experience is sorted, so the problems can be located in the file.
+use crate::config::arguments::Arguments;
+use crate::config::format_config::{CheckConfig, Severity};
+use crate::config::Config;
+use crate::data::markup::visible_text;
+use crate::data::source_map::SourceMap;
+use crate::data::Resume;
+use crate::error::Error;
+use crate::util::{input_format, string_from_file};
+use std::fmt;
+
+/// Common verbs to start a bullet with. Past tenses and third person forms of these are accepted
+/// as well, e.g. "Reduced" or "Builds".
+const ACTION_VERBS: &[&str] = &[
+    "accelerate",
+    "achieve",
+    "administer",
+    "advise",
+    "align",
+    "analyze",
+    "architect",
+    "assess",
+    "assist",
+    "audit",
+    "author",
+    "automate",
+    "build",
+    "champion",
+    "coach",
+    "collaborate",
+    "compile",
+    "complete",
+    "conduct",
+    "configure",
+    "consolidate",
+    "construct",
+    "contribute",
+    "convert",
+    "coordinate",
+    "create",
+    "cultivate",
+    "cut",
+    "debug",
+    "decrease",
+    "define",
+    "delegate",
+    "deliver",
+    "demonstrate",
+    "deploy",
+    "design",
+    "develop",
+    "devise",
+    "diagnose",
+    "direct",
+    "document",
+    "drive",
+    "eliminate",
+    "enable",
+    "engineer",
+    "enhance",
+    "establish",
+    "evaluate",
+    "expand",
+    "facilitate",
+    "fix",
+    "forecast",
+    "formulate",
+    "found",
+    "generate",
+    "grow",
+    "guide",
+    "identify",
+    "implement",
+    "improve",
+    "increase",
+    "initiate",
+    "integrate",
+    "introduce",
+    "investigate",
+    "launch",
+    "lead",
+    "maintain",
+    "manage",
+    "mentor",
+    "migrate",
+    "minimize",
+    "model",
+    "modernize",
+    "monitor",
+    "negotiate",
+    "optimize",
+    "orchestrate",
+    "organize",
+    "oversee",
+    "own",
+    "partner",
+    "pilot",
+    "pioneer",
+    "plan",
+    "present",
+    "prioritize",
+    "produce",
+    "program",
+    "propose",
+    "prototype",
+    "provide",
+    "publish",
+    "rebuild",
+    "redesign",
+    "reduce",
+    "refactor",
+    "release",
+    "replace",
+    "research",
+    "resolve",
+    "restructure",
+    "revamp",
+    "review",
+    "rewrite",
+    "run",
+    "scale",
+    "secure",
+    "ship",
+    "simplify",
+    "solve",
+    "spearhead",
+    "standardize",
+    "streamline",
+    "strengthen",
+    "supervise",
+    "support",
+    "teach",
+    "test",
+    "train",
+    "transform",
+    "translate",
+    "triage",
+    "troubleshoot",
+    "unify",
+    "update",
+    "upgrade",
+    "validate",
+    "win",
+    "write",
+];
+
+/// The past tenses of [ACTION_VERBS] that don't end in "ed".
+const IRREGULAR_VERBS: &[&str] = &[
+    "built", "drove", "grew", "led", "oversaw", "ran", "rebuilt", "rewrote", "taught", "won",
+    "wrote",
+];
+
+const FIRST_PERSON: &[&str] = &[
+    "i",
+    "i'm",
+    "i've",
+    "i'd",
+    "i'll",
+    "me",
+    "my",
+    "mine",
+    "myself",
+    "we",
+    "we're",
+    "we've",
+    "our",
+    "ours",
+    "ourselves",
+];
+
+/// The rules that the resume is checked against. See [CheckConfig] for what each one checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    ActionVerb,
+    BulletLength,
+    TrailingPeriod,
+    FirstPerson,
+    EmptySection,
+    ProjectUrl,
+    OverlappingDates,
+    BulletCount,
+}
+
+impl Rule {
+    pub fn severity(&self, config: &CheckConfig) -> Severity {
+        match self {
+            Rule::ActionVerb => config.action_verb,
+            Rule::BulletLength => config.bullet_length,
+            Rule::TrailingPeriod => config.trailing_period,
+            Rule::FirstPerson => config.first_person,
+            Rule::EmptySection => config.empty_section,
+            Rule::ProjectUrl => config.project_url,
+            Rule::OverlappingDates => config.overlapping_dates,
+            Rule::BulletCount => config.bullet_count,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    /// The name of the rule's setting in the `check_config` table.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Rule::ActionVerb => "action_verb",
+            Rule::BulletLength => "bullet_length",
+            Rule::TrailingPeriod => "trailing_period",
+            Rule::FirstPerson => "first_person",
+            Rule::EmptySection => "empty_section",
+            Rule::ProjectUrl => "project_url",
+            Rule::OverlappingDates => "overlapping_dates",
+            Rule::BulletCount => "bullet_count",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A problem found by a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub rule: Rule,
+    pub severity: Severity,
+    /// Where the problem is in the resume, e.g. `professional_experience[0].experience[2]`.
+    pub path: String,
+    pub message: String,
+    /// The 1-based line and column in the resume data file, if the path could be found in it.
+    pub location: Option<(usize, usize)>,
+}
+
+/// The problems found in a resume data file.
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    pub file_name: String,
+    pub findings: Vec<Finding>,
+}
+
+impl CheckReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|x| x.severity == severity)
+            .count()
+    }
+
+    /// Convert the report to an error if any rule that's set to "error" failed.
+    pub fn into_result(self) -> Result<(), Error> {
+        match self.count(Severity::Error) {
+            0 => Ok(()),
+            errors => Err(Error::Check(count_string(errors, "error"))),
+        }
+    }
+}
+
+/// E.g. "1 error" or "2 errors".
+fn count_string(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for x in &self.findings {
+            match x.location {
+                Some((line, column)) => write!(f, "{}:{}:{}", self.file_name, line, column)?,
+                None => write!(f, "{} ({})", self.file_name, x.path)?,
+            }
+            writeln!(f, ": {}[{}]: {}", x.severity, x.rule, x.message)?;
+        }
+        if self.findings.is_empty() {
+            write!(f, "No problems found")
+        } else {
+            write!(
+                f,
+                "{}, {}",
+                count_string(self.count(Severity::Error), "error"),
+                count_string(self.count(Severity::Warning), "warning")
+            )
+        }
+    }
+}
+
+/// Read the resume data file and check it against the rules in the format config.
+pub fn check(args: &Arguments) -> Result<CheckReport, Error> {
+    let config = Config::from_args(args.clone())?;
+    let file_name = &config.args.resume_input;
+    let source = string_from_file(file_name)?;
+    let resume = Resume::verify(Resume::from_source(&source, file_name, &config)?)?;
+
+    let source_map = SourceMap::new(&source, input_format(file_name, args.input_format));
+    let mut findings = check_resume(&resume, &config.format_config.check_config);
+    for x in findings.iter_mut() {
+        x.location = source_map.locate(&x.path);
+    }
+    findings.sort_by_key(|x| (x.location.is_none(), x.location));
+    Ok(CheckReport {
+        file_name: file_name.to_owned(),
+        findings,
+    })
+}
+
+/// Check the resume against each rule that isn't turned off. The findings aren't located.
+pub fn check_resume(resume: &Resume, config: &CheckConfig) -> Vec<Finding> {
+    let mut findings = vec![];
+    let mut report = |rule: Rule, path: String, message: String| {
+        let severity = rule.severity(config);
+        if severity != Severity::Off {
+            findings.push(Finding {
+                rule,
+                severity,
+                path,
+                message,
+                location: None,
+            });
+        }
+    };
+
+    let bullets = bullets(resume);
+    for (path, text) in &bullets {
+        if !starts_with_action_verb(text, &config.action_verbs) {
+            report(
+                Rule::ActionVerb,
+                path.clone(),
+                format!("\"{}\" doesn't start with an action verb", excerpt(text)),
+            );
+        }
+        let length = visible_text(text).chars().count();
+        if length > config.max_bullet_length {
+            report(
+                Rule::BulletLength,
+                path.clone(),
+                format!(
+                    "\"{}\" is {} characters long, more than the {} allowed by max_bullet_length",
+                    excerpt(text),
+                    length,
+                    config.max_bullet_length
+                ),
+            );
+        }
+    }
+
+    // Only the bullets that differ from the majority are reported
+    let with_period = bullets.iter().filter(|(_, x)| ends_with_period(x)).count();
+    if with_period > 0 && with_period < bullets.len() {
+        let majority = with_period * 2 > bullets.len();
+        for (path, text) in bullets
+            .iter()
+            .filter(|(_, x)| ends_with_period(x) != majority)
+        {
+            let message = if majority {
+                "doesn't end with a period, unlike most bullets"
+            } else {
+                "ends with a period, unlike most bullets"
+            };
+            report(
+                Rule::TrailingPeriod,
+                path.clone(),
+                format!("\"{}\" {}", excerpt(text), message),
+            );
+        }
+    }
+
+    let mut prose = bullets.clone();
+    if let Some(objective) = &resume.objective {
+        prose.push((String::from("objective.objective"), &objective.objective));
+    }
+    if let Some(other_experience) = &resume.other_experience {
+        prose.extend(other_experience.projects.iter().enumerate().map(|(i, x)| {
+            (
+                format!("other_experience.projects[{}].description", i),
+                x.description.as_str(),
+            )
+        }));
+    }
+    for (path, text) in &prose {
+        if let Some(word) = first_person_word(text) {
+            report(
+                Rule::FirstPerson,
+                path.clone(),
+                format!("\"{}\" uses the first person \"{}\"", excerpt(text), word),
+            );
+        }
+    }
+
+    for (path, section) in empty_sections(resume) {
+        report(Rule::EmptySection, path, format!("{} is empty", section));
+    }
+
+    if let Some(other_experience) = &resume.other_experience {
+        for (i, x) in other_experience.projects.iter().enumerate() {
+            if x.url.trim().is_empty() {
+                report(
+                    Rule::ProjectUrl,
+                    format!("other_experience.projects[{}].project_name", i),
+                    format!("The project [{}] has no URL", x.project_name),
+                );
+            }
+        }
+    }
+
+    // Experience without an organization belongs to the organization of the experience before it
+    let mut organization = None;
+    let jobs: Vec<_> = resume
+        .professional_experience
+        .iter()
+        .map(|x| {
+            if x.organization.is_some() {
+                organization = x.organization.as_deref();
+            }
+            match organization {
+                Some(organization) => (x, format!("{} at {}", x.position, organization)),
+                None => (x, x.position.clone()),
+            }
+        })
+        .collect();
+    for (i, (a, name_a)) in jobs.iter().enumerate() {
+        for (b, name_b) in &jobs[i + 1..] {
+            if a.start < b.end && b.start < a.end {
+                report(
+                    Rule::OverlappingDates,
+                    format!("professional_experience[{}].start", i),
+                    format!(
+                        "The dates of [{}] ({} - {}) overlap with [{}] ({} - {})",
+                        name_a,
+                        a.start.format("%b %Y"),
+                        a.end.format("%b %Y"),
+                        name_b,
+                        b.start.format("%b %Y"),
+                        b.end.format("%b %Y")
+                    ),
+                );
+            }
+        }
+    }
+
+    for (i, (x, name)) in jobs.iter().enumerate() {
+        if x.experience.len() > config.max_bullets_per_job {
+            report(
+                Rule::BulletCount,
+                format!("professional_experience[{}].experience", i),
+                format!(
+                    "[{}] has {} bullets, more than the {} allowed by max_bullets_per_job",
+                    name,
+                    x.experience.len(),
+                    config.max_bullets_per_job
+                ),
+            );
+        }
+    }
+
+    findings
+}
+
+/// The bullets of the professional experience and custom sections, with their paths.
+fn bullets(resume: &Resume) -> Vec<(String, &str)> {
+    let mut bullets = vec![];
+    for (i, x) in resume.professional_experience.iter().enumerate() {
+        bullets.extend(x.experience.iter().enumerate().map(|(j, x)| {
+            (
+                format!("professional_experience[{}].experience[{}]", i, j),
+                x.text.as_str(),
+            )
+        }));
+    }
+    for (i, section) in resume.custom_sections.iter().enumerate() {
+        for (j, item) in section.items.iter().enumerate() {
+            bullets.extend(item.bullets.iter().enumerate().map(|(k, x)| {
+                (
+                    format!("custom_sections[{}].items[{}].bullets[{}]", i, j, k),
+                    x.text.as_str(),
+                )
+            }));
+        }
+    }
+    bullets
+}
+
+/// The paths and names of the sections, and jobs, that don't have any content.
+fn empty_sections(resume: &Resume) -> Vec<(String, String)> {
+    let mut empty = vec![];
+    if resume
+        .objective
+        .as_ref()
+        .is_some_and(|x| x.objective.trim().is_empty())
+    {
+        empty.push((String::from("objective"), String::from("The objective")));
+    }
+    if resume.professional_experience.is_empty() {
+        empty.push((
+            String::from("professional_experience"),
+            String::from("The professional experience"),
+        ));
+    }
+    for (i, x) in resume.professional_experience.iter().enumerate() {
+        if x.experience.is_empty() {
+            empty.push((
+                format!("professional_experience[{}].experience", i),
+                format!("The experience of [{}]", x.position),
+            ));
+        }
+    }
+    if resume
+        .other_experience
+        .as_ref()
+        .is_some_and(|x| x.projects.is_empty())
+    {
+        empty.push((
+            String::from("other_experience"),
+            String::from("The projects section"),
+        ));
+    }
+    if resume
+        .technologies
+        .as_ref()
+        .is_some_and(|x| x.technologies.is_empty())
+    {
+        empty.push((
+            String::from("technologies"),
+            String::from("The technologies section"),
+        ));
+    }
+    for (i, x) in resume.custom_sections.iter().enumerate() {
+        if x.items.is_empty() {
+            empty.push((
+                format!("custom_sections[{}].title", i),
+                format!("The [{}] section", x.title),
+            ));
+        }
+    }
+    empty
+}
+
+fn starts_with_action_verb(text: &str, extra_verbs: &[String]) -> bool {
+    let word = match text.split_whitespace().next() {
+        Some(x) => x.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase(),
+        None => return false,
+    };
+    // E.g. "Co-founded"
+    let word = word.strip_prefix("co-").unwrap_or(&word);
+    let is_verb = |x: &str| {
+        ACTION_VERBS.contains(&x) || extra_verbs.iter().any(|v| v.eq_ignore_ascii_case(x))
+    };
+    if is_verb(word) || IRREGULAR_VERBS.contains(&word) {
+        return true;
+    }
+    let mut stems = vec![];
+    if let Some(x) = word.strip_suffix('s') {
+        stems.push(x.to_owned());
+        stems.extend(x.strip_suffix('e').map(str::to_owned));
+    }
+    if let Some(x) = word.strip_suffix('d') {
+        // "reduced"
+        stems.push(x.to_owned());
+        if let Some(x) = x.strip_suffix('e') {
+            // "designed"
+            stems.push(x.to_owned());
+            // "shipped"
+            let chars: Vec<char> = x.chars().collect();
+            if chars.len() > 2 && chars[chars.len() - 1] == chars[chars.len() - 2] {
+                stems.push(chars[..chars.len() - 1].iter().collect());
+            }
+            // "unified"
+            stems.extend(x.strip_suffix('i').map(|x| format!("{}y", x)));
+        }
+    }
+    stems.iter().any(|x| is_verb(x))
+}
+
+fn ends_with_period(text: &str) -> bool {
+    text.trim_end().ends_with('.')
+}
+
+fn first_person_word(text: &str) -> Option<&str> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+        .find(|x| {
+            let word = x.to_lowercase().replace('’', "'");
+            FIRST_PERSON.contains(&word.as_str())
+        })
+}
+
+/// The start of the text, to show which bullet a finding is about.
+fn excerpt(text: &str) -> String {
+    const LENGTH: usize = 40;
+    if text.chars().count() <= LENGTH {
+        text.to_owned()
+    } else {
+        format!("{}...", text.chars().take(LENGTH).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::check::{
+        check, check_resume, first_person_word, starts_with_action_verb, Finding, Rule,
+    };
+    use crate::config::arguments::Arguments;
+    use crate::config::format_config::{CheckConfig, Severity};
+    use crate::data::date::ResumeDate;
+    use crate::data::{
+        CustomSection, Objective, OtherExperience, ProfessionalExperience, ProjectInfo, Resume,
+        TaggedText,
+    };
+    use crate::error::Error;
+
+    fn job(
+        organization: Option<&str>,
+        start: ResumeDate,
+        end: ResumeDate,
+        bullets: &[&str],
+    ) -> ProfessionalExperience {
+        ProfessionalExperience {
+            organization: organization.map(String::from),
+            position: String::from("position"),
+            start,
+            end,
+            experience: bullets.iter().map(|x| TaggedText::from(*x)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn rules(findings: &[Finding]) -> Vec<(Rule, &str)> {
+        findings.iter().map(|x| (x.rule, x.path.as_str())).collect()
+    }
+
+    #[test]
+    fn test_action_verb() {
+        for x in [
+            "Built a thing",
+            "Led the team",
+            "Reduced latency by 50%",
+            "Designed the API",
+            "Shipped the feature",
+            "Unified the build",
+            "Manages the on-call rotation",
+            "Co-founded the club",
+            "\"Spearheaded\" the migration",
+        ] {
+            assert!(starts_with_action_verb(x, &[]), "{}", x);
+        }
+        for x in ["Responsible for the API", "Worked on the team", ""] {
+            assert!(!starts_with_action_verb(x, &[]), "{}", x);
+        }
+        assert!(starts_with_action_verb(
+            "Worked on the team",
+            &[String::from("Work")]
+        ));
+    }
+
+    #[test]
+    fn test_first_person() {
+        assert_eq!(first_person_word("I built the thing"), Some("I"));
+        assert_eq!(first_person_word("Built our CI"), Some("our"));
+        assert_eq!(
+            first_person_word("Built CI, which I’m proud of"),
+            Some("I’m")
+        );
+        assert_eq!(first_person_word("Expanded to the US"), None);
+        assert_eq!(first_person_word("Improved IMAP support"), None);
+    }
+
+    #[test]
+    fn test_check_resume() {
+        let resume = Resume {
+            objective: Some(Objective {
+                objective: String::from("I like building things"),
+            }),
+            professional_experience: vec![
+                job(
+                    Some("organizationA"),
+                    ResumeDate::new(2019, 6).unwrap(),
+                    ResumeDate::Present,
+                    &["Built a thing.", "Led a team.", "Responsible for the API."],
+                ),
+                job(
+                    Some("organizationB"),
+                    ResumeDate::new(2018, 1).unwrap(),
+                    ResumeDate::new(2019, 6).unwrap(),
+                    &[
+                        "Designed our API",
+                        "Wrote docs.",
+                        "Fixed bugs.",
+                        "Ran tests.",
+                    ],
+                ),
+                job(
+                    None,
+                    ResumeDate::new(2017, 1).unwrap(),
+                    ResumeDate::new(2018, 2).unwrap(),
+                    &[],
+                ),
+            ],
+            other_experience: Some(OtherExperience {
+                projects: vec![ProjectInfo {
+                    project_name: String::from("Foo"),
+                    description: String::from("A project"),
+                    ..Default::default()
+                }],
+            }),
+            custom_sections: vec![CustomSection {
+                title: String::from("Awards"),
+                items: vec![],
+            }],
+            ..Default::default()
+        };
+        let config = CheckConfig {
+            max_bullets_per_job: 3,
+            ..Default::default()
+        };
+        let findings = check_resume(&resume, &config);
+
+        assert_eq!(
+            rules(&findings),
+            vec![
+                (Rule::ActionVerb, "professional_experience[0].experience[2]"),
+                (
+                    Rule::TrailingPeriod,
+                    "professional_experience[1].experience[0]"
+                ),
+                (
+                    Rule::FirstPerson,
+                    "professional_experience[1].experience[0]"
+                ),
+                (Rule::FirstPerson, "objective.objective"),
+                (Rule::EmptySection, "professional_experience[2].experience"),
+                (Rule::EmptySection, "custom_sections[0].title"),
+                (
+                    Rule::ProjectUrl,
+                    "other_experience.projects[0].project_name"
+                ),
+                (Rule::OverlappingDates, "professional_experience[1].start"),
+                (Rule::BulletCount, "professional_experience[1].experience"),
+            ]
+        );
+        assert_eq!(
+            findings[7].message,
+            "The dates of [position at organizationB] (Jan 2018 - Jun 2019) overlap with [position at organizationB] (Jan 2017 - Feb 2018)"
+        );
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[2].severity, Severity::Error);
+
+        let config = CheckConfig {
+            action_verb: Severity::Off,
+            first_person: Severity::Warning,
+            empty_section: Severity::Off,
+            project_url: Severity::Error,
+            overlapping_dates: Severity::Off,
+            bullet_count: Severity::Off,
+            trailing_period: Severity::Off,
+            ..Default::default()
+        };
+        let findings = check_resume(&resume, &config);
+        assert_eq!(
+            findings
+                .iter()
+                .map(|x| (x.rule, x.severity))
+                .collect::<Vec<_>>(),
+            vec![
+                (Rule::FirstPerson, Severity::Warning),
+                (Rule::FirstPerson, Severity::Warning),
+                (Rule::ProjectUrl, Severity::Error),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bullet_length() {
+        let resume = Resume {
+            professional_experience: vec![job(
+                Some("organizationA"),
+                ResumeDate::new(2019, 6).unwrap(),
+                ResumeDate::Present,
+                &["Built a very long bullet that goes on and on"],
+            )],
+            ..Default::default()
+        };
+        let config = CheckConfig {
+            max_bullet_length: 20,
+            ..Default::default()
+        };
+        let findings = check_resume(&resume, &config);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "\"Built a very long bullet that goes on an...\" is 44 characters long, more than the 20 allowed by max_bullet_length"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let args = Arguments {
+            resume_input: String::from("tst/test_check.toml"),
+            ..Default::default()
+        };
+        let report = check(&args).unwrap();
+        assert_eq!(
+            report.to_string(),
+            "tst/test_check.toml:13:6: warning[action_verb]: \"Responsible for the API\" doesn't start with an action verb\n\
+             tst/test_check.toml:14:6: error[first_person]: \"Built our CI\" uses the first person \"our\"\n\
+             tst/test_check.toml:18:17: warning[project_url]: The project [Foo] has no URL\n\
+             1 error, 2 warnings"
+        );
+        assert!(matches!(report.into_result(), Err(Error::Check(_))));
+
+        let args = Arguments {
+            resume_input: String::from("tst/test_resume.toml"),
+            format_config: Some(String::from("tst/test_format_config.toml")),
+            ..Default::default()
+        };
+        let report = check(&args).unwrap();
+        assert!(report.findings.iter().all(|x| x.location.is_some()));
+        assert!(report.into_result().is_ok());
+    }
+}
@@ -1,16 +1,27 @@
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::ffi::OsString;
+use std::fmt;
+use std::path::Path;
 
 /// Resume Generator.
-#[derive(Debug, Parser, Default)]
+#[derive(Debug, Parser, Default, Clone)]
 #[clap(author, version, about, long_about = None)]
 pub struct Arguments {
     /// The resume data file.
-    #[clap(short = 'i', long)]
+    // Global arguments can't be required, so it's checked in `try_parse_args_from`
+    #[clap(
+        short = 'i',
+        long,
+        global = true,
+        default_value_t,
+        hide_default_value = true
+    )]
     pub resume_input: String,
 
-    /// The name to use for the generated resumes.
+    /// The name to use for the generated resumes. Required unless running `check`.
     #[clap(short, long)]
-    pub output_name: String,
+    pub output_name: Option<String>,
 
     /// The name to use for the generated cover letters. Defaults to appending '-cover_letter' to the output-name.
     #[clap(short, long)]
@@ -21,10 +32,198 @@ pub struct Arguments {
     pub output_dir: Option<String>,
 
     /// Configuration file for the various resume formats. Optional.
-    #[clap(short, long)]
+    #[clap(short, long, global = true)]
     pub format_config: Option<String>,
 
     /// Override the email in the resume config with this one.
     #[clap(short, long)]
     pub email: Option<String>,
+
+    /// The format of the resume data and format config files. Default: inferred from the file extension, falling back to toml. Use 'json-resume' to import a resume in the JSON Resume (jsonresume.org) schema.
+    #[clap(long, value_enum, global = true)]
+    pub input_format: Option<InputFormat>,
+
+    /// Comma separated list of the output formats to generate. Overrides the 'formats' list in the format config. Default: all formats.
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub format: Option<Vec<OutputFormat>>,
+
+    /// Render the resume with this template instead of the built-in formats. Either the path of a template file, or the name of a built-in template: text, markdown, latex or html. The output's extension is taken from the template's file name, e.g. 'resume.tex.jinja' renders a .tex file, which is then compiled to a pdf. Can't be combined with --format, and the 'formats' list in the format config is ignored.
+    #[clap(long, conflicts_with = "format")]
+    pub template: Option<String>,
+
+    /// Tailor the resume by only including the entries that match this variant. Either the name of a variant defined in the resume file, or a tag to include.
+    #[clap(long)]
+    pub variant: Option<String>,
+
+    /// Keep running and render the resume again whenever the resume data or format config file is saved.
+    #[clap(long)]
+    pub watch: bool,
+
+    /// Ignore keys in the resume data file that don't match any field instead of failing. Only applies to the toml, json and yaml formats, not to json-resume.
+    #[clap(long, global = true)]
+    pub lenient: bool,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Arguments {
+    /// Parse the command line arguments, exiting with the usage if they're invalid.
+    pub fn parse_args() -> Arguments {
+        Arguments::try_parse_args_from(std::env::args_os()).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parse the arguments. The input arguments can come before or after the subcommand.
+    /// Everything but `check` writes files, so it needs an output name.
+    pub fn try_parse_args_from<I, T>(args: I) -> Result<Arguments, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let args = Arguments::try_parse_from(args)?;
+        if args.resume_input.is_empty() {
+            return Err(Arguments::command().error(
+                ErrorKind::MissingRequiredArgument,
+                "the argument '--resume-input <RESUME_INPUT>' is required",
+            ));
+        }
+        if args.output_name.is_none() && !matches!(args.command, Some(Command::Check)) {
+            return Err(Arguments::command().error(
+                ErrorKind::MissingRequiredArgument,
+                "the argument '--output-name <OUTPUT_NAME>' is required to render the resume",
+            ));
+        }
+        Ok(args)
+    }
+
+    /// The name of the generated resumes, or "resume" if none was given.
+    pub fn output_name(&self) -> &str {
+        self.output_name.as_deref().unwrap_or("resume")
+    }
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Start a local server that previews the rendered resume and reloads it whenever the resume data or format config file is saved. The selected output files are written as usual.
+    Serve {
+        /// The port to listen on. The server only accepts connections from this machine.
+        #[clap(short, long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Check the resume for common problems, e.g. bullets that don't start with an action verb. The rules are set in the 'check_config' table of the format config. Exits with an error if any rule that's set to "error" fails.
+    Check,
+}
+
+/// The file formats supported for the resume data and format config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat {
+    Toml,
+    Json,
+    Yaml,
+    /// Json in the JSON Resume (jsonresume.org) schema. Only applies to the resume data file, the
+    /// format config file is read as plain json.
+    JsonResume,
+}
+
+impl InputFormat {
+    /// Infer the format from the extension of the given file name, if it's a known extension.
+    pub fn from_file_name(file_name: &str) -> Option<InputFormat> {
+        let ext = Path::new(file_name).extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "toml" => Some(InputFormat::Toml),
+            "json" => Some(InputFormat::Json),
+            "yaml" | "yml" => Some(InputFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InputFormat::Toml => "toml",
+            InputFormat::Json => "json",
+            InputFormat::Yaml => "yaml",
+            InputFormat::JsonResume => "json-resume",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The output formats that can be generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    Text,
+    Pdf,
+    Markdown,
+    Github,
+    Html,
+    JsonResume,
+}
+
+impl OutputFormat {
+    pub fn all() -> Vec<OutputFormat> {
+        vec![
+            OutputFormat::Text,
+            OutputFormat::Pdf,
+            OutputFormat::Markdown,
+            OutputFormat::Github,
+            OutputFormat::Html,
+            OutputFormat::JsonResume,
+        ]
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Pdf => "pdf",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Github => "github",
+            OutputFormat::Html => "html",
+            OutputFormat::JsonResume => "json-resume",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::{Arguments, Command};
+
+    #[test]
+    fn test_check_args() {
+        for args in [
+            vec!["resume_generator", "-i", "resume.toml", "check"],
+            vec![
+                "resume_generator",
+                "check",
+                "-i",
+                "resume.toml",
+                "--lenient",
+            ],
+        ] {
+            let args = Arguments::try_parse_args_from(args).unwrap();
+            assert!(matches!(args.command, Some(Command::Check)));
+            assert_eq!(args.resume_input, "resume.toml");
+            assert_eq!(args.output_name, None);
+        }
+    }
+
+    #[test]
+    fn test_render_args() {
+        let args =
+            Arguments::try_parse_args_from(["resume_generator", "-i", "resume.toml", "-o", "foo"])
+                .unwrap();
+        assert_eq!(args.output_name(), "foo");
+        assert!(Arguments::try_parse_args_from(["resume_generator", "-i", "resume.toml"]).is_err());
+        assert!(Arguments::try_parse_args_from(["resume_generator", "-o", "foo"]).is_err());
+        assert!(Arguments::try_parse_args_from(["resume_generator", "check"]).is_err());
+        assert!(
+            Arguments::try_parse_args_from(["resume_generator", "-i", "resume.toml", "serve"])
+                .is_err()
+        );
+    }
 }
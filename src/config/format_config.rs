@@ -1,39 +1,415 @@
-use crate::util::{string_from_file, toml_from_string};
+use crate::config::arguments::{InputFormat, OutputFormat};
+use crate::data::date::validate_date_format;
+use crate::error::Error;
+use crate::util::{default_date_format, deserialize_file};
+use std::fmt;
 
 impl FormatConfig {
-    pub fn read_from_config_file(file_name: &str) -> Result<FormatConfig, String> {
-        let s = string_from_file(file_name)?;
-        toml_from_string(&s)
+    pub fn read_from_config_file(
+        file_name: &str,
+        format: Option<InputFormat>,
+    ) -> Result<FormatConfig, Error> {
+        FormatConfig::verify(deserialize_file(file_name, format)?)
+            .map_err(|e| e.with_path(file_name))
     }
+
+    fn verify(config: FormatConfig) -> Result<FormatConfig, Error> {
+        [
+            &config.text_config.dates.date_format,
+            &config.pdf_config.dates.date_format,
+            &config.markdown_config.dates.date_format,
+            &config.html_config.dates.date_format,
+        ]
+        .iter()
+        .try_for_each(|x| validate_date_format(x))
+        .map_err(Error::format_config)?;
+        if config.pdf_config.contact_columns == 0 {
+            return Err(Error::format_config(String::from(
+                "pdf_config.contact_columns must be at least 1",
+            )));
+        }
+        if config.pdf_config.max_pages == Some(0) {
+            return Err(Error::format_config(String::from(
+                "pdf_config.max_pages must be at least 1",
+            )));
+        }
+        if config.pdf_config.timeout_secs == 0 {
+            return Err(Error::format_config(String::from(
+                "pdf_config.timeout_secs must be at least 1",
+            )));
+        }
+        if let Some(size) = &config.pdf_config.font_size {
+            if !size
+                .strip_suffix("pt")
+                .is_some_and(|x| x.parse::<f64>().is_ok_and(|x| x > 0.0))
+            {
+                return Err(Error::format_config(format!(
+                    "pdf_config.font_size must be a size in points, e.g. \"11pt\", not \"{}\"",
+                    size
+                )));
+            }
+        }
+        if config.pdf_config.engine == Engine::Native && !cfg!(feature = "native-pdf") {
+            return Err(Error::format_config(String::from(
+                "pdf_config.engine = \"native\" needs the native-pdf feature, which this build doesn't have",
+            )));
+        }
+        if config.pdf_config.engine == Engine::Native && config.pdf_config.margin_points().is_none()
+        {
+            return Err(Error::format_config(format!(
+                "pdf_config.margin must be a length in in, cm, mm or pt, e.g. \"0.75in\", to use the native pdf engine, not \"{}\"",
+                config.pdf_config.margin
+            )));
+        }
+        for (name, color) in [
+            ("accent_color", &config.pdf_config.accent_color),
+            ("link_color", &config.pdf_config.link_color),
+        ] {
+            if let Some(color) = color {
+                if color.len() != 6 || !color.chars().all(|x| x.is_ascii_hexdigit()) {
+                    return Err(Error::format_config(format!(
+                        "pdf_config.{} must be a hex color like \"1F4E79\", not \"{}\"",
+                        name, color
+                    )));
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// The sections to render and their order. The list in the format's own config takes
+    /// precedence over the global list, and the renderer's default is used if neither is set.
+    pub fn sections(
+        &self,
+        format_sections: &Option<Vec<Section>>,
+        default: &[Section],
+    ) -> Vec<Section> {
+        format_sections
+            .as_ref()
+            .or(self.sections.as_ref())
+            .cloned()
+            .unwrap_or_else(|| default.to_vec())
+    }
+
+    /// The contact fields to show and their order, resolved the same way as [Self::sections].
+    pub fn contact(
+        &self,
+        format_contact: &Option<Vec<ContactField>>,
+        default: &[ContactField],
+    ) -> Vec<ContactField> {
+        format_contact
+            .as_ref()
+            .or(self.contact.as_ref())
+            .cloned()
+            .unwrap_or_else(|| default.to_vec())
+    }
+}
+
+/// The sections of the resume that follow the name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Section {
+    PersonalInfo,
+    Objective,
+    Experience,
+    Projects,
+    Technologies,
+    Education,
+    Certifications,
+    /// All of the resume's custom sections, in the order they're defined in.
+    Custom,
+}
+
+/// The contact details in the personal info.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactField {
+    Email,
+    Phone,
+    Location,
+    Github,
+    Linkedin,
+    Website,
+    Pronouns,
+    /// All of the `other` personal info items.
+    Other,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct FormatConfig {
+    /// The output formats to generate. All formats are generated if this is omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formats: Option<Vec<OutputFormat>>,
+    /// Sort professional experience from the most recent to the oldest. Experience grouped under
+    /// one organization stays together.
+    #[serde(default)]
+    pub sort_experience: bool,
+    /// Leave out certifications that have expired instead of warning about them.
+    #[serde(default)]
+    pub hide_expired_certifications: bool,
+    /// Leave the GPA out of the education section.
+    #[serde(default)]
+    pub hide_gpa: bool,
+    /// The sections to render and their order, for all formats. Each renderer has its own default
+    /// order if this is omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<Section>>,
+    /// The contact fields to show and their order, for all formats. Each renderer has its own
+    /// default if this is omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Vec<ContactField>>,
     pub text_config: TextConfig,
     pub pdf_config: PdfConfig,
     pub markdown_config: MarkdownConfig,
+    #[serde(default)]
+    pub html_config: HtmlConfig,
+    #[serde(default)]
+    pub github_config: GitHubConfig,
+    #[serde(default)]
+    pub check_config: CheckConfig,
+}
+
+/// How a format shows dates. Flattened into the config of each format that shows dates.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DateConfig {
+    /// A chrono format string used for dates, e.g. "%b %Y" for "Jan 2020" or "%Y-%m" for "2020-01".
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    /// Add the duration to each time range, e.g. "Jan 2020 - Mar 2022 (2 yrs 3 mos)".
+    #[serde(default)]
+    pub show_duration: bool,
+}
+
+impl Default for DateConfig {
+    fn default() -> Self {
+        DateConfig {
+            date_format: default_date_format(),
+            show_duration: false,
+        }
+    }
+}
+
+/// What a format shows. Flattened into the config of each format, where it overrides the global
+/// lists in [FormatConfig].
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ContentConfig {
+    /// The sections to render and their order, e.g. `["experience", "education"]`. Sections that
+    /// aren't listed are hidden. Overrides the global `sections` list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<Section>>,
+    /// The contact fields to show and their order, e.g. `["email", "phone"]`. Fields that aren't
+    /// listed are hidden. Overrides the global `contact` list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Vec<ContactField>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TextConfig {
     pub width: usize,
+    #[serde(flatten)]
+    pub dates: DateConfig,
+    #[serde(flatten)]
+    pub content: ContentConfig,
 }
 
 impl Default for TextConfig {
     fn default() -> Self {
-        TextConfig { width: 100 }
+        TextConfig {
+            width: 100,
+            dates: DateConfig::default(),
+            content: ContentConfig::default(),
+        }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PdfConfig {
+    #[serde(default)]
+    pub engine: Engine,
+    /// Extra arguments for the LaTeX engine, e.g. `["-shell-escape"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub engine_args: Vec<String>,
+    /// The directory the LaTeX engine runs in, which keeps its .aux, .log and .out files out of
+    /// the output directory. Defaults to a new directory in the system's temp directory for each
+    /// run, which is removed afterwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_dir: Option<String>,
+    /// Copy the .tex file to the output directory along with the pdf.
+    #[serde(default)]
+    pub keep_tex: bool,
+    /// How long each run of the LaTeX engine can take before it's stopped.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
     pub margin: String,
+    /// The number of contact fields on each row of the header.
+    #[serde(default = "default_contact_columns")]
+    pub contact_columns: usize,
+    #[serde(flatten)]
+    pub dates: DateConfig,
+    #[serde(flatten)]
+    pub content: ContentConfig,
+    /// The most pages the resume should take up. Longer resumes are reported as set by `page_limit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+    /// How a resume longer than `max_pages` is reported: "off", "warning" or "error".
+    #[serde(default = "default_page_limit")]
+    pub page_limit: Severity,
+    /// Shrink the margin, spacing and font size step by step until the resume fits on `max_pages`.
+    #[serde(default)]
+    pub shrink_to_fit: bool,
+    #[serde(default)]
+    pub font: Font,
+    /// The base font size, e.g. "11pt". The LaTeX default of 10pt is used if it isn't set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<String>,
+    #[serde(default)]
+    pub paper: Paper,
+    /// A hex color, e.g. "1F4E79", for the section headers and the rule under the contact details.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent_color: Option<String>,
+    /// A hex color for the links. How it's used depends on `link_style`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_color: Option<String>,
+    #[serde(default)]
+    pub link_style: LinkStyle,
+    #[serde(default)]
+    pub theme: Theme,
+    /// Pass the resume's text to LaTeX unescaped, so it can contain LaTeX markup.
+    #[serde(default)]
+    pub raw_latex: bool,
+}
+
+impl PdfConfig {
+    /// The margin in points, or None if it isn't a length in in, cm, mm or pt.
+    pub fn margin_points(&self) -> Option<f32> {
+        length_points(&self.margin)
+    }
+
+    /// The base font size in points.
+    pub fn font_size_points(&self) -> f32 {
+        self.font_size
+            .as_deref()
+            .and_then(length_points)
+            .unwrap_or(DEFAULT_FONT_SIZE_POINTS)
+    }
+}
+
+/// LaTeX's default font size.
+const DEFAULT_FONT_SIZE_POINTS: f32 = 10.0;
+
+/// Convert a length like "0.75in" or "2cm" to points.
+pub(crate) fn length_points(length: &str) -> Option<f32> {
+    let length = length.trim();
+    let (value, points_per_unit) = [
+        ("in", 72.0),
+        ("cm", 72.0 / 2.54),
+        ("mm", 72.0 / 25.4),
+        ("pt", 1.0),
+    ]
+    .iter()
+    .find_map(|(unit, points)| length.strip_suffix(unit).map(|x| (x, points)))?;
+    let value = value.trim().parse::<f32>().ok().filter(|x| *x >= 0.0)?;
+    Some(value * points_per_unit)
+}
+
+/// How the pdf is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Engine {
+    /// Write a LaTeX file and run `pdflatex` on it.
+    #[default]
+    Pdflatex,
+    /// Run `xelatex` instead, which reads Unicode input and can use system fonts.
+    Xelatex,
+    /// Run `lualatex` instead, which reads Unicode input and can use system fonts.
+    Lualatex,
+    /// Run `latexmk`, which runs pdflatex as many times as needed itself.
+    Latexmk,
+    /// Lay out and write the pdf directly, with embedded fonts. Doesn't need a LaTeX install, but
+    /// needs the `native-pdf` feature.
+    Native,
+}
+
+impl Engine {
+    /// Whether the engine reads Unicode input and sets up its fonts with fontspec.
+    pub fn is_unicode(self) -> bool {
+        matches!(self, Engine::Xelatex | Engine::Lualatex)
+    }
+}
+
+/// The font family of the pdf, named after its LaTeX package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Font {
+    #[default]
+    Times,
+    Helvet,
+    Lmodern,
+    Palatino,
+    Charter,
+    Bookman,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Paper {
+    #[default]
+    Letter,
+    A4,
+}
+
+/// How links are shown in the pdf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStyle {
+    /// A box around the link, which isn't printed. This is the hyperref default.
+    #[default]
+    Box,
+    /// Colored link text.
+    Color,
+    /// A line under the link, which isn't printed.
+    Underline,
+    /// No marking at all.
+    Hidden,
+}
+
+/// The built-in designs of the pdf. They all lay out the same sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    /// A centered name and section headers, with the contact details in rows under the name.
+    #[default]
+    Classic,
+    /// A left-aligned name and section headers, with the contact details and technologies in a
+    /// sidebar next to the other sections.
+    Modern,
+    /// Like `classic`, with a smaller font and less space between sections and list items.
+    Compact,
 }
 
 impl Default for PdfConfig {
     fn default() -> Self {
         PdfConfig {
+            engine: Engine::default(),
+            engine_args: Vec::new(),
+            build_dir: None,
+            keep_tex: false,
+            timeout_secs: default_timeout_secs(),
             margin: String::from("0.75in"),
+            contact_columns: default_contact_columns(),
+            dates: DateConfig::default(),
+            content: ContentConfig::default(),
+            max_pages: None,
+            page_limit: default_page_limit(),
+            shrink_to_fit: false,
+            font: Font::default(),
+            font_size: None,
+            paper: Paper::default(),
+            accent_color: None,
+            link_color: None,
+            link_style: LinkStyle::default(),
+            theme: Theme::default(),
+            raw_latex: false,
         }
     }
 }
@@ -41,17 +417,121 @@ impl Default for PdfConfig {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MarkdownConfig {
     pub width: usize,
+    #[serde(flatten)]
+    pub dates: DateConfig,
+    #[serde(flatten)]
+    pub content: ContentConfig,
 }
 
 impl Default for MarkdownConfig {
     fn default() -> Self {
-        MarkdownConfig { width: 120 }
+        MarkdownConfig {
+            width: 120,
+            dates: DateConfig::default(),
+            content: ContentConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct HtmlConfig {
+    #[serde(flatten)]
+    pub dates: DateConfig,
+    #[serde(flatten)]
+    pub content: ContentConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct GitHubConfig {
+    #[serde(flatten)]
+    pub content: ContentConfig,
+}
+
+/// How a problem found by the `check` command is reported. Errors make the command fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Off,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Off => "off",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The rules of the `check` command. Each rule is set to "off", "warning" or "error".
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckConfig {
+    /// Bullets that don't start with an action verb, e.g. "Responsible for ...".
+    pub action_verb: Severity,
+    /// Verbs accepted at the start of a bullet in addition to the built-in ones.
+    pub action_verbs: Vec<String>,
+    /// Bullets longer than `max_bullet_length` characters.
+    pub bullet_length: Severity,
+    pub max_bullet_length: usize,
+    /// Some bullets end with a period and others don't.
+    pub trailing_period: Severity,
+    /// "I", "my", "we" and so on in bullets, the objective or project descriptions.
+    pub first_person: Severity,
+    /// Sections and jobs without any content.
+    pub empty_section: Severity,
+    /// Projects without a URL.
+    pub project_url: Severity,
+    /// Jobs whose dates overlap.
+    pub overlapping_dates: Severity,
+    /// Jobs with more than `max_bullets_per_job` bullets.
+    pub bullet_count: Severity,
+    pub max_bullets_per_job: usize,
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        CheckConfig {
+            action_verb: Severity::Warning,
+            action_verbs: vec![],
+            bullet_length: Severity::Warning,
+            max_bullet_length: 150,
+            trailing_period: Severity::Warning,
+            first_person: Severity::Error,
+            empty_section: Severity::Error,
+            project_url: Severity::Warning,
+            overlapping_dates: Severity::Warning,
+            bullet_count: Severity::Warning,
+            max_bullets_per_job: 6,
+        }
     }
 }
 
+fn default_contact_columns() -> usize {
+    2
+}
+
+fn default_page_limit() -> Severity {
+    Severity::Error
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
 #[cfg(test)]
 mod test {
-    use crate::config::format_config::FormatConfig;
+    use crate::config::arguments::{InputFormat, OutputFormat};
+    use crate::config::format_config::{
+        length_points, ContactField, Engine, Font, FormatConfig, LinkStyle, Paper, Section,
+        Severity, Theme,
+    };
+    use crate::error::Error;
+    use crate::util::json_from_string;
 
     #[test]
     fn test_format_config_defaults() {
@@ -59,13 +539,282 @@ mod test {
         assert_eq!(c.text_config.width, 100);
         assert_eq!(c.pdf_config.margin, "0.75in");
         assert_eq!(c.markdown_config.width, 120);
+        assert_eq!(c.html_config.dates.date_format, "%b %Y");
+        assert!(!c.html_config.dates.show_duration);
+        assert!(!c.sort_experience);
+        assert!(!c.hide_expired_certifications);
+        assert!(!c.hide_gpa);
+        assert!(c.formats.is_none());
+        assert!(c.pdf_config.max_pages.is_none());
+        assert_eq!(c.pdf_config.page_limit, Severity::Error);
+        assert!(!c.pdf_config.shrink_to_fit);
+        assert_eq!(c.pdf_config.font, Font::Times);
+        assert_eq!(c.pdf_config.paper, Paper::Letter);
+        assert_eq!(c.pdf_config.link_style, LinkStyle::Box);
+        assert_eq!(c.pdf_config.theme, Theme::Classic);
+        assert_eq!(c.pdf_config.engine, Engine::Pdflatex);
+        assert!(c.pdf_config.engine_args.is_empty());
+        assert!(c.pdf_config.build_dir.is_none());
+        assert!(!c.pdf_config.keep_tex);
+        assert_eq!(c.pdf_config.timeout_secs, 60);
+    }
+
+    #[test]
+    fn test_verify_typography() {
+        for pdf_config in [
+            r#"{"margin": "", "font_size": "11"}"#,
+            r#"{"margin": "", "font_size": "-1pt"}"#,
+            r##"{"margin": "", "accent_color": "#1F4E79"}"##,
+            r#"{"margin": "", "link_color": "blue"}"#,
+            r#"{"margin": "", "timeout_secs": 0}"#,
+        ] {
+            let c = json_from_string::<FormatConfig>(&format!(
+                r#"{{"text_config": {{"width": 1}}, "pdf_config": {}, "markdown_config": {{"width": 1}}}}"#,
+                pdf_config
+            ))
+            .unwrap();
+            assert!(matches!(
+                FormatConfig::verify(c),
+                Err(Error::FormatConfig { .. })
+            ));
+        }
+    }
+
+    #[cfg(feature = "native-pdf")]
+    #[test]
+    fn test_native_engine() {
+        let c = json_from_string::<FormatConfig>(
+            r#"{"text_config": {"width": 1}, "pdf_config": {"engine": "native", "margin": "2cm", "font_size": "11pt"}, "markdown_config": {"width": 1}}"#,
+        )
+        .unwrap();
+        assert_eq!(c.pdf_config.engine, Engine::Native);
+        assert_eq!(c.pdf_config.font_size_points(), 11.0);
+        let c = FormatConfig::verify(c).unwrap();
+        assert!((c.pdf_config.margin_points().unwrap() - 56.69).abs() < 0.01);
+
+        // The native engine can't lay out the page with a margin it can't read
+        let c = json_from_string::<FormatConfig>(
+            r#"{"text_config": {"width": 1}, "pdf_config": {"engine": "native", "margin": "1em"}, "markdown_config": {"width": 1}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            FormatConfig::verify(c),
+            Err(Error::FormatConfig { .. })
+        ));
+    }
+
+    #[cfg(not(feature = "native-pdf"))]
+    #[test]
+    fn test_native_engine_without_feature() {
+        let c = json_from_string::<FormatConfig>(
+            r#"{"text_config": {"width": 1}, "pdf_config": {"engine": "native", "margin": "2cm"}, "markdown_config": {"width": 1}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            FormatConfig::verify(c),
+            Err(Error::FormatConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn test_length_points() {
+        assert_eq!(length_points("0.75in"), Some(54.0));
+        assert_eq!(length_points(" 10pt "), Some(10.0));
+        assert_eq!(length_points("25.4mm"), Some(72.0));
+        assert_eq!(length_points("1em"), None);
+        assert_eq!(length_points("-1in"), None);
     }
 
     #[test]
     fn test_deserialize_toml() {
-        let c = FormatConfig::read_from_config_file("tst/test_format_config.toml").unwrap();
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.toml", None).unwrap();
         assert_eq!(c.text_config.width, 25);
         assert_eq!(c.pdf_config.margin, "0.8in");
+        assert_eq!(c.pdf_config.max_pages, Some(1));
+        assert_eq!(c.pdf_config.page_limit, Severity::Warning);
+        assert!(c.pdf_config.shrink_to_fit);
+        assert_eq!(c.pdf_config.font, Font::Palatino);
+        assert_eq!(c.pdf_config.font_size.as_deref(), Some("11pt"));
+        assert_eq!(c.pdf_config.paper, Paper::A4);
+        assert_eq!(c.pdf_config.accent_color.as_deref(), Some("1F4E79"));
+        assert_eq!(c.pdf_config.link_style, LinkStyle::Underline);
+        assert_eq!(c.pdf_config.theme, Theme::Compact);
+        assert_eq!(c.pdf_config.engine, Engine::Latexmk);
+        assert_eq!(c.pdf_config.engine_args, vec!["-silent"]);
+        assert_eq!(c.pdf_config.build_dir.as_deref(), Some("build"));
+        assert!(c.pdf_config.keep_tex);
+        assert_eq!(c.pdf_config.timeout_secs, 30);
         assert_eq!(c.markdown_config.width, 30);
+        assert_eq!(
+            c.formats,
+            Some(vec![OutputFormat::Text, OutputFormat::JsonResume])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_check_config() {
+        let c = FormatConfig::default().check_config;
+        assert_eq!(c.action_verb, Severity::Warning);
+        assert_eq!(c.first_person, Severity::Error);
+        assert_eq!(c.max_bullet_length, 150);
+
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.toml", None)
+            .unwrap()
+            .check_config;
+        assert_eq!(c.action_verb, Severity::Off);
+        assert_eq!(c.first_person, Severity::Warning);
+        assert_eq!(c.empty_section, Severity::Error);
+        assert_eq!(c.max_bullets_per_job, 4);
+        assert_eq!(c.max_bullet_length, 150);
+        assert_eq!(c.action_verbs, vec!["work"]);
+    }
+
+    #[test]
+    fn test_deserialize_dates() {
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.toml", None).unwrap();
+        assert!(c.sort_experience);
+        assert!(c.hide_expired_certifications);
+        assert_eq!(c.text_config.dates.date_format, "%Y-%m");
+        assert!(c.text_config.dates.show_duration);
+        assert_eq!(c.pdf_config.dates.date_format, "%b %Y");
+        assert!(!c.pdf_config.dates.show_duration);
+
+        let c = json_from_string::<FormatConfig>(
+            r#"{"text_config": {"width": 1, "date_format": "%Q"}, "pdf_config": {"margin": ""}, "markdown_config": {"width": 1}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            FormatConfig::verify(c),
+            Err(Error::FormatConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sections() {
+        let default = [Section::PersonalInfo, Section::Experience];
+        let mut c = FormatConfig::default();
+        assert_eq!(
+            c.sections(&c.text_config.content.sections, &default),
+            default
+        );
+
+        c.sections = Some(vec![Section::Education, Section::Experience]);
+        assert_eq!(
+            c.sections(&c.text_config.content.sections, &default),
+            vec![Section::Education, Section::Experience]
+        );
+
+        c.text_config.content.sections = Some(vec![Section::Projects]);
+        assert_eq!(
+            c.sections(&c.text_config.content.sections, &default),
+            vec![Section::Projects]
+        );
+        assert_eq!(
+            c.sections(&c.pdf_config.content.sections, &default),
+            vec![Section::Education, Section::Experience]
+        );
+
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.toml", None).unwrap();
+        assert_eq!(
+            c.sections,
+            Some(vec![
+                Section::PersonalInfo,
+                Section::Education,
+                Section::Experience,
+                Section::Projects,
+                Section::Technologies
+            ])
+        );
+        assert_eq!(
+            c.github_config.content.sections,
+            Some(vec![Section::Projects, Section::PersonalInfo])
+        );
+    }
+
+    #[test]
+    fn test_contact() {
+        let default = [ContactField::Email, ContactField::Phone];
+        let mut c = FormatConfig::default();
+        assert_eq!(c.pdf_config.contact_columns, 2);
+        assert_eq!(c.contact(&c.text_config.content.contact, &default), default);
+
+        c.contact = Some(vec![ContactField::Github]);
+        c.html_config.content.contact = Some(vec![ContactField::Other, ContactField::Email]);
+        assert_eq!(
+            c.contact(&c.text_config.content.contact, &default),
+            vec![ContactField::Github]
+        );
+        assert_eq!(
+            c.contact(&c.html_config.content.contact, &default),
+            vec![ContactField::Other, ContactField::Email]
+        );
+
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.toml", None).unwrap();
+        assert_eq!(c.pdf_config.contact_columns, 3);
+        assert_eq!(
+            c.pdf_config.content.contact,
+            Some(vec![
+                ContactField::Email,
+                ContactField::Phone,
+                ContactField::Location,
+                ContactField::Linkedin
+            ])
+        );
+        assert_eq!(
+            c.github_config.content.contact,
+            Some(vec![ContactField::Github, ContactField::Website])
+        );
+        assert!(c.text_config.content.contact.is_none());
+
+        let c = json_from_string::<FormatConfig>(
+            r#"{"text_config": {"width": 1}, "pdf_config": {"margin": "", "contact_columns": 0}, "markdown_config": {"width": 1}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            FormatConfig::verify(c),
+            Err(Error::FormatConfig { .. })
+        ));
+
+        let c = json_from_string::<FormatConfig>(
+            r#"{"text_config": {"width": 1}, "pdf_config": {"margin": "", "max_pages": 0}, "markdown_config": {"width": 1}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            FormatConfig::verify(c),
+            Err(Error::FormatConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_json() {
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.json", None).unwrap();
+        assert_eq!(c.text_config.width, 25);
+        assert_eq!(c.pdf_config.margin, "0.8in");
+        assert_eq!(c.markdown_config.width, 30);
+        assert_eq!(
+            c.formats,
+            Some(vec![OutputFormat::Text, OutputFormat::JsonResume])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_yaml() {
+        let c = FormatConfig::read_from_config_file("tst/test_format_config.yaml", None).unwrap();
+        assert_eq!(c.text_config.width, 25);
+        assert_eq!(c.pdf_config.margin, "0.8in");
+        assert_eq!(c.markdown_config.width, 30);
+        assert_eq!(
+            c.formats,
+            Some(vec![OutputFormat::Text, OutputFormat::JsonResume])
+        );
+    }
+
+    #[test]
+    fn test_deserialize_wrong_format() {
+        let c = FormatConfig::read_from_config_file(
+            "tst/test_format_config.toml",
+            Some(InputFormat::Json),
+        );
+        assert!(matches!(c, Err(Error::Json { .. })));
     }
 }
@@ -1,6 +1,6 @@
 use crate::config::arguments::Arguments;
 use crate::config::format_config::FormatConfig;
-use clap::Parser;
+use crate::error::Error;
 
 pub mod arguments;
 pub mod format_config;
@@ -12,11 +12,15 @@ pub struct Config {
 }
 
 impl Config {
-    pub fn new_and_parse_args() -> Result<Config, String> {
-        let args = Arguments::parse();
+    pub fn new_and_parse_args() -> Result<Config, Error> {
+        Config::from_args(Arguments::parse_args())
+    }
 
+    /// Build the config from already parsed arguments, reading the format config file if one is
+    /// given.
+    pub fn from_args(args: Arguments) -> Result<Config, Error> {
         let format_config = if let Some(file_name) = &args.format_config {
-            FormatConfig::read_from_config_file(file_name)?
+            FormatConfig::read_from_config_file(file_name, args.input_format)?
         } else {
             Default::default()
         };
@@ -0,0 +1,184 @@
+//! Year-month dates for professional experience and education.
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, Local, NaiveDate};
+use std::convert::TryFrom;
+use std::fmt;
+
+const PRESENT: &str = "present";
+
+/// A year and month, e.g. `2020-01`, or `present` for ongoing experience.
+///
+/// Dates are ordered chronologically, and `present` comes after every other date.
+#[derive(
+    Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum ResumeDate {
+    /// Always the first day of the month.
+    Month(NaiveDate),
+    #[default]
+    Present,
+}
+
+impl ResumeDate {
+    pub fn new(year: i32, month: u32) -> Option<ResumeDate> {
+        NaiveDate::from_ymd_opt(year, month, 1).map(ResumeDate::Month)
+    }
+
+    /// Parse `YYYY-MM` or `present`. A full `YYYY-MM-DD` date is also accepted, but the day is
+    /// ignored.
+    pub fn parse(s: &str) -> Option<ResumeDate> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case(PRESENT) {
+            return Some(ResumeDate::Present);
+        }
+        NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
+            .ok()
+            .and_then(|x| ResumeDate::new(x.year(), x.month()))
+    }
+
+    /// The month this date refers to. `present` is the current month.
+    pub fn month(&self) -> NaiveDate {
+        match self {
+            ResumeDate::Month(date) => *date,
+            ResumeDate::Present => {
+                let now = Local::now();
+                NaiveDate::from_ymd_opt(now.year(), now.month(), 1).unwrap()
+            }
+        }
+    }
+
+    /// Format the date with a chrono format string, e.g. `%b %Y` for "Jan 2020". `present` is
+    /// always formatted as "Present". The format should be checked with [validate_date_format]
+    /// first.
+    pub fn format(&self, date_format: &str) -> String {
+        match self {
+            ResumeDate::Month(date) => date.format(date_format).to_string(),
+            ResumeDate::Present => String::from("Present"),
+        }
+    }
+}
+
+impl fmt::Display for ResumeDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeDate::Month(date) => write!(f, "{}", date.format("%Y-%m")),
+            ResumeDate::Present => write!(f, "{}", PRESENT),
+        }
+    }
+}
+
+impl TryFrom<String> for ResumeDate {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        ResumeDate::parse(&s).ok_or_else(|| {
+            format!(
+                "invalid date \"{}\", expected \"YYYY-MM\" or \"{}\"",
+                s, PRESENT
+            )
+        })
+    }
+}
+
+impl From<ResumeDate> for String {
+    fn from(date: ResumeDate) -> Self {
+        date.to_string()
+    }
+}
+
+/// The number of months from the start to the end, counting both the start and end month.
+pub fn months_between(start: &ResumeDate, end: &ResumeDate) -> u32 {
+    let (start, end) = (start.month(), end.month());
+    let months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    months.max(0) as u32 + 1
+}
+
+/// A duration in the style of "2 yrs 3 mos".
+pub fn duration_string(start: &ResumeDate, end: &ResumeDate) -> String {
+    let months = months_between(start, end);
+    let (years, months) = (months / 12, months % 12);
+    let mut parts = vec![];
+    match years {
+        0 => {}
+        1 => parts.push(String::from("1 yr")),
+        _ => parts.push(format!("{} yrs", years)),
+    }
+    match months {
+        0 => {}
+        1 => parts.push(String::from("1 mo")),
+        _ => parts.push(format!("{} mos", months)),
+    }
+    parts.join(" ")
+}
+
+/// Check that the chrono format string can be used to format a date.
+pub fn validate_date_format(date_format: &str) -> Result<(), String> {
+    if StrftimeItems::new(date_format).any(|x| matches!(x, Item::Error)) {
+        Err(format!("Invalid date format: {}", date_format))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data::date::{duration_string, months_between, validate_date_format, ResumeDate};
+    use std::convert::TryFrom;
+
+    fn date(year: i32, month: u32) -> ResumeDate {
+        ResumeDate::new(year, month).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ResumeDate::parse("2020-01"), Some(date(2020, 1)));
+        assert_eq!(ResumeDate::parse("2020-1"), Some(date(2020, 1)));
+        assert_eq!(ResumeDate::parse("2020-12-25"), Some(date(2020, 12)));
+        assert_eq!(ResumeDate::parse("present"), Some(ResumeDate::Present));
+        assert_eq!(ResumeDate::parse("Present"), Some(ResumeDate::Present));
+        assert_eq!(ResumeDate::parse("2020-13"), None);
+        assert_eq!(ResumeDate::parse("Jan 2020"), None);
+        assert_eq!(
+            ResumeDate::try_from(String::from("foo")).unwrap_err(),
+            "invalid date \"foo\", expected \"YYYY-MM\" or \"present\""
+        );
+    }
+
+    #[test]
+    fn test_order() {
+        assert!(date(2019, 12) < date(2020, 1));
+        assert!(date(2020, 1) < ResumeDate::Present);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(date(2020, 1).format("%b %Y"), "Jan 2020");
+        assert_eq!(date(2020, 1).format("%Y-%m"), "2020-01");
+        assert_eq!(ResumeDate::Present.format("%Y-%m"), "Present");
+        assert_eq!(date(2020, 1).to_string(), "2020-01");
+        assert_eq!(ResumeDate::Present.to_string(), "present");
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(months_between(&date(2020, 1), &date(2020, 1)), 1);
+        assert_eq!(months_between(&date(2020, 1), &date(2019, 1)), 1);
+        assert_eq!(duration_string(&date(2020, 1), &date(2020, 1)), "1 mo");
+        assert_eq!(duration_string(&date(2020, 1), &date(2020, 11)), "11 mos");
+        assert_eq!(duration_string(&date(2020, 1), &date(2020, 12)), "1 yr");
+        assert_eq!(
+            duration_string(&date(2020, 1), &date(2022, 3)),
+            "2 yrs 3 mos"
+        );
+        assert_eq!(duration_string(&date(2020, 1), &date(2021, 1)), "1 yr 1 mo");
+    }
+
+    #[test]
+    fn test_validate_date_format() {
+        assert!(validate_date_format("%b %Y").is_ok());
+        assert!(validate_date_format("%Y-%m").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+    }
+}
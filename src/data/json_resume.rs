@@ -0,0 +1,906 @@
+//! Conversion between [Resume] and the JSON Resume schema (https://jsonresume.org/schema).
+//!
+//! Only the parts of the schema that have an equivalent in [Resume] are modeled here. Data that
+//! can't be represented in the schema is stored in the [JSON_RESUME_EXTENSION_KEY] block so it
+//! survives a round trip.
+use crate::data::{
+    date::ResumeDate, variant::Variant, Certification, CoverLetter, CustomSection, Education,
+    Objective, OtherExperience, OtherPersonalInfo, PersonalInfo, ProfessionalExperience,
+    ProjectInfo, Resume, TaggedText, Technologies,
+};
+use crate::error::Error;
+use crate::util::add_https_to_url;
+use serde::de::{self, Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
+
+/// The key of the block that holds the data that doesn't map to the JSON Resume schema.
+pub const JSON_RESUME_EXTENSION_KEY: &str = "x-resume_generator";
+
+const GITHUB_NETWORK: &str = "GitHub";
+const LINKEDIN_NETWORK: &str = "LinkedIn";
+const TECHNOLOGIES_SKILL: &str = "Technologies";
+
+/// Read a schema date. Besides the formats [ResumeDate::parse] accepts, the schema allows a bare
+/// year, `YYYY`, which is read as January of that year.
+fn deserialize_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ResumeDate>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| {
+            let year = s.trim();
+            let date = if year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()) {
+                year.parse().ok().and_then(|year| ResumeDate::new(year, 1))
+            } else {
+                ResumeDate::parse(&s)
+            };
+            date.ok_or_else(|| {
+                de::Error::custom(format!(
+                    "invalid date \"{}\", expected \"YYYY-MM-DD\", \"YYYY-MM\" or \"YYYY\"",
+                    s
+                ))
+            })
+        })
+        .transpose()
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResume {
+    #[serde(default)]
+    pub basics: JsonResumeBasics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<JsonResumeWork>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<JsonResumeEducation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<JsonResumeSkill>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<JsonResumeProject>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub certificates: Vec<JsonResumeCertificate>,
+    #[serde(
+        rename = "x-resume_generator",
+        default,
+        skip_serializing_if = "JsonResumeExtension::is_empty"
+    )]
+    pub extension: JsonResumeExtension,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeBasics {
+    #[serde(default)]
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// The personal website.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<JsonResumeLocation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<JsonResumeProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+impl JsonResumeLocation {
+    /// Split a location like "Seattle, WA" into the city and the region.
+    fn new(location: &str) -> JsonResumeLocation {
+        let (city, region) = match location.split_once(", ") {
+            Some((city, region)) => (city, Some(region.to_owned())),
+            None => (location, None),
+        };
+        JsonResumeLocation {
+            city: Some(city.to_owned()),
+            region,
+        }
+    }
+
+    fn to_location_string(&self) -> Option<String> {
+        let parts = [&self.city, &self.region]
+            .iter()
+            .filter_map(|x| x.as_deref())
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<&str>>();
+        Some(parts.join(", ")).filter(|x| !x.is_empty())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeProfile {
+    pub network: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonResumeWork {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub position: String,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_date: Option<ResumeDate>,
+    /// Omitted for ongoing work.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_date: Option<ResumeDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+    #[serde(
+        rename = "x-resume_generator",
+        default,
+        skip_serializing_if = "JsonResumeTags::is_empty"
+    )]
+    pub extension: JsonResumeTags,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonResumeEducation {
+    #[serde(default)]
+    pub institution: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub study_type: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub end_date: Option<ResumeDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub courses: Vec<String>,
+    #[serde(
+        rename = "x-resume_generator",
+        default,
+        skip_serializing_if = "JsonResumeEducationExtension::is_empty"
+    )]
+    pub extension: JsonResumeEducationExtension,
+}
+
+/// The parts of an education entry that have no place in the schema.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeEducationExtension {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minor: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub honors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extras: Option<Vec<TaggedText>>,
+    /// The tags of the entry and the tagged version of `courses`.
+    #[serde(default, skip_serializing_if = "JsonResumeTags::is_empty")]
+    pub tags: JsonResumeTags,
+}
+
+impl JsonResumeEducationExtension {
+    fn is_empty(&self) -> bool {
+        self.location.is_none()
+            && self.minor.is_none()
+            && self.honors.is_empty()
+            && self.extras.is_none()
+            && self.tags.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeSkill {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    #[serde(
+        rename = "x-resume_generator",
+        default,
+        skip_serializing_if = "JsonResumeTags::is_empty"
+    )]
+    pub extension: JsonResumeTags,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeProject {
+    #[serde(default)]
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(
+        rename = "x-resume_generator",
+        default,
+        skip_serializing_if = "JsonResumeTags::is_empty"
+    )]
+    pub extension: JsonResumeTags,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeCertificate {
+    #[serde(default)]
+    pub name: String,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub date: Option<ResumeDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(
+        rename = "x-resume_generator",
+        default,
+        skip_serializing_if = "JsonResumeCertificateExtension::is_empty"
+    )]
+    pub extension: JsonResumeCertificateExtension,
+}
+
+/// The parts of a certification that have no place in the schema.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeCertificateExtension {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<ResumeDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl JsonResumeCertificateExtension {
+    fn is_empty(&self) -> bool {
+        self.expires.is_none() && self.credential_id.is_none() && self.tags.is_empty()
+    }
+}
+
+/// The tags of a JSON Resume item, which have no place in the schema.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeTags {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The tagged version of the item's list of strings (e.g. `highlights`). Only set if at least
+    /// one of the entries has tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tagged: Vec<TaggedText>,
+}
+
+impl JsonResumeTags {
+    fn new(tags: &[String], list: &[TaggedText]) -> JsonResumeTags {
+        JsonResumeTags {
+            tags: tags.to_vec(),
+            tagged: if list.iter().any(|x| !x.tags.is_empty()) {
+                list.to_vec()
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.tagged.is_empty()
+    }
+
+    /// Use the tagged version of the list if there is one, otherwise the plain strings.
+    fn restore(&mut self, list: Vec<String>) -> Vec<TaggedText> {
+        if self.tagged.is_empty() {
+            list.into_iter().map(TaggedText::from).collect()
+        } else {
+            std::mem::take(&mut self.tagged)
+        }
+    }
+}
+
+/// Resume data that doesn't have a place in the JSON Resume schema.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeExtension {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pronouns: Option<String>,
+    /// Written by older versions, before education was a list. Applies to the first entry.
+    #[serde(default, skip_serializing)]
+    pub education_location: Option<String>,
+    /// Written by older versions, before education was a list. Applies to the first entry.
+    #[serde(default, skip_serializing)]
+    pub education_extras: Option<Vec<TaggedText>>,
+    /// Only projects that are hidden from at least one output are listed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<JsonResumeProjectExtension>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_letter: Option<CoverLetter>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variants: BTreeMap<String, Variant>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_sections: Vec<CustomSection>,
+}
+
+impl JsonResumeExtension {
+    fn is_empty(&self) -> bool {
+        self.pronouns.is_none()
+            && self.projects.is_empty()
+            && self.cover_letter.is_none()
+            && self.variants.is_empty()
+            && self.custom_sections.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct JsonResumeProjectExtension {
+    pub project_name: String,
+    pub include_on_resume: bool,
+    pub include_on_github: bool,
+}
+
+impl From<&Resume> for JsonResume {
+    fn from(resume: &Resume) -> Self {
+        let mut profiles = vec![];
+        if !resume.personal_info.github.is_empty() {
+            profiles.push(JsonResumeProfile {
+                network: String::from(GITHUB_NETWORK),
+                username: resume
+                    .personal_info
+                    .github
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .map(String::from),
+                url: Some(add_https_to_url(&resume.personal_info.github)),
+            });
+        }
+        if let Some(linkedin) = &resume.personal_info.linkedin {
+            profiles.push(JsonResumeProfile {
+                network: String::from(LINKEDIN_NETWORK),
+                username: None,
+                url: Some(add_https_to_url(linkedin)),
+            });
+        }
+        if let Some(other) = &resume.personal_info.other {
+            profiles.extend(other.iter().map(|x| JsonResumeProfile {
+                network: x.item.clone(),
+                username: None,
+                url: x.url.clone(),
+            }));
+        }
+
+        let basics = JsonResumeBasics {
+            name: resume.name.clone(),
+            email: Some(resume.personal_info.email.clone()).filter(|x| !x.is_empty()),
+            phone: resume.personal_info.phone.clone(),
+            url: resume.personal_info.website.clone(),
+            summary: resume.objective.as_ref().map(|x| x.objective.clone()),
+            location: resume
+                .personal_info
+                .location
+                .as_deref()
+                .map(JsonResumeLocation::new),
+            profiles,
+        };
+
+        // Entries without an organization are grouped under the previous entry's organization.
+        // The schema has no such grouping, so fill in the organization from the previous entry.
+        let mut organization = None;
+        let mut location = None;
+        let work = resume
+            .professional_experience
+            .iter()
+            .map(|x| {
+                if x.organization.is_some() {
+                    organization = x.organization.clone();
+                    location = x.location.clone();
+                }
+                JsonResumeWork {
+                    name: organization.clone(),
+                    location: location.clone(),
+                    position: x.position.clone(),
+                    start_date: Some(x.start),
+                    end_date: Some(x.end).filter(|x| *x != ResumeDate::Present),
+                    highlights: texts(&x.experience),
+                    extension: JsonResumeTags::new(&x.tags, &x.experience),
+                }
+            })
+            .collect();
+
+        let education = resume
+            .education
+            .iter()
+            .map(|x| JsonResumeEducation {
+                institution: x.school.clone(),
+                area: Some(x.major.clone()),
+                study_type: x.degree.clone(),
+                end_date: x.graduation,
+                score: x.gpa.clone(),
+                courses: texts(&x.coursework),
+                extension: JsonResumeEducationExtension {
+                    location: Some(x.location.clone()).filter(|x| !x.is_empty()),
+                    minor: x.minor.clone(),
+                    honors: x.honors.clone(),
+                    extras: x.extras.clone(),
+                    tags: JsonResumeTags::new(&x.tags, &x.coursework),
+                },
+            })
+            .collect();
+
+        let skills = resume
+            .technologies
+            .iter()
+            .map(|x| JsonResumeSkill {
+                name: String::from(TECHNOLOGIES_SKILL),
+                keywords: texts(&x.technologies),
+                extension: JsonResumeTags::new(&[], &x.technologies),
+            })
+            .collect();
+
+        let all_projects = resume
+            .other_experience
+            .as_ref()
+            .map(|x| x.get_projects())
+            .unwrap_or_default();
+        let projects = all_projects
+            .iter()
+            .map(|x| JsonResumeProject {
+                name: x.project_name.clone(),
+                description: Some(x.description.clone()),
+                url: Some(x.url.clone()).filter(|x| !x.is_empty()),
+                extension: JsonResumeTags::new(&x.tags, &[]),
+            })
+            .collect();
+
+        let certificates = resume
+            .certifications
+            .iter()
+            .map(|x| JsonResumeCertificate {
+                name: x.name.clone(),
+                date: Some(x.issued),
+                issuer: Some(x.issuer.clone()).filter(|x| !x.is_empty()),
+                url: x.url.clone(),
+                extension: JsonResumeCertificateExtension {
+                    expires: x.expires,
+                    credential_id: x.credential_id.clone(),
+                    tags: x.tags.clone(),
+                },
+            })
+            .collect();
+
+        let extension = JsonResumeExtension {
+            pronouns: resume.personal_info.pronouns.clone(),
+            education_location: None,
+            education_extras: None,
+            projects: all_projects
+                .iter()
+                .filter(|x| !x.include_on_resume || !x.include_on_github)
+                .map(|x| JsonResumeProjectExtension {
+                    project_name: x.project_name.clone(),
+                    include_on_resume: x.include_on_resume,
+                    include_on_github: x.include_on_github,
+                })
+                .collect(),
+            cover_letter: resume.cover_letter.as_ref().map(|x| CoverLetter {
+                salutation: x.salutation.clone(),
+                closing: x.closing.clone(),
+                paragraphs: x.paragraphs.clone(),
+                ..Default::default()
+            }),
+            variants: resume.variants.clone(),
+            custom_sections: resume.custom_sections.clone(),
+        };
+
+        JsonResume {
+            basics,
+            work,
+            education,
+            skills,
+            projects,
+            certificates,
+            extension,
+        }
+    }
+}
+
+impl TryFrom<JsonResume> for Resume {
+    type Error = Error;
+
+    fn try_from(json: JsonResume) -> Result<Self, Self::Error> {
+        let mut github = String::new();
+        let mut linkedin = None;
+        let mut other = vec![];
+        for profile in json.basics.profiles {
+            if profile.network.eq_ignore_ascii_case(GITHUB_NETWORK) && github.is_empty() {
+                let username = profile.username;
+                github = profile
+                    .url
+                    .map(|x| strip_scheme(&x))
+                    .or_else(|| username.map(|x| format!("github.com/{}", x)))
+                    .unwrap_or_default();
+            } else if profile.network.eq_ignore_ascii_case(LINKEDIN_NETWORK) && linkedin.is_none() {
+                let username = profile.username;
+                linkedin = profile
+                    .url
+                    .map(|x| strip_scheme(&x))
+                    .or_else(|| username.map(|x| format!("linkedin.com/in/{}", x)));
+            } else {
+                other.push(OtherPersonalInfo {
+                    item: profile.network,
+                    url: profile.url,
+                });
+            }
+        }
+
+        let JsonResumeExtension {
+            pronouns,
+            education_location,
+            education_extras,
+            projects: project_extensions,
+            cover_letter,
+            variants,
+            custom_sections,
+        } = json.extension;
+        let personal_info = PersonalInfo {
+            email: json.basics.email.unwrap_or_default(),
+            github,
+            phone: json.basics.phone,
+            location: json.basics.location.and_then(|x| x.to_location_string()),
+            linkedin,
+            website: json.basics.url,
+            pronouns,
+            other: Some(other).filter(|x| !x.is_empty()),
+        };
+
+        // Undo the organization fill-in done on export so consecutive positions at the same
+        // organization are grouped together again.
+        let mut previous: Option<(Option<String>, Option<String>)> = None;
+        let professional_experience = json
+            .work
+            .into_iter()
+            .map(|mut x| {
+                let experience = x.extension.restore(x.highlights);
+                let current = (x.name, x.location);
+                let same_organization = current.0.is_some() && previous.as_ref() == Some(&current);
+                previous = Some(current.clone());
+                let (organization, location) = if same_organization {
+                    (None, None)
+                } else {
+                    current
+                };
+                let position = x.position;
+                let start = x.start_date.ok_or_else(|| {
+                    Error::Validation(format!("Work [{}] has no startDate", position))
+                })?;
+                Ok(ProfessionalExperience {
+                    organization,
+                    location,
+                    position,
+                    start,
+                    end: x.end_date.unwrap_or(ResumeDate::Present),
+                    experience,
+                    tags: x.extension.tags,
+                })
+            })
+            .collect::<Result<Vec<ProfessionalExperience>, Error>>()?;
+
+        let mut education = json
+            .education
+            .into_iter()
+            .map(|mut x| Education {
+                school: x.institution,
+                location: x.extension.location.unwrap_or_default(),
+                degree: x.study_type,
+                major: x.area.unwrap_or_default(),
+                minor: x.extension.minor,
+                graduation: x.end_date,
+                gpa: x.score,
+                honors: x.extension.honors,
+                coursework: x.extension.tags.restore(x.courses),
+                extras: x.extension.extras,
+                tags: x.extension.tags.tags,
+            })
+            .collect::<Vec<Education>>();
+        if let Some(first) = education.first_mut() {
+            if let Some(location) = education_location {
+                first.location = location;
+            }
+            if education_extras.is_some() {
+                first.extras = education_extras;
+            }
+        }
+
+        // The schema groups skills by category, but the resume only has a flat list.
+        let mut seen = HashSet::new();
+        let technologies = json
+            .skills
+            .into_iter()
+            .flat_map(|mut x| {
+                if x.keywords.is_empty() {
+                    vec![TaggedText::from(x.name)]
+                } else {
+                    x.extension.restore(x.keywords)
+                }
+            })
+            .filter(|x| seen.insert(x.text.clone()))
+            .collect::<Vec<TaggedText>>();
+
+        let projects = json
+            .projects
+            .into_iter()
+            .map(|x| {
+                let flags = project_extensions.iter().find(|e| e.project_name == x.name);
+                ProjectInfo {
+                    url: x.url.unwrap_or_default(),
+                    description: x.description.unwrap_or_default(),
+                    include_on_resume: flags.map(|e| e.include_on_resume).unwrap_or(true),
+                    include_on_github: flags.map(|e| e.include_on_github).unwrap_or(true),
+                    project_name: x.name,
+                    tags: x.extension.tags,
+                }
+            })
+            .collect::<Vec<ProjectInfo>>();
+
+        let certifications = json
+            .certificates
+            .into_iter()
+            .map(|x| {
+                let name = x.name;
+                let issued = x.date.ok_or_else(|| {
+                    Error::Validation(format!("Certificate [{}] has no date", name))
+                })?;
+                Ok(Certification {
+                    name,
+                    issuer: x.issuer.unwrap_or_default(),
+                    issued,
+                    expires: x.extension.expires,
+                    credential_id: x.extension.credential_id,
+                    url: x.url,
+                    tags: x.extension.tags,
+                })
+            })
+            .collect::<Result<Vec<Certification>, Error>>()?;
+
+        Ok(Resume {
+            name: json.basics.name,
+            personal_info,
+            objective: json.basics.summary.map(|objective| Objective { objective }),
+            professional_experience,
+            education,
+            other_experience: Some(OtherExperience { projects }).filter(|x| !x.projects.is_empty()),
+            technologies: Some(Technologies { technologies })
+                .filter(|x| !x.technologies.is_empty()),
+            certifications,
+            custom_sections,
+            cover_letter,
+            variants,
+        })
+    }
+}
+
+fn texts(x: &[TaggedText]) -> Vec<String> {
+    x.iter().map(|x| x.text.clone()).collect()
+}
+
+fn strip_scheme(url: &str) -> String {
+    url.trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::{Arguments, InputFormat};
+    use crate::config::Config;
+    use crate::data::date::ResumeDate;
+    use crate::data::json_resume::JsonResume;
+    use crate::data::{Resume, TaggedText};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_round_trip() {
+        let resume =
+            Resume::read_from_config_file("tst/test_resume.toml", &Default::default()).unwrap();
+        let json = JsonResume::from(&resume);
+
+        assert_eq!(json.basics.name, "Foo Bar");
+        assert_eq!(json.basics.profiles.len(), 4);
+        assert_eq!(json.basics.profiles[0].network, "GitHub");
+        assert_eq!(
+            json.basics.profiles[0].url,
+            Some(String::from("https://github.com/foo"))
+        );
+        assert_eq!(json.basics.profiles[0].username, Some(String::from("foo")));
+        assert_eq!(json.basics.profiles[1].network, "LinkedIn");
+        assert_eq!(
+            json.basics.profiles[1].url,
+            Some(String::from("https://linkedin.com/in/foo"))
+        );
+        assert_eq!(json.basics.phone, Some(String::from("555-555-5555")));
+        assert_eq!(
+            json.basics.location.as_ref().unwrap().city,
+            Some(String::from("Seattle"))
+        );
+        assert_eq!(json.extension.pronouns, Some(String::from("they/them")));
+        assert_eq!(json.work.len(), 2);
+        assert_eq!(json.work[1].highlights, vec!["FooB", "BarB", "BazB"]);
+        assert_eq!(json.skills[0].keywords, vec!["Foo", "Bar", "Baz"]);
+        assert_eq!(json.education.len(), 2);
+        assert_eq!(json.education[0].study_type, Some(String::from("B.S.")));
+        assert_eq!(json.education[0].score, Some(String::from("3.8")));
+        assert_eq!(json.education[0].courses, vec!["Course A", "Course B"]);
+        assert_eq!(
+            json.education[0].extension.location,
+            Some(String::from("location"))
+        );
+        assert_eq!(
+            json.education[0].extension.minor,
+            Some(String::from("minor"))
+        );
+        assert!(json.extension.cover_letter.is_some());
+        assert_eq!(json.certificates.len(), 2);
+        assert_eq!(json.certificates[0].date, ResumeDate::new(2021, 3));
+        assert_eq!(
+            json.certificates[0].extension.credential_id,
+            Some(String::from("ABC-123"))
+        );
+
+        let round_trip = Resume::try_from(json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&round_trip).unwrap(),
+            serde_json::to_value(&resume).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_grouped_experience() {
+        let mut resume =
+            Resume::read_from_config_file("tst/test_resume.toml", &Default::default()).unwrap();
+        resume.professional_experience[1].organization = None;
+        resume.professional_experience[1].location = None;
+        resume.other_experience.as_mut().unwrap().projects[0].include_on_github = false;
+
+        let json = JsonResume::from(&resume);
+        assert_eq!(json.work[1].name, Some(String::from("organizationA")));
+        assert_eq!(json.work[1].location, Some(String::from("locationA")));
+        assert_eq!(json.extension.projects.len(), 1);
+
+        let round_trip = Resume::try_from(json).unwrap();
+        assert_eq!(round_trip.professional_experience[1].organization, None);
+        assert_eq!(round_trip.professional_experience[1].location, None);
+        let project = &round_trip.other_experience.unwrap().projects[0];
+        assert!(project.include_on_resume);
+        assert!(!project.include_on_github);
+    }
+
+    #[test]
+    fn test_import_year_only_dates() {
+        let json: JsonResume = serde_json::from_str(
+            r#"{
+                "work": [{"position": "a", "startDate": "2019", "endDate": "2020-06-15"}],
+                "education": [{"institution": "b", "endDate": "2017"}],
+                "certificates": [{"name": "c", "date": "2021"}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(json.work[0].start_date, ResumeDate::new(2019, 1));
+        assert_eq!(json.work[0].end_date, ResumeDate::new(2020, 6));
+        assert_eq!(json.education[0].end_date, ResumeDate::new(2017, 1));
+        assert_eq!(json.certificates[0].date, ResumeDate::new(2021, 1));
+
+        let error = serde_json::from_str::<JsonResume>(r#"{"work": [{"startDate": "19"}]}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("invalid date \"19\""), "{}", error);
+    }
+
+    #[test]
+    fn test_import_single_education_extension() {
+        let json: JsonResume = serde_json::from_str(
+            r#"{
+                "education": [{"institution": "a"}, {"institution": "b"}],
+                "x-resume_generator": {"education_location": "location", "education_extras": ["Foo"]}
+            }"#,
+        )
+        .unwrap();
+        let resume = Resume::try_from(json).unwrap();
+        assert_eq!(resume.education[0].location, "location");
+        assert_eq!(resume.education[0].get_extras(), [TaggedText::from("Foo")]);
+        assert_eq!(resume.education[1].location, "");
+        assert_eq!(resume.education[1].extras, None);
+
+        let json = serde_json::to_value(JsonResume::from(&resume)).unwrap();
+        assert!(json["x-resume_generator"]
+            .get("education_location")
+            .is_none());
+        assert_eq!(
+            json["education"][0]["x-resume_generator"]["location"],
+            "location"
+        );
+    }
+
+    #[test]
+    fn test_import() {
+        let config = Config {
+            args: Arguments {
+                input_format: Some(InputFormat::JsonResume),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let resume = Resume::read_from_config_file("tst/test_json_resume.json", &config).unwrap();
+
+        assert_eq!(resume.name, "Foo Bar");
+        assert_eq!(resume.personal_info.email, "foo@example.com");
+        assert_eq!(resume.personal_info.github, "github.com/foo");
+        assert_eq!(
+            resume.personal_info.linkedin,
+            Some(String::from("linkedin.com/in/foo"))
+        );
+        assert_eq!(
+            resume.personal_info.phone,
+            Some(String::from("555-555-5555"))
+        );
+        assert_eq!(
+            resume.personal_info.location,
+            Some(String::from("Seattle, WA"))
+        );
+        assert_eq!(
+            resume.personal_info.website,
+            Some(String::from("https://foo.example.com"))
+        );
+        assert!(resume.personal_info.other.is_none());
+        assert_eq!(resume.objective.unwrap().objective, "objective");
+
+        assert_eq!(resume.professional_experience.len(), 2);
+        assert_eq!(
+            resume.professional_experience[0].organization,
+            Some(String::from("organizationA"))
+        );
+        assert_eq!(resume.professional_experience[0].end, ResumeDate::Present);
+        assert_eq!(resume.professional_experience[1].organization, None);
+        assert_eq!(
+            resume.professional_experience[1].start,
+            ResumeDate::new(2018, 1).unwrap()
+        );
+
+        assert_eq!(resume.education.len(), 1);
+        let education = &resume.education[0];
+        assert_eq!(education.school, "school");
+        assert_eq!(education.degree, Some(String::from("Bachelor")));
+        assert_eq!(education.major, "major");
+        assert_eq!(education.location, "");
+
+        assert_eq!(
+            resume.technologies.unwrap().get_technologies(),
+            vec!["Foo", "Bar", "Baz", "Things"]
+        );
+        let projects = resume.other_experience.unwrap().projects;
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].url, "https://example.com");
+        assert_eq!(resume.certifications.len(), 1);
+        assert_eq!(
+            resume.certifications[0].issued,
+            ResumeDate::new(2021, 3).unwrap()
+        );
+        assert_eq!(resume.certifications[0].expires, None);
+        assert!(resume.cover_letter.is_none());
+    }
+}
@@ -0,0 +1,228 @@
+//! Inline markup in the resume's text: `**bold**`, `*emphasis*`, `` `code` `` and `[links](url)`.
+//! A '\' in front of a markup character keeps it as a literal character.
+
+/// A piece of text with inline markup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Bold(Vec<Inline>),
+    Emphasis(Vec<Inline>),
+    Code(String),
+    Link { text: Vec<Inline>, url: String },
+}
+
+const MARKUP_CHARS: [char; 4] = ['*', '`', '[', ']'];
+
+/// Parse the markup. Delimiters without a closing delimiter are kept as text.
+pub fn parse(s: &str) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    let mut text = String::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let parsed = match c {
+            '\\' => match rest[1..].chars().next() {
+                Some(x) if MARKUP_CHARS.contains(&x) => {
+                    text.push(x);
+                    rest = &rest[2..];
+                    continue;
+                }
+                _ => None,
+            },
+            '`' => delimited(&rest[1..], "`")
+                .map(|(inner, after)| (Inline::Code(inner.to_string()), after)),
+            '*' if rest.starts_with("**") => delimited(&rest[2..], "**")
+                .map(|(inner, after)| (Inline::Bold(parse(inner)), after)),
+            '*' => delimited(&rest[1..], "*")
+                .map(|(inner, after)| (Inline::Emphasis(parse(inner)), after)),
+            '[' => link(rest),
+            _ => None,
+        };
+        match parsed {
+            Some((inline, after)) => {
+                if !text.is_empty() {
+                    inlines.push(Inline::Text(std::mem::take(&mut text)));
+                }
+                inlines.push(inline);
+                rest = after;
+            }
+            None => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !text.is_empty() {
+        inlines.push(Inline::Text(text));
+    }
+    inlines
+}
+
+/// Find the text up to the closing delimiter. Like in Markdown, the text can't start or end with
+/// whitespace, so "5 * 3 * 2" isn't emphasized.
+fn delimited<'a>(s: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let end = s.find(delimiter)?;
+    let inner = &s[..end];
+    if inner.is_empty()
+        || inner.starts_with(char::is_whitespace)
+        || inner.ends_with(char::is_whitespace)
+    {
+        return None;
+    }
+    Some((inner, &s[end + delimiter.len()..]))
+}
+
+fn link(s: &str) -> Option<(Inline, &str)> {
+    let text_end = s.find("](")?;
+    let url_end = text_end + s[text_end..].find(')')?;
+    let url = &s[text_end + 2..url_end];
+    if url.is_empty() || url.contains(char::is_whitespace) {
+        return None;
+    }
+    let text = Inline::Link {
+        text: parse(&s[1..text_end]),
+        url: url.to_string(),
+    };
+    Some((text, &s[url_end + 1..]))
+}
+
+/// Render the markup with a function for each kind of markup. `text` is applied to all literal
+/// text, including code, and `link` gets the rendered link text and the url.
+pub struct Style<'a> {
+    pub text: &'a dyn Fn(&str) -> String,
+    pub bold: &'a dyn Fn(&str) -> String,
+    pub emphasis: &'a dyn Fn(&str) -> String,
+    pub code: &'a dyn Fn(&str) -> String,
+    pub link: &'a dyn Fn(&str, &str) -> String,
+}
+
+pub fn render(inlines: &[Inline], style: &Style) -> String {
+    inlines
+        .iter()
+        .map(|x| match x {
+            Inline::Text(s) => (style.text)(s),
+            Inline::Bold(x) => (style.bold)(&render(x, style)),
+            Inline::Emphasis(x) => (style.emphasis)(&render(x, style)),
+            Inline::Code(s) => (style.code)(&(style.text)(s)),
+            Inline::Link { text, url } => (style.link)(&render(text, style), url),
+        })
+        .collect()
+}
+
+/// The text without markup. Links are written as "text (url)".
+pub fn plain_text(s: &str) -> String {
+    render(
+        &parse(s),
+        &Style {
+            text: &|x| x.to_string(),
+            bold: &|x| x.to_string(),
+            emphasis: &|x| x.to_string(),
+            code: &|x| x.to_string(),
+            link: &|text, url| format!("{} ({})", text, url),
+        },
+    )
+}
+
+/// The text as it appears once the markup is rendered, without the urls of links.
+pub fn visible_text(s: &str) -> String {
+    render(
+        &parse(s),
+        &Style {
+            text: &|x| x.to_string(),
+            bold: &|x| x.to_string(),
+            emphasis: &|x| x.to_string(),
+            code: &|x| x.to_string(),
+            link: &|text, _| text.to_string(),
+        },
+    )
+}
+
+/// The number of characters of a word that are visible once its markup is rendered. A word can
+/// hold part of a span, e.g. "**very" in "**very bold**", so instead of parsing the word this drops
+/// the urls of links and all delimiter characters.
+pub fn visible_width(word: &str) -> usize {
+    let mut visible = String::new();
+    let mut rest = word;
+    while let Some(start) = rest.find("](") {
+        visible.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find(')')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    visible.push_str(rest);
+    let width = visible
+        .chars()
+        .filter(|x| !['*', '`', '['].contains(x))
+        .count();
+    // A word made of delimiters only, like the '*' in "5 * 3", is literal text
+    if width == 0 {
+        word.chars().count()
+    } else {
+        width
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data::markup::{parse, plain_text, visible_text, visible_width, Inline};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("Cut **build times** by *half* with `cargo` and [sccache](example.com)"),
+            vec![
+                Inline::Text(String::from("Cut ")),
+                Inline::Bold(vec![Inline::Text(String::from("build times"))]),
+                Inline::Text(String::from(" by ")),
+                Inline::Emphasis(vec![Inline::Text(String::from("half"))]),
+                Inline::Text(String::from(" with ")),
+                Inline::Code(String::from("cargo")),
+                Inline::Text(String::from(" and ")),
+                Inline::Link {
+                    text: vec![Inline::Text(String::from("sccache"))],
+                    url: String::from("example.com")
+                },
+            ]
+        );
+        assert_eq!(
+            parse("**[bold link](example.com)**"),
+            vec![Inline::Bold(vec![Inline::Link {
+                text: vec![Inline::Text(String::from("bold link"))],
+                url: String::from("example.com")
+            }])]
+        );
+    }
+
+    #[test]
+    fn test_parse_literal() {
+        for s in ["5 * 3 * 2", "**open", "[not a link] (example.com)", "a `b"] {
+            assert_eq!(parse(s), vec![Inline::Text(String::from(s))]);
+        }
+        assert_eq!(
+            parse("\\*not emphasis\\* C:\\Users"),
+            vec![Inline::Text(String::from("*not emphasis* C:\\Users"))]
+        );
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            plain_text("**Led** a *team* using `Rust` ([more](example.com))"),
+            "Led a team using Rust (more (example.com))"
+        );
+        assert_eq!(
+            visible_text("**Led** a *team* using `Rust` ([more](example.com))"),
+            "Led a team using Rust (more)"
+        );
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("**bold**"), 4);
+        assert_eq!(visible_width("**very"), 4);
+        assert_eq!(visible_width("bold**,"), 5);
+        assert_eq!(visible_width("[link](example.com),"), 5);
+        assert_eq!(visible_width("[link](example.com)"), 4);
+        assert_eq!(visible_width("`code`"), 4);
+        assert_eq!(visible_width("*"), 1);
+    }
+}
@@ -1,12 +1,34 @@
+use crate::config::arguments::InputFormat;
+use crate::config::format_config::ContactField;
 use crate::config::Config;
-use crate::util::{default_true, string_from_file, toml_from_string};
-use std::collections::HashSet;
+use crate::data::date::ResumeDate;
+use crate::data::json_resume::JsonResume;
+use crate::data::unknown_keys::{find_unknown_keys, struct_fields};
+use crate::error::Error;
+use crate::util::{
+    add_https_to_url, current_month, default_true, deserialize_string, input_format,
+    string_from_file,
+};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryInto;
+use std::fmt;
+use std::ops::Deref;
+use std::path::PathBuf;
+
+pub mod date;
+pub mod json_resume;
+pub mod markup;
+pub mod source_map;
+pub mod unknown_keys;
+pub mod variant;
 
 impl Resume {
-    pub fn read_from_config_file(file_name: &str, config: &Config) -> Result<Resume, String> {
-        let s = string_from_file(file_name)?;
-        // todo: support other config file formats?
-        let mut resume: Resume = toml_from_string(&s)?;
+    pub fn read_from_config_file(file_name: &str, config: &Config) -> Result<Resume, Error> {
+        let source = string_from_file(file_name)?;
+        let mut resume = Resume::from_source(&source, file_name, config)?;
         if let Some(email) = &config.args.email {
             resume.personal_info.email = email.to_owned()
         }
@@ -15,24 +37,163 @@ impl Resume {
             cover_letter.email = Some(resume.personal_info.email.clone());
             cover_letter.paragraphs.retain(|x| !x.is_empty())
         }
+        if let Some(variant) = &config.args.variant {
+            resume.apply_variant(variant)?;
+        }
+        if config.format_config.sort_experience {
+            resume.sort_experience();
+        }
+        let today = current_month();
+        if config.format_config.hide_expired_certifications {
+            resume.certifications.retain(|x| !x.is_expired(&today));
+        }
         Resume::verify(resume)
     }
 
-    fn verify(resume: Resume) -> Result<Resume, String> {
+    /// Problems with the resume that don't stop it from being rendered, like certifications that
+    /// have expired.
+    pub fn warnings(&self) -> Vec<String> {
+        let today = current_month();
+        self.certifications
+            .iter()
+            .filter(|x| x.is_expired(&today))
+            .map(|x| {
+                format!(
+                    "The certification [{}] expired in {}. Set hide_expired_certifications in the format config to leave it out.",
+                    x.name,
+                    x.expires.unwrap_or_default()
+                )
+            })
+            .collect()
+    }
+
+    /// Parse the contents of the resume data file as they're written: unlike
+    /// [Resume::read_from_config_file], no variant is applied, nothing is sorted, hidden or
+    /// overridden, and the resume isn't verified.
+    pub fn from_source(source: &str, file_name: &str, config: &Config) -> Result<Resume, Error> {
+        let format = input_format(file_name, config.args.input_format);
+        if format == InputFormat::JsonResume {
+            return deserialize_string::<JsonResume>(source, format)
+                .map_err(|e| e.with_path(file_name))?
+                .try_into();
+        }
+        let resume = deserialize_string(source, format).map_err(|e| e.with_path(file_name));
+        if !config.args.lenient {
+            // Also checked if the file couldn't be read, since a misspelled section often shows
+            // up as a missing one
+            let keys = find_unknown_keys::<Resume>(source, format, &Resume::field_names());
+            if !keys.is_empty() {
+                return Err(Error::UnknownKeys {
+                    path: PathBuf::from(file_name),
+                    keys,
+                });
+            }
+        }
+        resume
+    }
+
+    /// The names of the keys of each table in the resume data file, which unknown keys are
+    /// compared against to suggest the key that was meant.
+    pub(crate) fn field_names() -> Vec<&'static str> {
+        let mut fields = [
+            struct_fields::<Resume>(),
+            struct_fields::<PersonalInfo>(),
+            struct_fields::<OtherPersonalInfo>(),
+            struct_fields::<Objective>(),
+            struct_fields::<ProfessionalExperience>(),
+            struct_fields::<Education>(),
+            struct_fields::<OtherExperience>(),
+            struct_fields::<ProjectInfo>(),
+            struct_fields::<Technologies>(),
+            struct_fields::<Certification>(),
+            struct_fields::<CustomSection>(),
+            struct_fields::<CustomSectionItem>(),
+            struct_fields::<CoverLetter>(),
+            struct_fields::<variant::Variant>(),
+        ]
+        .concat();
+        // The keys of tagged text, which isn't deserialized as a struct
+        fields.extend(["text", "tags"]);
+        fields.sort_unstable();
+        fields.dedup();
+        fields
+    }
+
+    pub(crate) fn verify(resume: Resume) -> Result<Resume, Error> {
         if let Some(tech) = &resume.technologies {
             Resume::verify_technologies(tech)?;
         }
+        resume
+            .professional_experience
+            .iter()
+            .try_for_each(Resume::verify_experience_dates)?;
+        resume
+            .certifications
+            .iter()
+            .try_for_each(Resume::verify_certification_dates)?;
         Ok(resume)
     }
 
-    fn verify_technologies(tech: &Technologies) -> Result<(), String> {
+    fn verify_certification_dates(cert: &Certification) -> Result<(), Error> {
+        if cert.issued == ResumeDate::Present {
+            return Err(Error::Validation(format!(
+                "Certification [{}] needs an issue date, not \"{}\"",
+                cert.name, cert.issued
+            )));
+        }
+        match cert.expires {
+            Some(expires) if cert.issued > expires => Err(Error::Validation(format!(
+                "Certification [{}] is issued ({}) after it expires ({})",
+                cert.name, cert.issued, expires
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn verify_experience_dates(exp: &ProfessionalExperience) -> Result<(), Error> {
+        if exp.start > exp.end {
+            return Err(Error::Validation(format!(
+                "Professional experience [{}] starts ({}) after it ends ({})",
+                exp.position, exp.start, exp.end
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sort professional experience from the most recent to the oldest, by end date and then by
+    /// start date. Entries without an organization are grouped under the entry before them, so
+    /// each group is sorted as a whole by its most recent entry and the group keeps its
+    /// organization and location on its first entry.
+    pub fn sort_experience(&mut self) {
+        let mut groups: Vec<Vec<ProfessionalExperience>> = vec![];
+        for e in self.professional_experience.drain(..) {
+            match groups.last_mut() {
+                Some(group) if e.organization.is_none() => group.push(e),
+                _ => groups.push(vec![e]),
+            }
+        }
+
+        let key = |e: &ProfessionalExperience| std::cmp::Reverse((e.end, e.start));
+        for group in groups.iter_mut() {
+            let organization = group[0].organization.take();
+            let location = group[0].location.take();
+            group.sort_by_key(key);
+            group[0].organization = organization;
+            group[0].location = location;
+        }
+        groups.sort_by_key(|group| key(&group[0]));
+
+        self.professional_experience = groups.into_iter().flatten().collect();
+    }
+
+    fn verify_technologies(tech: &Technologies) -> Result<(), Error> {
         let mut tech_set = HashSet::new();
-        for t in tech.technologies.iter() {
+        for t in tech.get_technologies() {
             if tech_set.contains(t) {
-                return Err(format!(
+                return Err(Error::Validation(format!(
                     "Technologies list contains a duplicate entry: {}",
                     t
-                ));
+                )));
             } else {
                 tech_set.insert(t);
             }
@@ -47,25 +208,228 @@ pub struct Resume {
     pub personal_info: PersonalInfo,
     pub objective: Option<Objective>,
     pub professional_experience: Vec<ProfessionalExperience>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub education: Option<Education>,
+    /// A single table is also accepted, which is how education was written before it was a list.
+    #[serde(
+        default,
+        deserialize_with = "crate::util::one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub education: Vec<Education>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub other_experience: Option<OtherExperience>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub technologies: Option<Technologies>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub certifications: Vec<Certification>,
+    /// Sections that aren't built in, e.g. awards, publications or volunteer work.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_sections: Vec<CustomSection>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cover_letter: Option<CoverLetter>,
+    /// Named sets of tag rules used to tailor the resume, selected with the `--variant` argument.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variants: BTreeMap<String, variant::Variant>,
+}
+
+/// Text that can be tagged so it can be filtered by a [variant::Variant]. In the resume file this
+/// is either a plain string or a table with `text` and `tags` keys.
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq, Hash)]
+#[serde(into = "TaggedTextData")]
+pub struct TaggedText {
+    pub text: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum TaggedTextData {
+    Text(String),
+    Tagged { text: String, tags: Vec<String> },
+}
+
+impl<'de> Deserialize<'de> for TaggedText {
+    // Not an untagged enum, so that a typo in the keys of the table can be reported
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TaggedTextVisitor;
+
+        impl<'de> Visitor<'de> for TaggedTextVisitor {
+            type Value = TaggedText;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a string or a table with `text` and `tags` keys")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<TaggedText, E> {
+                Ok(TaggedText::from(v))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<TaggedText, A::Error> {
+                #[derive(Deserialize)]
+                struct Tagged {
+                    text: String,
+                    #[serde(default)]
+                    tags: Vec<String>,
+                }
+                let x = Tagged::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(TaggedText {
+                    text: x.text,
+                    tags: x.tags,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(TaggedTextVisitor)
+    }
+}
+
+impl From<TaggedText> for TaggedTextData {
+    fn from(x: TaggedText) -> Self {
+        if x.tags.is_empty() {
+            TaggedTextData::Text(x.text)
+        } else {
+            TaggedTextData::Tagged {
+                text: x.text,
+                tags: x.tags,
+            }
+        }
+    }
+}
+
+impl From<&str> for TaggedText {
+    fn from(text: &str) -> Self {
+        TaggedText::from(text.to_owned())
+    }
+}
+
+impl From<String> for TaggedText {
+    fn from(text: String) -> Self {
+        TaggedText { text, tags: vec![] }
+    }
+}
+
+impl Deref for TaggedText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for TaggedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct PersonalInfo {
-    // todo: use the first to elements of a vector as the left/right items in the header
     pub email: String,
     pub github: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// E.g. "Seattle, WA".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// E.g. "linkedin.com/in/foo".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linkedin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pronouns: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub other: Option<Vec<OtherPersonalInfo>>,
 }
 
+impl PersonalInfo {
+    /// The contact details for the given fields, in the same order. Fields that aren't set are
+    /// skipped.
+    pub fn contact_items(&self, fields: &[ContactField]) -> Vec<ContactItem> {
+        let mut items = vec![];
+        for field in fields {
+            let item = |label: &str, text: &str, link: Option<String>| ContactItem {
+                field: *field,
+                label: label.to_owned(),
+                text: text.to_owned(),
+                link,
+            };
+            match field {
+                ContactField::Email => items.push(item(
+                    "Email",
+                    &self.email,
+                    Some(format!("mailto:{}", self.email)),
+                )),
+                ContactField::Github => items.push(item(
+                    "GitHub",
+                    &self.github,
+                    Some(add_https_to_url(&self.github)),
+                )),
+                ContactField::Phone => items.extend(self.phone.as_ref().map(|x| {
+                    let digits = x.chars().filter(|c| c.is_ascii_digit() || *c == '+');
+                    item(
+                        "Phone",
+                        x,
+                        Some(format!("tel:{}", digits.collect::<String>())),
+                    )
+                })),
+                ContactField::Location => {
+                    items.extend(self.location.as_ref().map(|x| item("Location", x, None)))
+                }
+                ContactField::Linkedin => items.extend(
+                    self.linkedin
+                        .as_ref()
+                        .map(|x| item("LinkedIn", x, Some(add_https_to_url(x)))),
+                ),
+                ContactField::Website => items.extend(
+                    self.website
+                        .as_ref()
+                        .map(|x| item("Website", x, Some(add_https_to_url(x)))),
+                ),
+                ContactField::Pronouns => {
+                    items.extend(self.pronouns.as_ref().map(|x| item("Pronouns", x, None)))
+                }
+                ContactField::Other => {
+                    items.extend(self.other.iter().flatten().map(|x| ContactItem {
+                        field: *field,
+                        label: x.item.clone(),
+                        text: x.url.clone().unwrap_or_else(|| x.item.clone()),
+                        link: x.url.as_deref().map(add_https_to_url),
+                    }))
+                }
+            }
+        }
+        items.retain(|x| !x.text.is_empty());
+        items
+    }
+}
+
+/// A contact detail ready to be rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactItem {
+    pub field: ContactField,
+    /// What the detail is, e.g. "Phone", or the name of an `other` item.
+    pub label: String,
+    pub text: String,
+    pub link: Option<String>,
+}
+
+impl ContactItem {
+    /// Whether the label should be shown next to the text even where the built-in fields are
+    /// shown without one. Only `other` items that have a url need it.
+    pub fn needs_label(&self) -> bool {
+        self.field == ContactField::Other && self.link.is_some()
+    }
+
+    /// The item as plain text, for outputs without links.
+    pub fn plain_text(&self) -> String {
+        if self.needs_label() {
+            format!("{}: {}", self.label, self.text)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct OtherPersonalInfo {
     pub item: String,
@@ -88,19 +452,78 @@ pub struct ProfessionalExperience {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
     pub position: String,
-    pub start: String,
-    pub end: String,
-    pub experience: Vec<String>,
+    /// `YYYY-MM`
+    pub start: ResumeDate,
+    /// `YYYY-MM` or `present`
+    pub end: ResumeDate,
+    pub experience: Vec<TaggedText>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Education {
     pub school: String,
     pub location: String,
+    /// E.g. "B.S." or "Master of Science".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degree: Option<String>,
     pub major: String,
-    pub graduation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extras: Option<Vec<String>>,
+    pub minor: Option<String>,
+    /// `YYYY-MM`
+    pub graduation: Option<ResumeDate>,
+    /// Free-form, e.g. "3.8" or "3.8/4.0". Hidden if `hide_gpa` is set in the format config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpa: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub honors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coursework: Vec<TaggedText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extras: Option<Vec<TaggedText>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Education {
+    /// The degree and field of study, e.g. "B.S. in Computer Science, minor in Mathematics".
+    pub fn degree_string(&self) -> String {
+        let mut text = match &self.degree {
+            Some(degree) => format!("{} in {}", degree, self.major),
+            None => self.major.clone(),
+        };
+        if let Some(minor) = &self.minor {
+            text = format!("{}, minor in {}", text, minor);
+        }
+        text
+    }
+
+    /// The GPA and honors on one line, if there are any to show.
+    pub fn details_string(&self, show_gpa: bool) -> Option<String> {
+        let mut details = vec![];
+        if let Some(gpa) = self.gpa.as_ref().filter(|_| show_gpa) {
+            details.push(format!("GPA: {}", gpa));
+        }
+        details.extend(self.honors.iter().cloned());
+        Some(details.join(", ")).filter(|x| !x.is_empty())
+    }
+
+    pub fn coursework_string(&self) -> Option<String> {
+        if self.coursework.is_empty() {
+            return None;
+        }
+        let coursework = self
+            .coursework
+            .iter()
+            .map(|x| x.text.as_str())
+            .collect::<Vec<&str>>();
+        Some(format!("Relevant coursework: {}", coursework.join(", ")))
+    }
+
+    pub fn get_extras(&self) -> &[TaggedText] {
+        self.extras.as_deref().unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -131,17 +554,102 @@ impl OtherExperience {
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ProjectInfo {
     pub project_name: String,
+    /// Can be omitted for projects that aren't online, in which case only the name is shown.
+    #[serde(default)]
     pub url: String,
     pub description: String,
     #[serde(default = "default_true")]
     pub include_on_resume: bool,
     #[serde(default = "default_true")]
     pub include_on_github: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Technologies {
-    pub technologies: Vec<String>,
+    pub technologies: Vec<TaggedText>,
+}
+
+impl Technologies {
+    pub fn get_technologies(&self) -> Vec<&str> {
+        self.technologies.iter().map(|x| x.text.as_str()).collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct Certification {
+    pub name: String,
+    pub issuer: String,
+    /// `YYYY-MM`
+    pub issued: ResumeDate,
+    /// `YYYY-MM`, omitted if the certification doesn't expire.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<ResumeDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    /// Where the certification can be verified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Certification {
+    /// Whether the certification expired before the given month. A certification that expires
+    /// this month is still valid.
+    pub fn is_expired(&self, today: &ResumeDate) -> bool {
+        self.expires.is_some_and(|x| x < *today)
+    }
+
+    /// The issue date, followed by the expiry date if there is one.
+    pub fn dates(&self, date_format: &str) -> String {
+        match &self.expires {
+            Some(expires) => format!(
+                "{} - {}",
+                self.issued.format(date_format),
+                expires.format(date_format)
+            ),
+            None => self.issued.format(date_format),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CustomSection {
+    pub title: String,
+    pub items: Vec<CustomSectionItem>,
+}
+
+/// An entry in a [CustomSection]. The header is laid out like professional experience: the title
+/// and location on the first line, the subtitle and date on the second. All of it is optional.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct CustomSectionItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    /// Free-form, e.g. "2021" or "Jun 2021".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bullets: Vec<TaggedText>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl CustomSectionItem {
+    /// The lines of the item's header as (left, right) pairs. Lines without any content are
+    /// omitted.
+    pub fn header_lines(&self) -> Vec<(&str, Option<&str>)> {
+        [(&self.title, &self.location), (&self.subtitle, &self.date)]
+            .iter()
+            .filter(|(l, r)| l.is_some() || r.is_some())
+            .map(|(l, r)| (l.as_deref().unwrap_or_default(), r.as_deref()))
+            .collect()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -159,17 +667,125 @@ pub struct CoverLetter {
 
 #[cfg(test)]
 mod test {
-    use crate::data::{Resume, Technologies};
+    use crate::config::arguments::{Arguments, InputFormat};
+    use crate::config::format_config::ContactField;
+    use crate::config::format_config::FormatConfig;
+    use crate::config::Config;
+    use crate::data::date::ResumeDate;
+    use crate::data::{
+        Certification, Education, OtherPersonalInfo, PersonalInfo, ProfessionalExperience, Resume,
+        TaggedText, Technologies,
+    };
+    use crate::error::Error;
+    use crate::util::{json_from_string, toml_from_string};
 
     #[test]
     fn test_deserialize_toml() {
         let resume =
             Resume::read_from_config_file("tst/test_resume.toml", &Default::default()).unwrap();
+        assert_test_resume(resume);
+    }
+
+    #[test]
+    fn test_deserialize_json() {
+        let resume =
+            Resume::read_from_config_file("tst/test_resume.json", &Default::default()).unwrap();
+        assert_test_resume(resume);
+    }
+
+    #[test]
+    fn test_deserialize_yaml() {
+        let resume =
+            Resume::read_from_config_file("tst/test_resume.yaml", &Default::default()).unwrap();
+        assert_test_resume(resume);
+    }
+
+    #[test]
+    fn test_deserialize_with_input_format() {
+        let config = Config {
+            args: Arguments {
+                input_format: Some(InputFormat::Yaml),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // Json is a subset of yaml, so the json file can also be read as yaml
+        let resume = Resume::read_from_config_file("tst/test_resume.json", &config).unwrap();
+        assert_test_resume(resume);
+
+        let r = Resume::read_from_config_file("tst/test_resume.toml", &config);
+        assert!(matches!(r, Err(Error::Yaml { .. })));
+    }
 
+    #[test]
+    fn test_unknown_keys() {
+        let e = Resume::read_from_config_file("tst/test_unknown_keys.toml", &Default::default())
+            .unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "An error occurred while reading [tst/test_unknown_keys.toml]: found keys that don't match any field. Fix them, or run with --lenient to ignore them:\n  line 7, column 2: unknown key `objectve`, did you mean `objective`?\n  line 19, column 1: unknown key `other_experience.projects[0].include_on_resum`, did you mean `include_on_resume`?"
+        );
+
+        let config = Config {
+            args: Arguments {
+                lenient: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let resume = Resume::read_from_config_file("tst/test_unknown_keys.toml", &config).unwrap();
+        assert!(resume.objective.is_none());
+    }
+
+    #[test]
+    fn test_unknown_keys_missing_section() {
+        let e = Resume::read_from_config_file(
+            "tst/test_unknown_keys_missing_section.toml",
+            &Default::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(e, Error::UnknownKeys { .. }));
+        assert!(e.to_string().ends_with(
+            "line 7, column 3: unknown key `proffesional_experience`, did you mean `professional_experience`?"
+        ));
+
+        let config = Config {
+            args: Arguments {
+                lenient: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let r =
+            Resume::read_from_config_file("tst/test_unknown_keys_missing_section.toml", &config);
+        assert!(matches!(r, Err(Error::Toml { .. })));
+    }
+
+    fn assert_test_resume(resume: Resume) {
         assert_eq!(resume.name, "Foo Bar");
 
         assert_eq!(resume.personal_info.email, String::from("foo@example.com"));
         assert_eq!(resume.personal_info.github, String::from("github.com/foo"));
+        assert_eq!(
+            resume.personal_info.phone,
+            Some(String::from("555-555-5555"))
+        );
+        assert_eq!(
+            resume.personal_info.location,
+            Some(String::from("Seattle, WA"))
+        );
+        assert_eq!(
+            resume.personal_info.linkedin,
+            Some(String::from("linkedin.com/in/foo"))
+        );
+        assert_eq!(
+            resume.personal_info.website,
+            Some(String::from("foo.example.com"))
+        );
+        assert_eq!(
+            resume.personal_info.pronouns,
+            Some(String::from("they/them"))
+        );
 
         assert!(resume.personal_info.other.is_some());
         let personal_info = resume.personal_info.other.unwrap();
@@ -199,15 +815,15 @@ mod test {
         );
         assert_eq!(
             resume.professional_experience[0].start,
-            String::from("startA")
+            ResumeDate::new(2020, 1).unwrap()
         );
-        assert_eq!(resume.professional_experience[0].end, String::from("endA"));
+        assert_eq!(resume.professional_experience[0].end, ResumeDate::Present);
         assert_eq!(
             resume.professional_experience[0].experience,
             vec![
-                String::from("FooA"),
-                String::from("BarA"),
-                String::from("BazA")
+                TaggedText::from("FooA"),
+                TaggedText::from("BarA"),
+                TaggedText::from("BazA")
             ]
         );
 
@@ -225,32 +841,49 @@ mod test {
         );
         assert_eq!(
             resume.professional_experience[1].start,
-            String::from("startB")
+            ResumeDate::new(2018, 1).unwrap()
+        );
+        assert_eq!(
+            resume.professional_experience[1].end,
+            ResumeDate::new(2019, 12).unwrap()
         );
-        assert_eq!(resume.professional_experience[1].end, String::from("endB"));
         assert_eq!(
             resume.professional_experience[1].experience,
             vec![
-                String::from("FooB"),
-                String::from("BarB"),
-                String::from("BazB")
+                TaggedText::from("FooB"),
+                TaggedText::from("BarB"),
+                TaggedText::from("BazB")
             ]
         );
 
-        assert!(resume.education.is_some());
-        let education = resume.education.unwrap();
+        assert_eq!(resume.education.len(), 2);
+        let education = &resume.education[0];
         assert_eq!(education.school, String::from("school"));
         assert_eq!(education.location, String::from("location"));
+        assert_eq!(education.degree, Some(String::from("B.S.")));
         assert_eq!(education.major, String::from("major"));
-        assert_eq!(education.graduation, Some(String::from("graduation")));
+        assert_eq!(education.minor, Some(String::from("minor")));
+        assert_eq!(education.graduation, ResumeDate::new(2017, 6));
+        assert_eq!(education.gpa, Some(String::from("3.8")));
+        assert_eq!(education.honors, vec![String::from("Cum Laude")]);
+        assert_eq!(
+            education.coursework,
+            vec![TaggedText::from("Course A"), TaggedText::from("Course B")]
+        );
         assert_eq!(
             education.extras,
             Some(vec![
-                String::from("Foo"),
-                String::from("Bar"),
-                String::from("Baz")
+                TaggedText::from("Foo"),
+                TaggedText::from("Bar"),
+                TaggedText::from("Baz")
             ])
         );
+        let education = &resume.education[1];
+        assert_eq!(education.school, String::from("other school"));
+        assert_eq!(education.degree, None);
+        assert_eq!(education.graduation, None);
+        assert!(education.coursework.is_empty());
+        assert_eq!(education.extras, None);
 
         assert!(resume.other_experience.is_some());
         let exp = resume.other_experience.unwrap();
@@ -259,6 +892,31 @@ mod test {
         assert_eq!(exp.projects[0].url, String::from("https://example.com"));
         assert_eq!(exp.projects[0].description, String::from("Description"));
 
+        assert_eq!(resume.certifications.len(), 2);
+        let certification = &resume.certifications[0];
+        assert_eq!(certification.name, "Certification");
+        assert_eq!(certification.issuer, "issuer");
+        assert_eq!(certification.issued, ResumeDate::new(2021, 3).unwrap());
+        assert_eq!(certification.expires, ResumeDate::new(2099, 3));
+        assert_eq!(certification.credential_id, Some(String::from("ABC-123")));
+        assert_eq!(
+            certification.url,
+            Some(String::from("https://example.com/verify"))
+        );
+        assert_eq!(resume.certifications[1].credential_id, None);
+
+        assert_eq!(resume.custom_sections.len(), 1);
+        let section = &resume.custom_sections[0];
+        assert_eq!(section.title, "Awards");
+        assert_eq!(section.items.len(), 2);
+        assert_eq!(
+            section.items[0].header_lines(),
+            vec![("Award", Some("location")), ("subtitle", Some("2021"))]
+        );
+        assert_eq!(section.items[0].bullets, vec![TaggedText::from("Foo")]);
+        assert_eq!(section.items[1].header_lines(), vec![("Other Award", None)]);
+        assert!(section.items[1].bullets.is_empty());
+
         assert!(resume.cover_letter.is_some());
         let cover_letter = resume.cover_letter.unwrap();
         assert_eq!(cover_letter.salutation, "Hello,");
@@ -271,15 +929,281 @@ mod test {
         assert_eq!(cover_letter.email.unwrap(), resume.personal_info.email);
     }
 
+    #[test]
+    fn test_contact_items() {
+        let x = PersonalInfo {
+            email: String::from("foo@bar.com"),
+            github: String::from("github.com/foo"),
+            phone: Some(String::from("+1 (555) 555-5555")),
+            location: Some(String::from("Seattle, WA")),
+            other: Some(vec![
+                OtherPersonalInfo {
+                    item: String::from("Foo"),
+                    url: Some(String::from("example.com/foo")),
+                },
+                OtherPersonalInfo {
+                    item: String::from("Bar"),
+                    url: None,
+                },
+            ]),
+            ..Default::default()
+        };
+        let items = x.contact_items(&[
+            ContactField::Phone,
+            ContactField::Website,
+            ContactField::Location,
+            ContactField::Other,
+        ]);
+
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0].text, "+1 (555) 555-5555");
+        assert_eq!(items[0].link, Some(String::from("tel:+15555555555")));
+        assert_eq!(items[1].plain_text(), "Seattle, WA");
+        assert!(items[1].link.is_none());
+        assert!(items[2].needs_label());
+        assert_eq!(items[2].plain_text(), "Foo: example.com/foo");
+        assert_eq!(items[2].link, Some(String::from("https://example.com/foo")));
+        assert!(!items[3].needs_label());
+        assert_eq!(items[3].plain_text(), "Bar");
+    }
+
     #[test]
     fn test_verify_technologies() {
         let t = Technologies {
             technologies: vec!["foo", "bar", "baz", "foo", "things", "stuff"]
                 .into_iter()
-                .map(String::from)
+                .map(TaggedText::from)
                 .collect(),
         };
         let r = Resume::verify_technologies(&t);
-        assert!(r.is_err());
+        assert!(matches!(r, Err(Error::Validation(_))));
+    }
+
+    fn experience(
+        organization: Option<&str>,
+        position: &str,
+        start: (i32, u32),
+        end: Option<(i32, u32)>,
+    ) -> ProfessionalExperience {
+        ProfessionalExperience {
+            organization: organization.map(String::from),
+            location: organization.map(|x| format!("{} location", x)),
+            position: String::from(position),
+            start: ResumeDate::new(start.0, start.1).unwrap(),
+            end: end
+                .and_then(|(year, month)| ResumeDate::new(year, month))
+                .unwrap_or(ResumeDate::Present),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_experience_dates() {
+        let resume = Resume {
+            professional_experience: vec![experience(Some("A"), "a", (2020, 2), Some((2020, 1)))],
+            ..Default::default()
+        };
+        let r = Resume::verify(resume);
+        assert!(matches!(r, Err(Error::Validation(_))));
+
+        let resume = Resume {
+            professional_experience: vec![experience(Some("A"), "a", (2020, 1), Some((2020, 1)))],
+            ..Default::default()
+        };
+        assert!(Resume::verify(resume).is_ok());
+    }
+
+    #[test]
+    fn test_sort_experience() {
+        let mut resume = Resume {
+            professional_experience: vec![
+                experience(Some("A"), "a1", (2010, 1), Some((2012, 1))),
+                experience(None, "a2", (2012, 1), Some((2014, 1))),
+                experience(Some("B"), "b1", (2016, 1), None),
+                experience(Some("C"), "c1", (2014, 1), Some((2016, 1))),
+            ],
+            ..Default::default()
+        };
+        resume.sort_experience();
+
+        let exp = &resume.professional_experience;
+        let positions = exp
+            .iter()
+            .map(|x| x.position.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(positions, vec!["b1", "c1", "a2", "a1"]);
+        assert_eq!(exp[2].organization, Some(String::from("A")));
+        assert_eq!(exp[2].location, Some(String::from("A location")));
+        assert_eq!(exp[3].organization, None);
+        assert_eq!(exp[3].location, None);
+    }
+
+    #[test]
+    fn test_single_education_table() {
+        let resume: Resume = toml_from_string(
+            r#"
+            name = "name"
+            professional_experience = []
+
+            [personal_info]
+            email = "email"
+            github = "github"
+
+            [education]
+            school = "school"
+            location = "location"
+            major = "major"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(resume.education.len(), 1);
+        assert_eq!(resume.education[0].school, "school");
+
+        let resume: Resume = toml_from_string(
+            r#"
+            name = "name"
+            professional_experience = []
+
+            [personal_info]
+            email = "email"
+            github = "github"
+            "#,
+        )
+        .unwrap();
+        assert!(resume.education.is_empty());
+
+        let resume = json_from_string::<Resume>(
+            r#"{"name": "name", "personal_info": {"email": "", "github": ""}, "professional_experience": [], "education": "school"}"#,
+        );
+        assert!(matches!(resume, Err(Error::Json { .. })));
+    }
+
+    #[test]
+    fn test_education_strings() {
+        let mut x = Education {
+            major: String::from("Computer Science"),
+            gpa: Some(String::from("3.8")),
+            ..Default::default()
+        };
+        assert_eq!(x.degree_string(), "Computer Science");
+        assert_eq!(x.details_string(true), Some(String::from("GPA: 3.8")));
+        assert_eq!(x.details_string(false), None);
+        assert_eq!(x.coursework_string(), None);
+
+        x.degree = Some(String::from("B.S."));
+        x.minor = Some(String::from("Mathematics"));
+        x.honors = vec![String::from("Cum Laude"), String::from("Dean's List")];
+        x.coursework = vec![TaggedText::from("Compilers"), TaggedText::from("Databases")];
+        assert_eq!(
+            x.degree_string(),
+            "B.S. in Computer Science, minor in Mathematics"
+        );
+        assert_eq!(
+            x.details_string(true),
+            Some(String::from("GPA: 3.8, Cum Laude, Dean's List"))
+        );
+        assert_eq!(
+            x.details_string(false),
+            Some(String::from("Cum Laude, Dean's List"))
+        );
+        assert_eq!(
+            x.coursework_string(),
+            Some(String::from("Relevant coursework: Compilers, Databases"))
+        );
+    }
+
+    #[test]
+    fn test_verify_certification_dates() {
+        let certification = |issued, expires| Certification {
+            name: String::from("name"),
+            issued,
+            expires,
+            ..Default::default()
+        };
+        let verify = |x| {
+            Resume::verify(Resume {
+                certifications: vec![x],
+                ..Default::default()
+            })
+        };
+        let jan_2020 = ResumeDate::new(2020, 1).unwrap();
+        let feb_2020 = ResumeDate::new(2020, 2).unwrap();
+
+        assert!(verify(certification(jan_2020, None)).is_ok());
+        assert!(verify(certification(jan_2020, Some(jan_2020))).is_ok());
+        assert!(matches!(
+            verify(certification(feb_2020, Some(jan_2020))),
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            verify(certification(ResumeDate::Present, None)),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_certification() {
+        let mut x = Certification {
+            issued: ResumeDate::new(2020, 1).unwrap(),
+            expires: ResumeDate::new(2022, 6),
+            ..Default::default()
+        };
+        assert!(!x.is_expired(&ResumeDate::new(2022, 6).unwrap()));
+        assert!(x.is_expired(&ResumeDate::new(2022, 7).unwrap()));
+        assert_eq!(x.dates("%b %Y"), "Jan 2020 - Jun 2022");
+
+        x.expires = None;
+        assert!(!x.is_expired(&ResumeDate::new(2099, 1).unwrap()));
+        assert_eq!(x.dates("%Y-%m"), "2020-01");
+    }
+
+    #[test]
+    fn test_read_hide_expired_certifications() {
+        let config = Config {
+            format_config: FormatConfig {
+                hide_expired_certifications: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let resume = Resume::read_from_config_file("tst/test_resume.toml", &config).unwrap();
+        assert_eq!(resume.certifications.len(), 1);
+        assert_eq!(resume.certifications[0].name, "Certification");
+    }
+
+    #[test]
+    fn test_warnings() {
+        let resume =
+            Resume::read_from_config_file("tst/test_resume.toml", &Default::default()).unwrap();
+        assert_eq!(
+            resume.warnings(),
+            vec![String::from("The certification [Expired Certification] expired in 2018-01. Set hide_expired_certifications in the format config to leave it out.")]
+        );
+
+        let config = Config {
+            format_config: FormatConfig {
+                hide_expired_certifications: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let resume = Resume::read_from_config_file("tst/test_resume.toml", &config).unwrap();
+        assert!(resume.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_read_sorted() {
+        let config = Config {
+            format_config: FormatConfig {
+                sort_experience: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut resume = Resume::read_from_config_file("tst/test_resume.toml", &config).unwrap();
+        assert_eq!(resume.professional_experience[0].position, "positionA");
+        resume.professional_experience.reverse();
+        resume.sort_experience();
+        assert_eq!(resume.professional_experience[0].position, "positionA");
     }
 }
@@ -0,0 +1,218 @@
+//! Finding where the values of the resume are in the resume data file, so problems can be reported
+//! with a line and column. Only toml reports where each key is, so only toml files are mapped.
+use crate::config::arguments::InputFormat;
+use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::Deserializer;
+use std::fmt;
+use toml::Spanned;
+
+/// The resume data file parsed into a generic tree that remembers where each key is.
+pub struct SourceMap<'a> {
+    source: &'a str,
+    root: Option<Node>,
+}
+
+impl<'a> SourceMap<'a> {
+    /// Parse the file. Nothing can be located if it isn't a toml file, or if it can't be parsed.
+    pub fn new(source: &'a str, format: InputFormat) -> SourceMap<'a> {
+        let root = match format {
+            // Toml tables aren't necessarily read in order, but each key knows where it is
+            InputFormat::Toml => NodeSeed
+                .deserialize(&mut toml::Deserializer::new(source))
+                .ok(),
+            _ => None,
+        };
+        SourceMap { source, root }
+    }
+
+    /// The 1-based line and column of the value at the path, e.g.
+    /// `professional_experience[0].experience[2]`. Values are found by their key, so a value in a
+    /// list points to the key of the list, or to the text itself if it can be found after the key.
+    pub fn locate(&self, path: &str) -> Option<(usize, usize)> {
+        let (node, offset) = self.find(path)?;
+        let offset = match node {
+            // Tagged text
+            Node::Map(x) => x
+                .iter()
+                .find(|x| x.key == "text")
+                .map(|x| x.offset)
+                .or(offset)?,
+            Node::Str(text) => {
+                let offset = offset?;
+                self.source[offset..]
+                    .find(text.as_str())
+                    .map_or(offset, |x| offset + x)
+            }
+            _ => offset?,
+        };
+        Some(self.line_col(offset))
+    }
+
+    /// The 1-based line and column of the last key in the path, e.g. of `position` in
+    /// `professional_experience[0].position`.
+    pub fn locate_key(&self, path: &str) -> Option<(usize, usize)> {
+        let (_, offset) = self.find(path)?;
+        Some(self.line_col(offset?))
+    }
+
+    /// The node at the path, and the offset of the last key on the way to it.
+    fn find(&self, path: &str) -> Option<(&Node, Option<usize>)> {
+        let mut node = self.root.as_ref()?;
+        let mut offset = None;
+        for segment in path.split(['.', '[']) {
+            match (segment.strip_suffix(']'), node) {
+                (Some(index), Node::Seq(x)) => node = x.get(index.parse::<usize>().ok()?)?,
+                (None, Node::Map(x)) => {
+                    let entry = x.iter().find(|x| x.key == segment)?;
+                    offset = Some(entry.offset);
+                    node = &entry.value;
+                }
+                _ => return None,
+            }
+        }
+        Some((node, offset))
+    }
+
+    /// The 1-based line and column of the byte offset.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map(|x| x + 1).unwrap_or(0);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+}
+
+/// A parsed data file. Map entries are kept in the order they were read.
+enum Node {
+    /// A number, boolean or date, which isn't searched for.
+    Other,
+    Str(String),
+    Seq(Vec<Node>),
+    Map(Vec<Entry>),
+}
+
+struct Entry {
+    key: String,
+    /// The byte offset of the key in the file.
+    offset: usize,
+    value: Node,
+}
+
+struct NodeSeed;
+
+impl<'de> DeserializeSeed<'de> for NodeSeed {
+    type Value = Node;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeSeed {
+    type Value = Node;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "any value")
+    }
+
+    fn visit_bool<E>(self, _v: bool) -> Result<Node, E> {
+        Ok(Node::Other)
+    }
+
+    fn visit_i64<E>(self, _v: i64) -> Result<Node, E> {
+        Ok(Node::Other)
+    }
+
+    fn visit_u64<E>(self, _v: u64) -> Result<Node, E> {
+        Ok(Node::Other)
+    }
+
+    fn visit_f64<E>(self, _v: f64) -> Result<Node, E> {
+        Ok(Node::Other)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Node, E> {
+        Ok(Node::Str(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Node, E> {
+        Ok(Node::Str(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Node, E> {
+        Ok(Node::Other)
+    }
+
+    fn visit_none<E>(self) -> Result<Node, E> {
+        Ok(Node::Other)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+        let mut nodes = vec![];
+        while let Some(x) = seq.next_element_seed(NodeSeed)? {
+            nodes.push(x);
+        }
+        Ok(Node::Seq(nodes))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+        let mut entries = vec![];
+        while let Some(key) = map.next_key::<Spanned<String>>()? {
+            let offset = key.start();
+            let value = map.next_value_seed(NodeSeed)?;
+            entries.push(Entry {
+                key: key.into_inner(),
+                offset,
+                value,
+            });
+        }
+        Ok(Node::Map(entries))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::InputFormat;
+    use crate::data::source_map::SourceMap;
+
+    #[test]
+    fn test_locate_toml() {
+        let source = std::fs::read_to_string("tst/test_resume.toml").unwrap();
+        let map = SourceMap::new(&source, InputFormat::Toml);
+        let line = |path: &str| source.lines().nth(map.locate(path).unwrap().0 - 1).unwrap();
+
+        assert!(line("name").starts_with("name = "));
+        assert!(line("professional_experience[1].position").contains("positionB"));
+        assert!(line("professional_experience[1].experience[2]").contains("BazB"));
+        assert!(line("education[1].school").starts_with("school = "));
+        assert_eq!(map.locate("professional_experience[5].position"), None);
+        assert_eq!(map.locate("does_not_exist"), None);
+    }
+
+    #[test]
+    fn test_locate_key() {
+        let source = std::fs::read_to_string("tst/test_resume.toml").unwrap();
+        let map = SourceMap::new(&source, InputFormat::Toml);
+        let (line, column) = map.locate_key("personal_info.email").unwrap();
+        assert!(source.lines().nth(line - 1).unwrap()[column - 1..].starts_with("email = "));
+    }
+
+    #[test]
+    fn test_only_toml_is_located() {
+        for (file, format) in [
+            ("tst/test_resume.json", InputFormat::Json),
+            ("tst/test_resume.yaml", InputFormat::Yaml),
+            ("tst/test_json_resume.json", InputFormat::JsonResume),
+        ] {
+            let source = std::fs::read_to_string(file).unwrap();
+            let map = SourceMap::new(&source, format);
+            assert_eq!(map.locate("name"), None, "{}", file);
+        }
+    }
+}
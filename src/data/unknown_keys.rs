@@ -0,0 +1,291 @@
+//! Finding the keys in a resume data file that don't match any field of the resume, so that a typo
+//! like `proffesional_experience` doesn't silently drop a whole section.
+//!
+//! Serde skips the keys it doesn't know, and [serde_ignored] reports the path of each one. Only toml
+//! keeps track of where each key is, so the keys of json and yaml files are reported without a
+//! line and column.
+use crate::config::arguments::InputFormat;
+use crate::data::source_map::SourceMap;
+use serde::de::value::Error as ValueError;
+use serde::de::{DeserializeOwned, Error as _, Visitor};
+use serde::Deserializer;
+use serde_ignored::Path;
+use std::cell::Cell;
+use std::cmp::max;
+use std::fmt;
+
+/// A key in the resume data file that doesn't match any field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// The full path of the key, e.g. `professional_experience[0].include_on_resum`.
+    pub path: String,
+    /// The 1-based line of the key, if it could be found in the file.
+    pub line: Option<usize>,
+    /// The 1-based column of the key, if it could be found in the file.
+    pub column: Option<usize>,
+    /// The closest valid key, if one is close enough to make a typo likely.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "line {}, column {}: ", line, column)?;
+        }
+        write!(f, "unknown key `{}`", self.path)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean `{}`?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find the keys in `source` that `T` doesn't have a field for. Suggestions are picked from
+/// `fields`, the names of the fields in the file's tables. If `source` can't be deserialized as
+/// `T`, only the keys found before the error are returned, so the file should be deserialized
+/// normally first to report the error.
+pub fn find_unknown_keys<T: DeserializeOwned>(
+    source: &str,
+    format: InputFormat,
+    fields: &[&str],
+) -> Vec<UnknownKey> {
+    let mut paths = vec![];
+    let mut record = |path: Path| paths.push(path_string(&path));
+    match format {
+        InputFormat::Toml => {
+            let _: Result<T, _> =
+                serde_ignored::deserialize(&mut toml::Deserializer::new(source), &mut record);
+        }
+        InputFormat::Json => {
+            let _: Result<T, _> = serde_ignored::deserialize(
+                &mut serde_json::Deserializer::from_str(source),
+                &mut record,
+            );
+        }
+        InputFormat::Yaml => {
+            let _: Result<T, _> =
+                serde_ignored::deserialize(serde_yaml::Deserializer::from_str(source), &mut record);
+        }
+        InputFormat::JsonResume => {}
+    }
+
+    let source_map = SourceMap::new(source, format);
+    let mut keys: Vec<UnknownKey> = paths
+        .into_iter()
+        .map(|path| {
+            let location = source_map.locate_key(&path);
+            let key = path.rsplit(['.', '[']).next().unwrap_or_default();
+            UnknownKey {
+                suggestion: suggest(key, fields.iter().copied()),
+                line: location.map(|x| x.0),
+                column: location.map(|x| x.1),
+                path,
+            }
+        })
+        .collect();
+    keys.sort_by_key(|x| (x.line.is_none(), x.line, x.column));
+    keys
+}
+
+/// The path in the same form as the paths of [SourceMap::locate], e.g.
+/// `professional_experience[0].experience`.
+fn path_string(path: &Path) -> String {
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{}]", path_string(parent), index),
+        Path::Map { parent, key } => match path_string(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => path_string(parent),
+    }
+}
+
+/// The closest candidate to the unknown key, if it's close enough to be the intended key.
+fn suggest<'a>(key: &str, candidates: impl Iterator<Item = &'a str>) -> Option<String> {
+    let limit = max(2, key.chars().count() / 3);
+    candidates
+        .map(|x| (strsim::damerau_levenshtein(key, x), x))
+        .filter(|(distance, _)| *distance > 0 && *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, x)| x.to_owned())
+}
+
+/// The names of the fields of the struct `T`, read from its `Deserialize` implementation, which
+/// passes them to the deserializer.
+pub fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldNames<'a>(&'a Cell<&'static [&'static str]>);
+
+    impl<'de, 'a> Deserializer<'de> for FieldNames<'a> {
+        type Error = ValueError;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ValueError> {
+            Err(ValueError::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, ValueError> {
+            self.0.set(fields);
+            Err(ValueError::custom("only the field names are read"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+            ignored_any
+        }
+    }
+
+    let fields = Cell::new(&[][..]);
+    let _ = T::deserialize(FieldNames(&fields));
+    fields.get()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::InputFormat;
+    use crate::data::unknown_keys::{find_unknown_keys, struct_fields, UnknownKey};
+    use crate::data::{Certification, Resume};
+
+    fn key(path: &str, location: Option<(usize, usize)>, suggestion: Option<&str>) -> UnknownKey {
+        UnknownKey {
+            path: String::from(path),
+            line: location.map(|x| x.0),
+            column: location.map(|x| x.1),
+            suggestion: suggestion.map(String::from),
+        }
+    }
+
+    fn unknown_keys(source: &str, format: InputFormat) -> Vec<UnknownKey> {
+        find_unknown_keys::<Resume>(source, format, &Resume::field_names())
+    }
+
+    #[test]
+    fn test_no_unknown_keys() {
+        for (file, format) in [
+            ("tst/test_resume.toml", InputFormat::Toml),
+            ("tst/test_resume.json", InputFormat::Json),
+            ("tst/test_resume.yaml", InputFormat::Yaml),
+            ("tst/test_variants.toml", InputFormat::Toml),
+        ] {
+            let source = std::fs::read_to_string(file).unwrap();
+            assert_eq!(unknown_keys(&source, format), vec![], "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys_toml() {
+        let source = r#"name = "Foo Bar"
+emial = "foo@example.com"
+
+[personal_info]
+email = "foo@example.com"
+github = "github.com/foo"
+
+[[proffesional_experience]]
+position = "positionA"
+
+[other_experience]
+[[other_experience.projects]]
+project_name = "Foo"
+url = "example.com"
+description = "Bar"
+include_on_resum = false
+tag = ["backend"]
+"#;
+        assert_eq!(
+            unknown_keys(source, InputFormat::Toml),
+            vec![
+                key("emial", Some((2, 1)), Some("email")),
+                key(
+                    "proffesional_experience",
+                    Some((8, 3)),
+                    Some("professional_experience")
+                ),
+                key(
+                    "other_experience.projects[0].include_on_resum",
+                    Some((16, 1)),
+                    Some("include_on_resume")
+                ),
+                key(
+                    "other_experience.projects[0].tag",
+                    Some((17, 1)),
+                    Some("tags")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_json() {
+        let source = r#"{
+  "name": "Foo Bar",
+  "personal_info": {
+    "email": "foo@example.com",
+    "github": "github.com/foo",
+    "phnoe": "555-555-5555"
+  },
+  "certifications": [
+    {
+      "name": "Certification",
+      "issuer": "issuer",
+      "issued": "2021-03",
+      "expire": "2024-03"
+    }
+  ]
+}"#;
+        assert_eq!(
+            unknown_keys(source, InputFormat::Json),
+            vec![
+                key("personal_info.phnoe", None, Some("phone")),
+                key("certifications[0].expire", None, Some("expires")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_yaml() {
+        let source = r#"name: Foo Bar
+personal_info:
+  email: foo@example.com
+  github: github.com/foo
+professional_experience:
+  - organization: organizationA
+    position: positionA
+    start: 2020-01
+    end: present
+    experience:
+      - text: Foo
+        tag: [backend]
+"#;
+        assert_eq!(
+            unknown_keys(source, InputFormat::Yaml),
+            vec![key(
+                "professional_experience[0].experience[0].tag",
+                None,
+                Some("tags")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_struct_fields() {
+        assert!(struct_fields::<Certification>().contains(&"credential_id"));
+        assert_eq!(struct_fields::<String>(), &[] as &[&str]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            key("foo", Some((1, 2)), Some("bar")).to_string(),
+            "line 1, column 2: unknown key `foo`, did you mean `bar`?"
+        );
+        assert_eq!(key("foo", None, None).to_string(), "unknown key `foo`");
+    }
+}
@@ -0,0 +1,334 @@
+//! Tailoring the resume to a specific kind of role by filtering tagged entries.
+//!
+//! Professional experience, experience bullets, projects, technologies, education entries and their
+//! coursework and extras, certifications and the items and bullets of custom sections can be
+//! tagged. A variant selects the tagged entries to keep:
+//! ```toml
+//! [variants.backend]
+//! include = ["backend", "infra"]
+//! exclude = ["frontend"]
+//! ```
+//! Untagged entries are always kept.
+use crate::data::{Resume, TaggedText};
+use crate::error::Error;
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Variant {
+    /// Tagged entries are kept only if they have at least one of these tags. If this is empty, all
+    /// tagged entries are kept unless they're excluded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Entries that have any of these tags are removed, even if they're also included.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl Variant {
+    pub fn matches(&self, tags: &[String]) -> bool {
+        if tags.is_empty() {
+            return true;
+        }
+        let included = self.include.is_empty() || tags.iter().any(|x| self.include.contains(x));
+        let excluded = tags.iter().any(|x| self.exclude.contains(x));
+        included && !excluded
+    }
+
+    fn retain(&self, list: &mut Vec<TaggedText>) {
+        list.retain(|x| self.matches(&x.tags))
+    }
+}
+
+impl Resume {
+    /// Get the variant with the given name. If the resume doesn't define any variants, the name is
+    /// used as the only included tag. If it does, the name has to be one of them, so a typo doesn't
+    /// silently remove every tagged entry.
+    pub fn get_variant(&self, name: &str) -> Result<Variant, Error> {
+        if let Some(variant) = self.variants.get(name) {
+            return Ok(variant.clone());
+        }
+        if !self.variants.is_empty() {
+            let mut names = self
+                .variants
+                .keys()
+                .map(String::as_str)
+                .collect::<Vec<&str>>();
+            names.sort_unstable();
+            return Err(Error::Validation(format!(
+                "The variant [{}] isn't defined. The resume defines: {}",
+                name,
+                names.join(", ")
+            )));
+        }
+        Ok(Variant {
+            include: vec![name.to_owned()],
+            exclude: vec![],
+        })
+    }
+
+    /// Remove all the entries that don't match the variant with the given name.
+    pub fn apply_variant(&mut self, name: &str) -> Result<(), Error> {
+        let variant = self.get_variant(name)?;
+
+        // An entry without an organization is grouped under the organization of the entry before
+        // it. If that entry is removed, the organization is moved to the next entry that's kept.
+        let mut header = None;
+        let mut experience = vec![];
+        for mut e in self.professional_experience.drain(..) {
+            if e.organization.is_some() {
+                header = None;
+            }
+            if variant.matches(&e.tags) {
+                if let Some((organization, location)) = header.take() {
+                    e.organization = organization;
+                    e.location = location;
+                }
+                variant.retain(&mut e.experience);
+                experience.push(e);
+            } else if e.organization.is_some() {
+                header = Some((e.organization, e.location));
+            }
+        }
+        self.professional_experience = experience;
+
+        if let Some(other_experience) = &mut self.other_experience {
+            other_experience
+                .projects
+                .retain(|x| variant.matches(&x.tags));
+            if other_experience.projects.is_empty() {
+                self.other_experience = None;
+            }
+        }
+
+        if let Some(technologies) = &mut self.technologies {
+            variant.retain(&mut technologies.technologies);
+            if technologies.technologies.is_empty() {
+                self.technologies = None;
+            }
+        }
+
+        self.education.retain(|x| variant.matches(&x.tags));
+        for education in self.education.iter_mut() {
+            variant.retain(&mut education.coursework);
+            if let Some(extras) = &mut education.extras {
+                variant.retain(extras);
+            }
+        }
+
+        self.certifications.retain(|x| variant.matches(&x.tags));
+
+        for section in self.custom_sections.iter_mut() {
+            section.items.retain(|x| variant.matches(&x.tags));
+            section
+                .items
+                .iter_mut()
+                .for_each(|x| variant.retain(&mut x.bullets));
+        }
+        self.custom_sections.retain(|x| !x.items.is_empty());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::Arguments;
+    use crate::config::Config;
+    use crate::data::variant::Variant;
+    use crate::data::{
+        Certification, CustomSection, CustomSectionItem, OtherExperience, ProfessionalExperience,
+        ProjectInfo, Resume, TaggedText, Technologies,
+    };
+    use crate::error::Error;
+
+    fn tags(x: &[&str]) -> Vec<String> {
+        x.iter().map(|x| x.to_string()).collect()
+    }
+
+    fn tagged(text: &str, x: &[&str]) -> TaggedText {
+        TaggedText {
+            text: String::from(text),
+            tags: tags(x),
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let v = Variant {
+            include: tags(&["backend", "infra"]),
+            exclude: tags(&["frontend"]),
+        };
+        assert!(v.matches(&[]));
+        assert!(v.matches(&tags(&["backend"])));
+        assert!(v.matches(&tags(&["infra", "management"])));
+        assert!(!v.matches(&tags(&["management"])));
+        assert!(!v.matches(&tags(&["backend", "frontend"])));
+
+        let v = Variant {
+            include: vec![],
+            exclude: tags(&["frontend"]),
+        };
+        assert!(v.matches(&tags(&["management"])));
+        assert!(!v.matches(&tags(&["frontend"])));
+    }
+
+    #[test]
+    fn test_get_variant() {
+        let mut resume = Resume::default();
+        assert_eq!(
+            resume.get_variant("backend").unwrap(),
+            Variant {
+                include: tags(&["backend"]),
+                exclude: vec![],
+            }
+        );
+
+        let v = Variant {
+            include: tags(&["infra"]),
+            exclude: tags(&["frontend"]),
+        };
+        resume.variants.insert(String::from("backend"), v.clone());
+        assert_eq!(resume.get_variant("backend").unwrap(), v);
+
+        // Once variants are defined, a name that isn't one of them is a mistake
+        assert!(matches!(
+            resume.get_variant("bakcend"),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_variant() {
+        let mut resume = Resume {
+            professional_experience: vec![
+                ProfessionalExperience {
+                    organization: Some(String::from("organizationA")),
+                    location: Some(String::from("locationA")),
+                    position: String::from("frontend"),
+                    tags: tags(&["frontend"]),
+                    ..Default::default()
+                },
+                ProfessionalExperience {
+                    position: String::from("backend"),
+                    experience: vec![
+                        TaggedText::from("foo"),
+                        tagged("bar", &["frontend"]),
+                        tagged("baz", &["backend"]),
+                    ],
+                    tags: tags(&["backend"]),
+                    ..Default::default()
+                },
+                ProfessionalExperience {
+                    organization: Some(String::from("organizationB")),
+                    location: Some(String::from("locationB")),
+                    position: String::from("untagged"),
+                    ..Default::default()
+                },
+            ],
+            other_experience: Some(OtherExperience {
+                projects: vec![ProjectInfo {
+                    project_name: String::from("frontend"),
+                    tags: tags(&["frontend"]),
+                    ..Default::default()
+                }],
+            }),
+            technologies: Some(Technologies {
+                technologies: vec![
+                    tagged("Foo", &["backend"]),
+                    tagged("Bar", &["frontend"]),
+                    TaggedText::from("Baz"),
+                ],
+            }),
+            certifications: vec![
+                Certification {
+                    name: String::from("frontend"),
+                    tags: tags(&["frontend"]),
+                    ..Default::default()
+                },
+                Certification {
+                    name: String::from("untagged"),
+                    ..Default::default()
+                },
+            ],
+            custom_sections: vec![
+                CustomSection {
+                    title: String::from("Talks"),
+                    items: vec![
+                        CustomSectionItem {
+                            title: Some(String::from("frontend")),
+                            tags: tags(&["frontend"]),
+                            ..Default::default()
+                        },
+                        CustomSectionItem {
+                            title: Some(String::from("untagged")),
+                            bullets: vec![tagged("foo", &["frontend"]), TaggedText::from("bar")],
+                            ..Default::default()
+                        },
+                    ],
+                },
+                CustomSection {
+                    title: String::from("Awards"),
+                    items: vec![CustomSectionItem {
+                        title: Some(String::from("frontend")),
+                        tags: tags(&["frontend"]),
+                        ..Default::default()
+                    }],
+                },
+            ],
+            ..Default::default()
+        };
+
+        resume.apply_variant("backend").unwrap();
+
+        let exp = &resume.professional_experience;
+        assert_eq!(exp.len(), 2);
+        assert_eq!(exp[0].position, "backend");
+        assert_eq!(exp[0].organization, Some(String::from("organizationA")));
+        assert_eq!(exp[0].location, Some(String::from("locationA")));
+        assert_eq!(
+            exp[0].experience,
+            vec![TaggedText::from("foo"), tagged("baz", &["backend"])]
+        );
+        assert_eq!(exp[1].position, "untagged");
+        assert_eq!(exp[1].organization, Some(String::from("organizationB")));
+        assert!(resume.other_experience.is_none());
+        assert_eq!(
+            resume.technologies.unwrap().get_technologies(),
+            vec!["Foo", "Baz"]
+        );
+        assert_eq!(resume.certifications.len(), 1);
+        assert_eq!(resume.certifications[0].name, "untagged");
+        assert_eq!(resume.custom_sections.len(), 1);
+        let items = &resume.custom_sections[0].items;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, Some(String::from("untagged")));
+        assert_eq!(items[0].bullets, vec![TaggedText::from("bar")]);
+    }
+
+    #[test]
+    fn test_read_variant() {
+        let config = Config {
+            args: Arguments {
+                variant: Some(String::from("backend")),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let resume = Resume::read_from_config_file("tst/test_variants.toml", &config).unwrap();
+
+        assert_eq!(resume.professional_experience.len(), 1);
+        assert_eq!(
+            resume.professional_experience[0].experience,
+            vec![TaggedText::from("FooA"), tagged("BazA", &["infra"])]
+        );
+        assert_eq!(
+            resume.technologies.unwrap().get_technologies(),
+            vec!["Foo", "Baz"]
+        );
+        let projects = resume.other_experience.unwrap().projects;
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].project_name, "Backend Project");
+        assert_eq!(
+            resume.education[0].extras,
+            Some(vec![TaggedText::from("Foo")])
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use crate::data::unknown_keys::UnknownKey;
+use notify_debouncer_mini::notify;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The errors that can occur while reading, validating and rendering a resume.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing a file or directory failed.
+    Io { path: PathBuf, source: io::Error },
+    /// A toml file couldn't be parsed. The line and column are 1-based.
+    Toml {
+        path: Option<PathBuf>,
+        line: Option<usize>,
+        column: Option<usize>,
+        source: toml::de::Error,
+    },
+    /// A json file couldn't be parsed.
+    Json {
+        path: Option<PathBuf>,
+        source: serde_json::Error,
+    },
+    /// A yaml file couldn't be parsed.
+    Yaml {
+        path: Option<PathBuf>,
+        source: serde_yaml::Error,
+    },
+    /// The resume data file has keys that don't match any field of the resume.
+    UnknownKeys {
+        path: PathBuf,
+        keys: Vec<UnknownKey>,
+    },
+    /// The resume data was parsed successfully, but isn't valid.
+    Validation(String),
+    /// The format config was parsed successfully, but isn't valid. `path` is the format config
+    /// file, if it's known.
+    FormatConfig {
+        path: Option<PathBuf>,
+        message: String,
+    },
+    /// The `check` command found problems that are configured as errors.
+    Check(String),
+    /// Running the LaTeX engine failed. `source` is set if the command couldn't be run at all,
+    /// `log` holds the relevant part of its output if the command ran but failed.
+    Latex {
+        command: String,
+        path: PathBuf,
+        source: Option<io::Error>,
+        log: Option<String>,
+    },
+    /// The LaTeX engine didn't finish within `pdf_config.timeout_secs`.
+    LatexTimeout {
+        command: String,
+        path: PathBuf,
+        seconds: u64,
+    },
+    /// The PDF has more pages than `pdf_config.max_pages`. `adjustments` lists the shrink steps
+    /// that were applied before giving up.
+    PageLimit {
+        path: PathBuf,
+        pages: usize,
+        max_pages: usize,
+        adjustments: Vec<String>,
+    },
+    /// A template couldn't be parsed or rendered.
+    Template {
+        path: PathBuf,
+        source: minijinja::Error,
+    },
+    /// A renderer failed to render an element of the resume.
+    Render(String),
+    /// Watching the input files for changes failed.
+    Watch(notify::Error),
+    /// The preview server couldn't be started.
+    Serve(String),
+}
+
+impl Error {
+    pub fn io(path: &Path, source: io::Error) -> Error {
+        Error::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// An invalid format config, before the path of its file is attached with [Self::with_path].
+    pub fn format_config(message: String) -> Error {
+        Error::FormatConfig {
+            path: None,
+            message,
+        }
+    }
+
+    /// Attach the path of the file that was being parsed to a parse error or an invalid format
+    /// config. Other errors are returned unchanged.
+    pub fn with_path(self, file_name: &str) -> Error {
+        let file_name = Some(PathBuf::from(file_name));
+        match self {
+            Error::Toml {
+                line,
+                column,
+                source,
+                ..
+            } => Error::Toml {
+                path: file_name,
+                line,
+                column,
+                source,
+            },
+            Error::Json { source, .. } => Error::Json {
+                path: file_name,
+                source,
+            },
+            Error::Yaml { source, .. } => Error::Yaml {
+                path: file_name,
+                source,
+            },
+            Error::FormatConfig { message, .. } => Error::FormatConfig {
+                path: file_name,
+                message,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(
+                f,
+                "An error occurred while accessing [{}]: {}",
+                path.display(),
+                source
+            ),
+            // The toml error message already includes the line and column
+            Error::Toml { path, source, .. } => {
+                write!(f, "An error occurred while parsing toml")?;
+                if let Some(path) = path {
+                    write!(f, " [{}]", path.display())?;
+                }
+                write!(f, ": {}", source)
+            }
+            Error::Json { path, source } => {
+                write!(f, "An error occurred while parsing json")?;
+                if let Some(path) = path {
+                    write!(f, " [{}]", path.display())?;
+                }
+                write!(f, ": {}", source)
+            }
+            Error::Yaml { path, source } => {
+                write!(f, "An error occurred while parsing yaml")?;
+                if let Some(path) = path {
+                    write!(f, " [{}]", path.display())?;
+                }
+                write!(f, ": {}", source)
+            }
+            Error::UnknownKeys { path, keys } => {
+                write!(
+                    f,
+                    "An error occurred while reading [{}]: found keys that don't match any field. Fix them, or run with --lenient to ignore them:",
+                    path.display()
+                )?;
+                for key in keys {
+                    write!(f, "\n  {}", key)?;
+                }
+                Ok(())
+            }
+            Error::Validation(e) => write!(f, "The resume is not valid: {}", e),
+            Error::FormatConfig { path, message } => {
+                write!(f, "The format config")?;
+                if let Some(path) = path {
+                    write!(f, " [{}]", path.display())?;
+                }
+                write!(f, " is not valid: {}", message)
+            }
+            Error::Check(e) => write!(f, "The resume didn't pass the checks: {}", e),
+            Error::Latex {
+                command,
+                path,
+                source,
+                log,
+            } => {
+                write!(
+                    f,
+                    "An error occurred while running the {} command on [{}]",
+                    command,
+                    path.display()
+                )?;
+                if let Some(source) = source {
+                    write!(f, ": {}", source)?;
+                }
+                if let Some(log) = log {
+                    write!(f, "\n{}", log)?;
+                }
+                Ok(())
+            }
+            Error::LatexTimeout {
+                command,
+                path,
+                seconds,
+            } => write!(
+                f,
+                "An error occurred while running the {} command on [{}]: it didn't finish within {} seconds. Set pdf_config.timeout_secs to allow more time.",
+                command,
+                path.display(),
+                seconds
+            ),
+            Error::PageLimit {
+                path,
+                pages,
+                max_pages,
+                adjustments,
+            } => {
+                write!(
+                    f,
+                    "An error occurred while rendering [{}]: it has {} pages, but pdf_config.max_pages is {}",
+                    path.display(),
+                    pages,
+                    max_pages
+                )?;
+                if adjustments.is_empty() {
+                    write!(f, ". Set pdf_config.shrink_to_fit to try to make it fit.")
+                } else {
+                    write!(f, ", even after shrinking it ({}).", adjustments.join(", "))
+                }
+            }
+            Error::Template { path, source } => write!(
+                f,
+                "An error occurred while rendering the template [{}]: {}",
+                path.display(),
+                source
+            ),
+            Error::Render(e) => write!(f, "{}", e),
+            Error::Watch(e) => write!(f, "An error occurred while watching for changes: {}", e),
+            Error::Serve(e) => write!(f, "An error occurred while starting the server: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Toml { source, .. } => Some(source),
+            Error::Json { source, .. } => Some(source),
+            Error::Yaml { source, .. } => Some(source),
+            Error::Watch(source) => Some(source),
+            Error::Template { source, .. } => Some(source),
+            Error::Latex {
+                source: Some(source),
+                ..
+            } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(source: toml::de::Error) -> Self {
+        let line_col = source.line_col();
+        Error::Toml {
+            path: None,
+            line: line_col.map(|(line, _)| line + 1),
+            column: line_col.map(|(_, column)| column + 1),
+            source,
+        }
+    }
+}
+
+impl From<notify::Error> for Error {
+    fn from(source: notify::Error) -> Self {
+        Error::Watch(source)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(source: serde_json::Error) -> Self {
+        Error::Json { path: None, source }
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(source: serde_yaml::Error) -> Self {
+        Error::Yaml { path: None, source }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::util::toml_from_string;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_toml_error() {
+        let e = toml_from_string::<HashMap<String, String>>("foo = \"bar\"\nbaz = ")
+            .unwrap_err()
+            .with_path("foo.toml");
+        match &e {
+            Error::Toml {
+                path, line, column, ..
+            } => {
+                assert_eq!(path.as_ref().unwrap().to_str(), Some("foo.toml"));
+                assert_eq!(*line, Some(2));
+                assert_eq!(*column, Some(7));
+            }
+            _ => panic!("Unexpected error: {:?}", e),
+        }
+        assert!(e
+            .to_string()
+            .starts_with("An error occurred while parsing toml [foo.toml]: "));
+    }
+
+    #[test]
+    fn test_with_path_ignores_other_errors() {
+        let e = Error::Validation(String::from("foo")).with_path("foo.toml");
+        assert_eq!(e.to_string(), "The resume is not valid: foo");
+    }
+
+    #[test]
+    fn test_format_config_with_path() {
+        let e = Error::format_config(String::from("foo"));
+        assert_eq!(e.to_string(), "The format config is not valid: foo");
+        let e = e.with_path("format.toml");
+        assert_eq!(
+            e.to_string(),
+            "The format config [format.toml] is not valid: foo"
+        );
+    }
+}
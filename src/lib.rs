@@ -2,7 +2,11 @@
 extern crate serde_derive;
 extern crate serde;
 
+pub mod check;
 pub mod config;
 pub mod data;
+pub mod error;
 pub mod renderer;
+pub mod serve;
 pub mod util;
+pub mod watch;
@@ -1,17 +1,30 @@
 extern crate resume_generator;
 
-use resume_generator::config::Config;
-use resume_generator::data::Resume;
-use resume_generator::renderer::render_resume;
+use resume_generator::config::arguments::{Arguments, Command};
+use resume_generator::{check, renderer, serve, watch};
+use std::process;
 
 fn main() {
-    let r = Config::new_and_parse_args().and_then(|config| {
-        let resume = Resume::read_from_config_file(&config.args.resume_input, &config)?;
-        render_resume(&resume, &config)
-    });
+    let args = Arguments::parse_args();
+    let r = if let Some(Command::Serve { port }) = args.command {
+        serve::serve(args, port)
+    } else if let Some(Command::Check) = args.command {
+        check::check(&args).and_then(|report| {
+            println!("{}", report);
+            report.into_result()
+        })
+    } else if args.watch {
+        watch::watch(args)
+    } else {
+        renderer::render(&args).and_then(|report| {
+            println!("{}", report);
+            report.into_result()
+        })
+    };
     if let Err(e) = r {
-        println!();
-        println!("An error occurred, please try again.");
-        println!("Error: {}", e);
+        eprintln!();
+        eprintln!("An error occurred, please try again.");
+        eprintln!("Error: {}", e);
+        process::exit(1);
     }
 }
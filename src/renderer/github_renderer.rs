@@ -1,10 +1,34 @@
+use crate::config::format_config::{ContactField, Section};
 use crate::config::Config;
-use crate::data::{Objective, OtherExperience, PersonalInfo, ProjectInfo, Resume, Technologies};
-use crate::renderer::markdown_renderer::MarkdownRenderer;
-use crate::renderer::Renderer;
+use crate::data::{
+    Certification, CustomSection, Education, Objective, OtherExperience, PersonalInfo,
+    ProfessionalExperience, ProjectInfo, Resume, Technologies,
+};
+use crate::error::Error;
+use crate::renderer::markdown_renderer::{contact_list, MarkdownRenderer};
+use crate::renderer::{render_option, Renderer};
 use crate::util::write_string_to_file;
 use std::path::PathBuf;
 
+/// The default order of the sections. Experience, education and certifications are left out of the
+/// GitHub profile unless they're configured.
+const SECTIONS: &[Section] = &[
+    Section::Objective,
+    Section::PersonalInfo,
+    Section::Projects,
+    Section::Technologies,
+];
+
+/// The default contact fields and their order. Email, phone and LinkedIn are left out of the
+/// GitHub profile unless they're configured.
+const CONTACT: &[ContactField] = &[
+    ContactField::Github,
+    ContactField::Location,
+    ContactField::Website,
+    ContactField::Pronouns,
+    ContactField::Other,
+];
+
 #[derive(Default)]
 pub struct GitHubRenderer {
     md: MarkdownRenderer,
@@ -17,34 +41,44 @@ impl GitHubRenderer {
 }
 
 impl Renderer<Resume, PathBuf> for GitHubRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
         let s: String = self.render(element, config)?;
 
         write_string_to_file(
             &s,
             config.args.output_dir.as_ref(),
-            &format!("{}-github", &config.args.output_name),
+            &format!("{}-github", config.args.output_name()),
             Some(String::from("md")).as_ref(),
         )
     }
 }
 
 impl Renderer<Resume, String> for GitHubRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<String, Error> {
         let mut text = format!("# {}", element.name);
-        if let Some(obj) = &element.objective {
-            text = format!("{}\n\n{}", text, self.render(obj, config)?);
-        }
-        text = format!(
-            "{}\n\n{}",
-            text,
-            self.render(&element.personal_info, config)?
+        let sections = config.format_config.sections(
+            &config.format_config.github_config.content.sections,
+            SECTIONS,
         );
-        if let Some(e) = &element.other_experience {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
-        }
-        if let Some(e) = &element.technologies {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
+        for section in sections {
+            let rendered = match section {
+                Section::PersonalInfo => Some(self.render(&element.personal_info, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+                Section::Objective => render_option(self, &element.objective, config)?,
+                Section::Experience if element.professional_experience.is_empty() => None,
+                Section::Experience => Some(self.render(&element.professional_experience, config)?),
+                Section::Projects => render_option(self, &element.other_experience, config)?,
+                Section::Technologies => render_option(self, &element.technologies, config)?,
+                Section::Education if element.education.is_empty() => None,
+                Section::Education => Some(self.render(&element.education, config)?),
+                Section::Certifications if element.certifications.is_empty() => None,
+                Section::Certifications => Some(self.render(&element.certifications, config)?),
+                Section::Custom => Some(self.render(&element.custom_sections, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+            };
+            if let Some(rendered) = rendered {
+                text = format!("{}\n\n{}", text, rendered);
+            }
         }
 
         Ok(text)
@@ -52,29 +86,50 @@ impl Renderer<Resume, String> for GitHubRenderer {
 }
 
 impl Renderer<PersonalInfo, String> for GitHubRenderer {
-    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, String> {
-        let info = self.md.render(element, config)?;
-        // Don't want to display email or linkedin on GitHub
-        // todo: enable configuring what to display in the toml
-        // todo: is there a way to do this without using 'to_owned'?
-        let info = info
-            .split('\n')
-            .map(|x| x.to_owned())
-            .filter(|x| !x.contains(&element.email))
-            .collect::<Vec<String>>()
-            .join("\n");
-        Ok(info)
+    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, Error> {
+        let fields = config
+            .format_config
+            .contact(&config.format_config.github_config.content.contact, CONTACT);
+        Ok(contact_list(element, &fields))
     }
 }
 
 impl Renderer<Objective, String> for GitHubRenderer {
-    fn render(&self, element: &Objective, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Objective, config: &Config) -> Result<String, Error> {
+        self.md.render(element, config)
+    }
+}
+
+impl Renderer<Vec<ProfessionalExperience>, String> for GitHubRenderer {
+    fn render(
+        &self,
+        element: &Vec<ProfessionalExperience>,
+        config: &Config,
+    ) -> Result<String, Error> {
+        self.md.render(element, config)
+    }
+}
+
+impl Renderer<Vec<CustomSection>, String> for GitHubRenderer {
+    fn render(&self, element: &Vec<CustomSection>, config: &Config) -> Result<String, Error> {
+        self.md.render(element, config)
+    }
+}
+
+impl Renderer<Vec<Education>, String> for GitHubRenderer {
+    fn render(&self, element: &Vec<Education>, config: &Config) -> Result<String, Error> {
+        self.md.render(element, config)
+    }
+}
+
+impl Renderer<Vec<Certification>, String> for GitHubRenderer {
+    fn render(&self, element: &Vec<Certification>, config: &Config) -> Result<String, Error> {
         self.md.render(element, config)
     }
 }
 
 impl Renderer<OtherExperience, String> for GitHubRenderer {
-    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, Error> {
         let mut text = "## Projects".to_string();
 
         // todo: clean up?
@@ -84,7 +139,9 @@ impl Renderer<OtherExperience, String> for GitHubRenderer {
             .map(|e| self.render(e, config))
             .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
             .unwrap_or_else(|| {
-                Err("An error occurred while rendering other experience to markdown.".to_string())
+                Err(Error::Render(
+                    "An error occurred while rendering other experience to markdown.".to_string(),
+                ))
             })?;
 
         text = format!("{}\n{}", text, projects);
@@ -94,27 +151,54 @@ impl Renderer<OtherExperience, String> for GitHubRenderer {
 }
 
 impl Renderer<ProjectInfo, String> for GitHubRenderer {
-    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, Error> {
         self.md.render(element, config)
     }
 }
 
 impl Renderer<Technologies, String> for GitHubRenderer {
-    fn render(&self, element: &Technologies, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Technologies, config: &Config) -> Result<String, Error> {
         self.md.render(element, config)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::config::format_config::{FormatConfig, TextConfig};
+    use crate::config::format_config::{FormatConfig, Section, TextConfig};
     use crate::config::Config;
     use crate::data::{
-        Objective, OtherExperience, OtherPersonalInfo, PersonalInfo, ProjectInfo, Technologies,
+        Objective, OtherExperience, OtherPersonalInfo, PersonalInfo, ProfessionalExperience,
+        ProjectInfo, Resume, TaggedText, Technologies,
     };
     use crate::renderer::github_renderer::GitHubRenderer;
     use crate::renderer::Renderer;
 
+    #[test]
+    fn test_sections() {
+        let x = Resume {
+            name: String::from("Foo Bar"),
+            objective: Some(Objective {
+                objective: String::from("objective"),
+            }),
+            professional_experience: vec![ProfessionalExperience {
+                position: String::from("position"),
+                experience: vec![TaggedText::from("experience")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let rendered: String = GitHubRenderer::new().render(&x, &get_config()).unwrap();
+        assert!(rendered.starts_with("# Foo Bar\n\nobjective"));
+        assert!(!rendered.contains("## Experience"));
+
+        let mut config = get_config();
+        config.format_config.github_config.content.sections = Some(vec![Section::Experience]);
+        let rendered: String = GitHubRenderer::new().render(&x, &config).unwrap();
+        assert!(rendered.starts_with("# Foo Bar\n\n## Experience"));
+        assert!(!rendered.contains("objective"));
+    }
+
     #[test]
     fn test_personal_info() {
         let a = OtherPersonalInfo {
@@ -128,13 +212,18 @@ mod test {
         let x = PersonalInfo {
             email: String::from("foo@bar.com"),
             github: String::from("github.com/foo"),
+            phone: Some(String::from("555-555-5555")),
+            location: Some(String::from("Seattle, WA")),
+            linkedin: Some(String::from("linkedin.com/in/foo")),
+            pronouns: Some(String::from("they/them")),
             other: Some(vec![a, b]),
+            ..Default::default()
         };
         let rendered = GitHubRenderer::new().render(&x, &get_config()).unwrap();
 
         assert_eq!(
             rendered,
-            "## Find me here\n- GitHub: [github.com/foo](https://github.com/foo)\n- Foo: [example.com/foo](https://example.com/foo)\n- Bar: [example.com/bar](https://example.com/bar)"
+            "## Find me here\n- GitHub: [github.com/foo](https://github.com/foo)\n- Location: Seattle, WA\n- Pronouns: they/them\n- Foo: [example.com/foo](https://example.com/foo)\n- Bar: [example.com/bar](https://example.com/bar)"
         );
     }
 
@@ -179,26 +268,26 @@ mod test {
 
         assert_eq!(
             rendered,
-            "## Projects\n- [project_nameA](https://example.com) - descriptionA\n- [project_nameB](https://example.com) - Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor\n  incididunt ut\n- [project_nameC](https://example.com) - descriptionC"
+            "## Projects\n- [project_nameA](https://example.com) - descriptionA\n- [project_nameB](https://example.com) - Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n- [project_nameC](https://example.com) - descriptionC"
         );
     }
 
     #[test]
     fn test_technologies() {
         let tech = vec![
-            String::from("Lorem"),
-            String::from("ipsum"),
-            String::from("dolor"),
-            String::from("sit"),
-            String::from("amet"),
-            String::from("consectetur"),
-            String::from("adipiscing"),
-            String::from("elit"),
-            String::from("sed"),
-            String::from("do"),
-            String::from("eiusmod"),
-            String::from("tempor"),
-            String::from("incididunt"),
+            TaggedText::from("Lorem"),
+            TaggedText::from("ipsum"),
+            TaggedText::from("dolor"),
+            TaggedText::from("sit"),
+            TaggedText::from("amet"),
+            TaggedText::from("consectetur"),
+            TaggedText::from("adipiscing"),
+            TaggedText::from("elit"),
+            TaggedText::from("sed"),
+            TaggedText::from("do"),
+            TaggedText::from("eiusmod"),
+            TaggedText::from("tempor"),
+            TaggedText::from("incididunt"),
         ];
 
         let x = Technologies { technologies: tech };
@@ -214,7 +303,10 @@ mod test {
     fn get_config() -> Config {
         Config {
             format_config: FormatConfig {
-                text_config: TextConfig { width: 50 },
+                text_config: TextConfig {
+                    width: 50,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             ..Default::default()
@@ -0,0 +1,837 @@
+use crate::config::format_config::{ContactField, Section};
+use crate::config::Config;
+use crate::data::markup::{parse, render, Style};
+use crate::data::{
+    Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+    OtherExperience, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, Technologies,
+};
+use crate::error::Error;
+use crate::renderer::{render_option, Renderer};
+use crate::util::{
+    add_https_to_url, cover_letter_file_name, date_string, escape_html, time_range_string,
+    write_string_to_file,
+};
+use std::path::PathBuf;
+
+/// The default order of the sections.
+pub(crate) const SECTIONS: &[Section] = &[
+    Section::PersonalInfo,
+    Section::Objective,
+    Section::Experience,
+    Section::Projects,
+    Section::Technologies,
+    Section::Education,
+    Section::Certifications,
+    Section::Custom,
+];
+
+/// The default contact fields and their order.
+pub(crate) const CONTACT: &[ContactField] = &[
+    ContactField::Email,
+    ContactField::Github,
+    ContactField::Phone,
+    ContactField::Location,
+    ContactField::Linkedin,
+    ContactField::Website,
+    ContactField::Pronouns,
+    ContactField::Other,
+];
+
+/// Renders a self-contained HTML page. All styling is inlined so the file can be hosted as-is or
+/// printed to PDF from a browser.
+#[derive(Default)]
+pub struct HtmlRenderer;
+
+impl HtmlRenderer {
+    pub fn new() -> HtmlRenderer {
+        HtmlRenderer
+    }
+}
+
+impl Renderer<Resume, PathBuf> for HtmlRenderer {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
+        let ext = String::from("html");
+
+        if let Some(c) = &element.cover_letter {
+            let cover_letter: String = self.render(c, config)?;
+            write_string_to_file(
+                &cover_letter,
+                config.args.output_dir.as_ref(),
+                &cover_letter_file_name(config),
+                Some(&ext),
+            )?;
+        }
+
+        let resume: String = self.render(element, config)?;
+        write_string_to_file(
+            &resume,
+            config.args.output_dir.as_ref(),
+            config.args.output_name(),
+            Some(&ext),
+        )
+    }
+}
+
+impl Renderer<Resume, String> for HtmlRenderer {
+    fn render(&self, element: &Resume, config: &Config) -> Result<String, Error> {
+        let mut body = format!(
+            "<header>\n<h1>{}</h1>\n</header>",
+            escape_html(&element.name)
+        );
+        let sections = config
+            .format_config
+            .sections(&config.format_config.html_config.content.sections, SECTIONS);
+        for section in sections {
+            let rendered = match section {
+                Section::PersonalInfo => Some(self.render(&element.personal_info, config)?),
+                Section::Objective => render_option(self, &element.objective, config)?,
+                Section::Experience if element.professional_experience.is_empty() => None,
+                Section::Experience => Some(self.render(&element.professional_experience, config)?),
+                Section::Projects => render_option(self, &element.other_experience, config)?,
+                Section::Technologies => render_option(self, &element.technologies, config)?,
+                Section::Education if element.education.is_empty() => None,
+                Section::Education => Some(self.render(&element.education, config)?),
+                Section::Certifications if element.certifications.is_empty() => None,
+                Section::Certifications => Some(self.render(&element.certifications, config)?),
+                Section::Custom => Some(self.render(&element.custom_sections, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+            };
+            if let Some(rendered) = rendered {
+                body = format!("{}\n{}", body, rendered);
+            }
+        }
+
+        Ok(html_document(
+            &element.name,
+            &format!("<main class=\"resume\">\n{}\n</main>", body),
+        ))
+    }
+}
+
+impl Renderer<PersonalInfo, String> for HtmlRenderer {
+    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, Error> {
+        let fields = config
+            .format_config
+            .contact(&config.format_config.html_config.content.contact, CONTACT);
+        let items = element
+            .contact_items(&fields)
+            .iter()
+            .map(|x| {
+                let text = match &x.link {
+                    Some(link) => format!(
+                        "<a href=\"{}\">{}</a>",
+                        escape_html(link),
+                        escape_html(&x.text)
+                    ),
+                    None => escape_html(&x.text),
+                };
+                if x.needs_label() {
+                    format!("<li>{}: {}</li>", escape_html(&x.label), text)
+                } else {
+                    format!("<li>{}</li>", text)
+                }
+            })
+            .collect::<Vec<String>>();
+
+        Ok(format!(
+            "<ul class=\"contact\">\n{}\n</ul>",
+            items.join("\n")
+        ))
+    }
+}
+
+impl Renderer<Objective, String> for HtmlRenderer {
+    fn render(&self, element: &Objective, _config: &Config) -> Result<String, Error> {
+        Ok(format!(
+            "<section class=\"objective\">\n<p>{}</p>\n</section>",
+            html(&element.objective)
+        ))
+    }
+}
+
+impl Renderer<Vec<ProfessionalExperience>, String> for HtmlRenderer {
+    fn render(
+        &self,
+        element: &Vec<ProfessionalExperience>,
+        config: &Config,
+    ) -> Result<String, Error> {
+        let exp = element
+            .iter()
+            .map(|e| self.render(e, config))
+            .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
+            .unwrap_or_else(|| {
+                Err(Error::Render(String::from(
+                    "An error occurred while rendering professional experience to HTML.",
+                )))
+            })?;
+
+        Ok(format!(
+            "<section class=\"experience\">\n<h2>Experience</h2>\n{}\n</section>",
+            exp
+        ))
+    }
+}
+
+impl Renderer<ProfessionalExperience, String> for HtmlRenderer {
+    fn render(&self, element: &ProfessionalExperience, config: &Config) -> Result<String, Error> {
+        let mut text = String::from("<article class=\"position\">\n");
+        if let (Some(org), Some(location)) = (&element.organization, &element.location) {
+            text = format!(
+                "{}<div class=\"line\"><h3>{}</h3><span>{}</span></div>\n",
+                text,
+                escape_html(org),
+                escape_html(location)
+            );
+        }
+        text = format!(
+            "{}<div class=\"line\"><em>{}</em><span>{}</span></div>\n",
+            text,
+            escape_html(&element.position),
+            escape_html(&time_range_string(
+                &element.start,
+                &element.end,
+                &config.format_config.html_config.dates.date_format,
+                config.format_config.html_config.dates.show_duration,
+            ))
+        );
+
+        let exp = element
+            .experience
+            .iter()
+            .map(|e| format!("<li>{}</li>", html(e)))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Ok(format!("{}<ul>\n{}\n</ul>\n</article>", text, exp))
+    }
+}
+
+impl Renderer<Vec<CustomSection>, String> for HtmlRenderer {
+    fn render(&self, element: &Vec<CustomSection>, config: &Config) -> Result<String, Error> {
+        Ok(element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n"))
+    }
+}
+
+impl Renderer<CustomSection, String> for HtmlRenderer {
+    fn render(&self, element: &CustomSection, config: &Config) -> Result<String, Error> {
+        let items = element
+            .items
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n");
+
+        Ok(format!(
+            "<section class=\"custom\">\n<h2>{}</h2>\n{}\n</section>",
+            escape_html(&element.title),
+            items
+        ))
+    }
+}
+
+impl Renderer<CustomSectionItem, String> for HtmlRenderer {
+    fn render(&self, element: &CustomSectionItem, _config: &Config) -> Result<String, Error> {
+        let line = |tag: &str, l: &Option<String>, r: &Option<String>| match (l, r) {
+            (None, None) => String::new(),
+            _ => format!(
+                "<div class=\"line\"><{tag}>{}</{tag}><span>{}</span></div>\n",
+                escape_html(l.as_deref().unwrap_or_default()),
+                escape_html(r.as_deref().unwrap_or_default()),
+                tag = tag
+            ),
+        };
+        let mut text = format!(
+            "<article>\n{}{}",
+            line("h3", &element.title, &element.location),
+            line("em", &element.subtitle, &element.date)
+        );
+        if !element.bullets.is_empty() {
+            let bullets = element
+                .bullets
+                .iter()
+                .map(|e| format!("<li>{}</li>", html(e)))
+                .collect::<Vec<String>>()
+                .join("\n");
+            text = format!("{}<ul>\n{}\n</ul>\n", text, bullets);
+        }
+
+        Ok(format!("{}</article>", text))
+    }
+}
+
+impl Renderer<OtherExperience, String> for HtmlRenderer {
+    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, Error> {
+        let projects = element
+            .get_projects_for_resume()
+            .into_iter()
+            .map(|e| self.render(e, config))
+            .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
+            .unwrap_or_else(|| {
+                Err(Error::Render(String::from(
+                    "An error occurred while rendering other experience to HTML.",
+                )))
+            })?;
+
+        Ok(format!(
+            "<section class=\"projects\">\n<h2>Projects</h2>\n<ul>\n{}\n</ul>\n</section>",
+            projects
+        ))
+    }
+}
+
+impl Renderer<ProjectInfo, String> for HtmlRenderer {
+    fn render(&self, element: &ProjectInfo, _config: &Config) -> Result<String, Error> {
+        let name = if element.url.is_empty() {
+            escape_html(&element.project_name)
+        } else {
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&add_https_to_url(&element.url)),
+                escape_html(&element.project_name)
+            )
+        };
+        Ok(format!("<li>{}: {}</li>", name, html(&element.description)))
+    }
+}
+
+impl Renderer<Technologies, String> for HtmlRenderer {
+    fn render(&self, element: &Technologies, _config: &Config) -> Result<String, Error> {
+        Ok(format!(
+            "<section class=\"technologies\">\n<h2>Technologies</h2>\n<p>{}</p>\n</section>",
+            escape_html(&element.get_technologies().join(", "))
+        ))
+    }
+}
+
+impl Renderer<Vec<Education>, String> for HtmlRenderer {
+    fn render(&self, element: &Vec<Education>, config: &Config) -> Result<String, Error> {
+        let education = element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n");
+
+        Ok(format!(
+            "<section class=\"education\">\n<h2>Education</h2>\n{}\n</section>",
+            education
+        ))
+    }
+}
+
+impl Renderer<Education, String> for HtmlRenderer {
+    fn render(&self, element: &Education, config: &Config) -> Result<String, Error> {
+        let graduation = element
+            .graduation
+            .map(|x| x.format(&config.format_config.html_config.dates.date_format))
+            .unwrap_or_default();
+        let mut text = format!(
+            "<article>\n<div class=\"line\"><h3>{}</h3><span>{}</span></div>\n<div class=\"line\"><em>{}</em><span>{}</span></div>\n",
+            escape_html(&element.school),
+            escape_html(&element.location),
+            escape_html(&element.degree_string()),
+            escape_html(&graduation)
+        );
+        let paragraphs = element
+            .details_string(!config.format_config.hide_gpa)
+            .into_iter()
+            .chain(element.coursework_string());
+        for paragraph in paragraphs {
+            text = format!("{}<p>{}</p>\n", text, escape_html(&paragraph));
+        }
+        if !element.get_extras().is_empty() {
+            let extras = element
+                .get_extras()
+                .iter()
+                .map(|e| format!("<li>{}</li>", html(e)))
+                .collect::<Vec<String>>()
+                .join("\n");
+            text = format!("{}<ul>\n{}\n</ul>\n", text, extras);
+        }
+
+        Ok(format!("{}</article>", text))
+    }
+}
+
+impl Renderer<Vec<Certification>, String> for HtmlRenderer {
+    fn render(&self, element: &Vec<Certification>, config: &Config) -> Result<String, Error> {
+        let certifications = element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n");
+
+        Ok(format!(
+            "<section class=\"certifications\">\n<h2>Certifications</h2>\n{}\n</section>",
+            certifications
+        ))
+    }
+}
+
+impl Renderer<Certification, String> for HtmlRenderer {
+    fn render(&self, element: &Certification, config: &Config) -> Result<String, Error> {
+        let name = match &element.url {
+            Some(url) => format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(&add_https_to_url(url)),
+                escape_html(&element.name)
+            ),
+            None => escape_html(&element.name),
+        };
+        let dates = element.dates(&config.format_config.html_config.dates.date_format);
+        let mut text = format!(
+            "<article>\n<div class=\"line\"><h3>{}</h3><span>{}</span></div>\n",
+            name,
+            escape_html(&dates)
+        );
+        if !element.issuer.is_empty() || element.credential_id.is_some() {
+            let credential_id = element
+                .credential_id
+                .as_ref()
+                .map(|x| format!("Credential ID: {}", x))
+                .unwrap_or_default();
+            text = format!(
+                "{}<div class=\"line\"><em>{}</em><span>{}</span></div>\n",
+                text,
+                escape_html(&element.issuer),
+                escape_html(&credential_id)
+            );
+        }
+
+        Ok(format!("{}</article>", text))
+    }
+}
+
+impl Renderer<CoverLetter, String> for HtmlRenderer {
+    fn render(&self, element: &CoverLetter, _config: &Config) -> Result<String, Error> {
+        let mut header = vec![];
+        if let Some(name) = &element.name {
+            header.push(escape_html(name));
+        }
+        if let Some(email) = &element.email {
+            header.push(format!(
+                "<a href=\"mailto:{email}\">{email}</a>",
+                email = escape_html(email)
+            ));
+        }
+        header.push(escape_html(&date_string()));
+
+        let paragraphs = element
+            .paragraphs
+            .iter()
+            .map(|p| format!("<p>{}</p>", html(p)))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let mut footer = format!("<p class=\"closing\">{}", escape_html(&element.closing));
+        if let Some(name) = &element.name {
+            footer = format!("{}<br>\n{}", footer, escape_html(name));
+        }
+        footer = format!("{}</p>", footer);
+
+        let body = format!(
+            "<main class=\"cover-letter\">\n<header>\n<p>{}</p>\n</header>\n<p class=\"salutation\">{}</p>\n{}\n{}\n</main>",
+            header.join("<br>\n"),
+            escape_html(&element.salutation),
+            paragraphs,
+            footer
+        );
+
+        Ok(html_document(
+            element.name.as_deref().unwrap_or_default(),
+            &body,
+        ))
+    }
+}
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        escape_html(title),
+        STYLE,
+        body
+    )
+}
+
+const STYLE: &str = r#"body {
+  margin: 0;
+  background: #f4f4f4;
+  color: #222;
+  font-family: "Times New Roman", Times, serif;
+  font-size: 11pt;
+  line-height: 1.35;
+}
+main {
+  box-sizing: border-box;
+  max-width: 8.5in;
+  margin: 2em auto;
+  padding: 0.75in;
+  background: #fff;
+  box-shadow: 0 0 0.5em rgba(0, 0, 0, 0.15);
+}
+header h1 {
+  margin: 0;
+  font-size: 1.6em;
+  text-align: center;
+}
+ul.contact {
+  display: flex;
+  flex-wrap: wrap;
+  justify-content: space-between;
+  margin: 0.5em 0 0;
+  padding: 0 0 0.25em;
+  border-bottom: 0.4pt solid #222;
+  list-style: none;
+}
+section {
+  margin-top: 1em;
+}
+section h2 {
+  margin: 0 0 0.4em;
+  font-size: 1em;
+  text-align: center;
+  text-transform: uppercase;
+}
+h3 {
+  margin: 0;
+  font-size: 1em;
+}
+.line {
+  display: flex;
+  justify-content: space-between;
+}
+article {
+  margin-bottom: 0.6em;
+}
+article ul, .projects ul {
+  margin: 0.2em 0 0;
+  padding-left: 1.5em;
+}
+.technologies p {
+  text-align: center;
+}
+.cover-letter p {
+  margin: 0 0 1em;
+}
+.cover-letter .salutation, .cover-letter .closing {
+  margin: 2em 0;
+}
+a {
+  color: inherit;
+}
+@media print {
+  @page {
+    size: letter;
+    margin: 0.75in;
+  }
+  body {
+    background: none;
+  }
+  main {
+    max-width: none;
+    margin: 0;
+    padding: 0;
+    box-shadow: none;
+  }
+  a {
+    text-decoration: none;
+  }
+  article, section h2 {
+    break-inside: avoid;
+  }
+  section h2 {
+    break-after: avoid;
+  }
+}
+"#;
+
+/// Escape text with inline markup for HTML and turn the markup into tags.
+pub(crate) fn html(s: &str) -> String {
+    render(
+        &parse(s),
+        &Style {
+            text: &escape_html,
+            bold: &|x| format!("<strong>{}</strong>", x),
+            emphasis: &|x| format!("<em>{}</em>", x),
+            code: &|x| format!("<code>{}</code>", x),
+            link: &|text, url| {
+                format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(&add_https_to_url(url)),
+                    text
+                )
+            },
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::Config;
+    use crate::data::date::ResumeDate;
+    use crate::data::{
+        Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+        OtherExperience, OtherPersonalInfo, PersonalInfo, ProfessionalExperience, ProjectInfo,
+        Resume, TaggedText, Technologies,
+    };
+    use crate::renderer::html_renderer::HtmlRenderer;
+    use crate::renderer::Renderer;
+    use crate::util::date_string;
+
+    #[test]
+    fn test_resume() {
+        let x = Resume {
+            name: String::from("Foo & Bar"),
+            professional_experience: vec![ProfessionalExperience {
+                position: String::from("position"),
+                experience: vec![TaggedText::from("experience")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let rendered: String = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert!(rendered.starts_with("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n"));
+        assert!(rendered.contains("<title>Foo &amp; Bar</title>"));
+        assert!(rendered.contains("@media print"));
+        assert!(rendered.contains("<main class=\"resume\">\n<header>\n<h1>Foo &amp; Bar</h1>\n</header>\n<ul class=\"contact\">"));
+    }
+
+    #[test]
+    fn test_personal_info() {
+        let a = OtherPersonalInfo {
+            item: String::from("Foo"),
+            url: Some(String::from("example.com/foo")),
+        };
+        let b = OtherPersonalInfo {
+            item: String::from("Bar"),
+            url: None,
+        };
+        let x = PersonalInfo {
+            email: String::from("foo@bar.com"),
+            github: String::from("github.com/foo"),
+            phone: Some(String::from("555-555-5555")),
+            location: Some(String::from("Seattle, WA")),
+            linkedin: Some(String::from("linkedin.com/in/foo")),
+            pronouns: Some(String::from("they/them")),
+            other: Some(vec![a, b]),
+            ..Default::default()
+        };
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<ul class=\"contact\">\n<li><a href=\"mailto:foo@bar.com\">foo@bar.com</a></li>\n<li><a href=\"https://github.com/foo\">github.com/foo</a></li>\n<li><a href=\"tel:5555555555\">555-555-5555</a></li>\n<li>Seattle, WA</li>\n<li><a href=\"https://linkedin.com/in/foo\">linkedin.com/in/foo</a></li>\n<li>they/them</li>\n<li>Foo: <a href=\"https://example.com/foo\">example.com/foo</a></li>\n<li>Bar</li>\n</ul>"
+        );
+    }
+
+    #[test]
+    fn test_objective() {
+        let x = Objective {
+            objective: String::from("Lorem <ipsum> dolor"),
+        };
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"objective\">\n<p>Lorem &lt;ipsum&gt; dolor</p>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_markup() {
+        let x = Objective {
+            objective: String::from(
+                "Cut **build times** by *half* with `sccache` and [more](example.com/a_b)",
+            ),
+        };
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(rendered, "<section class=\"objective\">\n<p>Cut <strong>build times</strong> by <em>half</em> with <code>sccache</code> and <a href=\"https://example.com/a_b\">more</a></p>\n</section>");
+    }
+
+    #[test]
+    fn test_professional_experience() {
+        let a = ProfessionalExperience {
+            organization: Some(String::from("organizationA")),
+            position: String::from("positionA"),
+            location: Some(String::from("locationA")),
+            start: ResumeDate::new(2020, 1).unwrap(),
+            end: ResumeDate::Present,
+            experience: vec![TaggedText::from("experienceA1"), TaggedText::from("R&D")],
+            tags: vec![],
+        };
+        let b = ProfessionalExperience {
+            organization: None,
+            position: String::from("positionB"),
+            location: None,
+            start: ResumeDate::new(2018, 1).unwrap(),
+            end: ResumeDate::new(2019, 12).unwrap(),
+            experience: vec![TaggedText::from("experienceB1")],
+            tags: vec![],
+        };
+        let x = vec![a, b];
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"experience\">\n<h2>Experience</h2>\n<article class=\"position\">\n<div class=\"line\"><h3>organizationA</h3><span>locationA</span></div>\n<div class=\"line\"><em>positionA</em><span>Jan 2020 - Present</span></div>\n<ul>\n<li>experienceA1</li>\n<li>R&amp;D</li>\n</ul>\n</article>\n<article class=\"position\">\n<div class=\"line\"><em>positionB</em><span>Jan 2018 - Dec 2019</span></div>\n<ul>\n<li>experienceB1</li>\n</ul>\n</article>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_other_experience() {
+        let a = ProjectInfo {
+            project_name: String::from("project_nameA"),
+            description: String::from("descriptionA"),
+            url: String::from("example.com"),
+            include_on_resume: true,
+            ..Default::default()
+        };
+        let b = ProjectInfo {
+            project_name: String::from("project_nameB"),
+            description: String::from("descriptionB"),
+            url: String::from("example.com"),
+            include_on_resume: false,
+            ..Default::default()
+        };
+        let c = ProjectInfo {
+            project_name: String::from("project_nameC"),
+            description: String::from("descriptionC"),
+            include_on_resume: true,
+            ..Default::default()
+        };
+        let x = OtherExperience {
+            projects: vec![a, b, c],
+        };
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"projects\">\n<h2>Projects</h2>\n<ul>\n<li><a href=\"https://example.com\">project_nameA</a>: descriptionA</li>\n<li>project_nameC: descriptionC</li>\n</ul>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_technologies() {
+        let x = Technologies {
+            technologies: vec![TaggedText::from("C++"), TaggedText::from("C#")],
+        };
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"technologies\">\n<h2>Technologies</h2>\n<p>C++, C#</p>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_education() {
+        let x = vec![
+            Education {
+                school: String::from("school"),
+                location: String::from("location"),
+                degree: Some(String::from("B.S.")),
+                major: String::from("major"),
+                minor: Some(String::from("minor")),
+                graduation: ResumeDate::new(2017, 6),
+                gpa: Some(String::from("3.8")),
+                honors: vec![String::from("Cum Laude")],
+                coursework: vec![TaggedText::from("Course A"), TaggedText::from("Course B")],
+                extras: Some(vec![TaggedText::from("extra")]),
+                ..Default::default()
+            },
+            Education {
+                school: String::from("other school"),
+                location: String::from("other location"),
+                major: String::from("other major"),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"education\">\n<h2>Education</h2>\n<article>\n<div class=\"line\"><h3>school</h3><span>location</span></div>\n<div class=\"line\"><em>B.S. in major, minor in minor</em><span>Jun 2017</span></div>\n<p>GPA: 3.8, Cum Laude</p>\n<p>Relevant coursework: Course A, Course B</p>\n<ul>\n<li>extra</li>\n</ul>\n</article>\n<article>\n<div class=\"line\"><h3>other school</h3><span>other location</span></div>\n<div class=\"line\"><em>other major</em><span></span></div>\n</article>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_certifications() {
+        let x = vec![
+            Certification {
+                name: String::from("name"),
+                issuer: String::from("issuer"),
+                issued: ResumeDate::new(2021, 3).unwrap(),
+                expires: ResumeDate::new(2024, 3),
+                credential_id: Some(String::from("ABC-123")),
+                url: Some(String::from("example.com/verify")),
+                ..Default::default()
+            },
+            Certification {
+                name: String::from("other name"),
+                issued: ResumeDate::new(2019, 1).unwrap(),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"certifications\">\n<h2>Certifications</h2>\n<article>\n<div class=\"line\"><h3><a href=\"https://example.com/verify\">name</a></h3><span>Mar 2021 - Mar 2024</span></div>\n<div class=\"line\"><em>issuer</em><span>Credential ID: ABC-123</span></div>\n</article>\n<article>\n<div class=\"line\"><h3>other name</h3><span>Jan 2019</span></div>\n</article>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_custom_section() {
+        let x = CustomSection {
+            title: String::from("Awards"),
+            items: vec![
+                CustomSectionItem {
+                    title: Some(String::from("title")),
+                    location: Some(String::from("location")),
+                    subtitle: Some(String::from("subtitle")),
+                    date: Some(String::from("2021")),
+                    bullets: vec![TaggedText::from("bullet")],
+                    ..Default::default()
+                },
+                CustomSectionItem {
+                    title: Some(String::from("other title")),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "<section class=\"custom\">\n<h2>Awards</h2>\n<article>\n<div class=\"line\"><h3>title</h3><span>location</span></div>\n<div class=\"line\"><em>subtitle</em><span>2021</span></div>\n<ul>\n<li>bullet</li>\n</ul>\n</article>\n<article>\n<div class=\"line\"><h3>other title</h3><span></span></div>\n</article>\n</section>"
+        );
+    }
+
+    #[test]
+    fn test_cover_letter() {
+        let x = CoverLetter {
+            salutation: String::from("Hello,"),
+            closing: String::from("From,"),
+            paragraphs: vec!["foo", "bar"].into_iter().map(String::from).collect(),
+            name: Some(String::from("Foo Bar")),
+            email: Some(String::from("foo@bar.com")),
+        };
+
+        let rendered = HtmlRenderer::new().render(&x, &Config::default()).unwrap();
+
+        assert!(rendered.contains("<title>Foo Bar</title>"));
+        let expected = format!("<main class=\"cover-letter\">\n<header>\n<p>Foo Bar<br>\n<a href=\"mailto:foo@bar.com\">foo@bar.com</a><br>\n{}</p>\n</header>\n<p class=\"salutation\">Hello,</p>\n<p>foo</p>\n<p>bar</p>\n<p class=\"closing\">From,<br>\nFoo Bar</p>\n</main>", date_string());
+        assert!(rendered.contains(&expected));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::config::Config;
+use crate::data::json_resume::JsonResume;
+use crate::data::Resume;
+use crate::error::Error;
+use crate::renderer::Renderer;
+use crate::util::write_string_to_file;
+use std::path::PathBuf;
+
+/// Renders the resume in the JSON Resume (jsonresume.org) schema so it can be used with the
+/// themes and tools built around that schema.
+#[derive(Default)]
+pub struct JsonResumeRenderer;
+
+impl JsonResumeRenderer {
+    pub fn new() -> JsonResumeRenderer {
+        JsonResumeRenderer
+    }
+}
+
+impl Renderer<Resume, PathBuf> for JsonResumeRenderer {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
+        let s: String = self.render(element, config)?;
+
+        write_string_to_file(
+            &s,
+            config.args.output_dir.as_ref(),
+            &format!("{}-json_resume", config.args.output_name()),
+            Some(String::from("json")).as_ref(),
+        )
+    }
+}
+
+impl Renderer<Resume, String> for JsonResumeRenderer {
+    fn render(&self, element: &Resume, _config: &Config) -> Result<String, Error> {
+        serde_json::to_string_pretty(&JsonResume::from(element)).map_err(|e| {
+            Error::Render(format!(
+                "An error occurred while rendering the resume to JSON Resume: {}",
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::Config;
+    use crate::data::{PersonalInfo, Resume};
+    use crate::renderer::json_resume_renderer::JsonResumeRenderer;
+    use crate::renderer::Renderer;
+
+    #[test]
+    fn test_resume() {
+        let x = Resume {
+            name: String::from("Foo Bar"),
+            personal_info: PersonalInfo {
+                email: String::from("foo@bar.com"),
+                github: String::from("github.com/foo"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let rendered: String = JsonResumeRenderer::new()
+            .render(&x, &Config::default())
+            .unwrap();
+
+        assert_eq!(
+            rendered,
+            r#"{
+  "basics": {
+    "name": "Foo Bar",
+    "email": "foo@bar.com",
+    "profiles": [
+      {
+        "network": "GitHub",
+        "username": "foo",
+        "url": "https://github.com/foo"
+      }
+    ]
+  }
+}"#
+        );
+    }
+}
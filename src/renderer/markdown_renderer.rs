@@ -1,14 +1,40 @@
+use crate::config::format_config::{ContactField, Section};
 use crate::config::Config;
 use crate::data::{
-    Education, Objective, OtherExperience, OtherPersonalInfo, PersonalInfo, ProfessionalExperience,
-    ProjectInfo, Resume, Technologies,
+    Certification, CustomSection, CustomSectionItem, Education, Objective, OtherExperience,
+    PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, Technologies,
 };
-use crate::renderer::Renderer;
+use crate::error::Error;
+use crate::renderer::{render_option, Renderer};
 use crate::util::{
     add_https_to_url, split_string_across_lines, time_range_string, write_string_to_file,
 };
 use std::path::PathBuf;
 
+/// The default order of the sections.
+pub(crate) const SECTIONS: &[Section] = &[
+    Section::Objective,
+    Section::PersonalInfo,
+    Section::Experience,
+    Section::Projects,
+    Section::Technologies,
+    Section::Education,
+    Section::Certifications,
+    Section::Custom,
+];
+
+/// The default contact fields and their order.
+pub(crate) const CONTACT: &[ContactField] = &[
+    ContactField::Email,
+    ContactField::Github,
+    ContactField::Phone,
+    ContactField::Location,
+    ContactField::Linkedin,
+    ContactField::Website,
+    ContactField::Pronouns,
+    ContactField::Other,
+];
+
 #[derive(Default)]
 pub struct MarkdownRenderer;
 
@@ -19,42 +45,44 @@ impl MarkdownRenderer {
 }
 
 impl Renderer<Resume, PathBuf> for MarkdownRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
         let s: String = self.render(element, config)?;
 
         write_string_to_file(
             &s,
             config.args.output_dir.as_ref(),
-            &config.args.output_name,
+            config.args.output_name(),
             Some(String::from("md")).as_ref(),
         )
     }
 }
 
 impl Renderer<Resume, String> for MarkdownRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<String, Error> {
         let mut text = format!("# {}", element.name);
-        if let Some(obj) = &element.objective {
-            text = format!("{}\n\n{}", text, self.render(obj, config)?);
-        }
-        text = format!(
-            "{}\n\n{}",
-            text,
-            self.render(&element.personal_info, config)?
+        let sections = config.format_config.sections(
+            &config.format_config.markdown_config.content.sections,
+            SECTIONS,
         );
-        text = format!(
-            "{}\n\n{}",
-            text,
-            self.render(&element.professional_experience, config)?
-        );
-        if let Some(e) = &element.other_experience {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
-        }
-        if let Some(e) = &element.technologies {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
-        }
-        if let Some(e) = &element.education {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
+        for section in sections {
+            let rendered = match section {
+                Section::PersonalInfo => Some(self.render(&element.personal_info, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+                Section::Objective => render_option(self, &element.objective, config)?,
+                Section::Experience if element.professional_experience.is_empty() => None,
+                Section::Experience => Some(self.render(&element.professional_experience, config)?),
+                Section::Projects => render_option(self, &element.other_experience, config)?,
+                Section::Technologies => render_option(self, &element.technologies, config)?,
+                Section::Education if element.education.is_empty() => None,
+                Section::Education => Some(self.render(&element.education, config)?),
+                Section::Certifications if element.certifications.is_empty() => None,
+                Section::Certifications => Some(self.render(&element.certifications, config)?),
+                Section::Custom => Some(self.render(&element.custom_sections, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+            };
+            if let Some(rendered) = rendered {
+                text = format!("{}\n\n{}", text, rendered);
+            }
         }
 
         Ok(text)
@@ -62,60 +90,36 @@ impl Renderer<Resume, String> for MarkdownRenderer {
 }
 
 impl Renderer<PersonalInfo, String> for MarkdownRenderer {
-    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, String> {
-        let mut text = "## Find me here".to_string();
-        text = format!(
-            "{}\n- Email: [{}](mailto:{})",
-            text, element.email, element.email
+    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, Error> {
+        let fields = config.format_config.contact(
+            &config.format_config.markdown_config.content.contact,
+            CONTACT,
         );
-        text = format!(
-            "{}\n- GitHub: [{}]({})",
-            text,
-            element.github,
-            add_https_to_url(&element.github)
-        );
-
-        let other_info = element
-            .other
-            .as_ref()
-            .map(|other| -> Result<String, String> {
-                let info = other
-                    .iter()
-                    .map(|e| self.render(e, config))
-                    .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
-                    .unwrap_or_else(|| {
-                        Err(String::from(
-                            "An error occurred while rendering personal info to markdown",
-                        ))
-                    });
-                info
-            });
-
-        if let Some(other_info) = other_info {
-            text = format!("{}\n{}", text, other_info?);
-        }
-
-        Ok(text)
+        Ok(contact_list(element, &fields))
     }
 }
 
-impl Renderer<OtherPersonalInfo, String> for MarkdownRenderer {
-    fn render(&self, element: &OtherPersonalInfo, _config: &Config) -> Result<String, String> {
-        if let Some(url) = &element.url {
-            Ok(format!(
-                "- {}: [{}]({})",
-                element.item,
-                url,
-                add_https_to_url(url)
-            ))
-        } else {
-            Ok(format!("- {}", element.item))
-        }
+/// The "Find me here" list with the given contact fields, or an empty string if none of them are
+/// set.
+pub(crate) fn contact_list(element: &PersonalInfo, fields: &[ContactField]) -> String {
+    let items = element.contact_items(fields);
+    if items.is_empty() {
+        return String::new();
+    }
+    let mut text = "## Find me here".to_string();
+    for item in items {
+        let item = match &item.link {
+            Some(link) => format!("- {}: [{}]({})", item.label, item.text, link),
+            None if item.field == ContactField::Other => format!("- {}", item.text),
+            None => format!("- {}: {}", item.label, item.text),
+        };
+        text = format!("{}\n{}", text, item);
     }
+    text
 }
 
 impl Renderer<Objective, String> for MarkdownRenderer {
-    fn render(&self, element: &Objective, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Objective, config: &Config) -> Result<String, Error> {
         Ok(split_string_across_lines(
             &element.objective,
             config.format_config.markdown_config.width,
@@ -130,7 +134,7 @@ impl Renderer<Vec<ProfessionalExperience>, String> for MarkdownRenderer {
         &self,
         element: &Vec<ProfessionalExperience>,
         config: &Config,
-    ) -> Result<String, String> {
+    ) -> Result<String, Error> {
         let mut text = "## Experience".to_string();
 
         let exp = element
@@ -138,9 +142,9 @@ impl Renderer<Vec<ProfessionalExperience>, String> for MarkdownRenderer {
             .map(|e| self.render(e, config))
             .reduce(|a, b| Ok(format!("{}\n\n{}", a?, b?)))
             .unwrap_or_else(|| {
-                Err(String::from(
+                Err(Error::Render(String::from(
                     "An error occurred while rendering professional experience to markdown.",
-                ))
+                )))
             })?;
 
         text = format!("{}\n{}", text, exp);
@@ -150,7 +154,7 @@ impl Renderer<Vec<ProfessionalExperience>, String> for MarkdownRenderer {
 }
 
 impl Renderer<ProfessionalExperience, String> for MarkdownRenderer {
-    fn render(&self, element: &ProfessionalExperience, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &ProfessionalExperience, config: &Config) -> Result<String, Error> {
         let mut text =
             if let (Some(org), Some(location)) = (&element.organization, &element.location) {
                 format!("### {} - {}", org, location)
@@ -161,7 +165,12 @@ impl Renderer<ProfessionalExperience, String> for MarkdownRenderer {
             "{}\n```\n{}\n{}\n```",
             text,
             element.position,
-            time_range_string(&element.start, &element.end)
+            time_range_string(
+                &element.start,
+                &element.end,
+                &config.format_config.markdown_config.dates.date_format,
+                config.format_config.markdown_config.dates.show_duration,
+            )
         );
 
         let exp = element
@@ -177,9 +186,9 @@ impl Renderer<ProfessionalExperience, String> for MarkdownRenderer {
             })
             .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
             .unwrap_or_else(|| {
-                Err(String::from(
+                Err(Error::Render(String::from(
                     "An error occurred while rendering professional experience to markdown.",
-                ))
+                )))
             })?;
 
         text = format!("{}\n{}", text, exp);
@@ -188,8 +197,61 @@ impl Renderer<ProfessionalExperience, String> for MarkdownRenderer {
     }
 }
 
+impl Renderer<Vec<CustomSection>, String> for MarkdownRenderer {
+    fn render(&self, element: &Vec<CustomSection>, config: &Config) -> Result<String, Error> {
+        Ok(element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n"))
+    }
+}
+
+impl Renderer<CustomSection, String> for MarkdownRenderer {
+    fn render(&self, element: &CustomSection, config: &Config) -> Result<String, Error> {
+        let items = element
+            .items
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n");
+
+        Ok(format!("## {}\n{}", element.title, items))
+    }
+}
+
+impl Renderer<CustomSectionItem, String> for MarkdownRenderer {
+    fn render(&self, element: &CustomSectionItem, config: &Config) -> Result<String, Error> {
+        let mut lines = vec![];
+        let heading = [&element.title, &element.location]
+            .iter()
+            .filter_map(|x| x.as_deref())
+            .collect::<Vec<&str>>();
+        if !heading.is_empty() {
+            lines.push(format!("### {}", heading.join(" - ")));
+        }
+        let details = [&element.subtitle, &element.date]
+            .iter()
+            .filter_map(|x| x.as_deref())
+            .collect::<Vec<&str>>();
+        if !details.is_empty() {
+            lines.push(format!("```\n{}\n```", details.join("\n")));
+        }
+        lines.extend(element.bullets.iter().map(|e| {
+            split_string_across_lines(
+                e,
+                config.format_config.markdown_config.width,
+                Some(String::from("- ")),
+                Some(String::from("  ")),
+            )
+        }));
+
+        Ok(lines.join("\n"))
+    }
+}
+
 impl Renderer<OtherExperience, String> for MarkdownRenderer {
-    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, Error> {
         let mut text = "## Projects".to_string();
 
         // todo: clean up?
@@ -199,7 +261,9 @@ impl Renderer<OtherExperience, String> for MarkdownRenderer {
             .map(|e| self.render(e, config))
             .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
             .unwrap_or_else(|| {
-                Err("An error occurred while rendering other experience to markdown.".to_string())
+                Err(Error::Render(
+                    "An error occurred while rendering other experience to markdown.".to_string(),
+                ))
             })?;
 
         text = format!("{}\n{}", text, projects);
@@ -209,13 +273,17 @@ impl Renderer<OtherExperience, String> for MarkdownRenderer {
 }
 
 impl Renderer<ProjectInfo, String> for MarkdownRenderer {
-    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, String> {
-        let project_info = format!(
-            "[{}]({}) - {}",
-            element.project_name,
-            add_https_to_url(&element.url),
-            element.description
-        );
+    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, Error> {
+        let project_info = if element.url.is_empty() {
+            format!("{} - {}", element.project_name, element.description)
+        } else {
+            format!(
+                "[{}]({}) - {}",
+                element.project_name,
+                add_https_to_url(&element.url),
+                element.description
+            )
+        };
         Ok(split_string_across_lines(
             &project_info,
             config.format_config.markdown_config.width,
@@ -226,32 +294,91 @@ impl Renderer<ProjectInfo, String> for MarkdownRenderer {
 }
 
 impl Renderer<Technologies, String> for MarkdownRenderer {
-    fn render(&self, element: &Technologies, _config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Technologies, _config: &Config) -> Result<String, Error> {
         Ok(format!(
             "## Technologies\n{}",
-            element.technologies.join(", ")
+            element.get_technologies().join(", ")
         ))
     }
 }
 
+impl Renderer<Vec<Education>, String> for MarkdownRenderer {
+    fn render(&self, element: &Vec<Education>, config: &Config) -> Result<String, Error> {
+        let education = element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n");
+
+        Ok(format!("## Education\n{}", education))
+    }
+}
+
 impl Renderer<Education, String> for MarkdownRenderer {
-    fn render(&self, element: &Education, _config: &Config) -> Result<String, String> {
-        let mut text = format!("## University\n### {}", element.school);
-        if element.graduation.is_some() {
-            text = format!(
-                "{}\n```\n{}\n{}\n{}\n```",
-                text,
-                element.major,
-                element.graduation.as_ref().unwrap(),
-                element.location
-            );
-        } else {
-            text = format!(
-                "{}\n```\n{}\n{}\n```",
-                text, element.major, element.location
-            );
+    fn render(&self, element: &Education, config: &Config) -> Result<String, Error> {
+        let mut details = vec![element.degree_string()];
+        if let Some(graduation) = &element.graduation {
+            details
+                .push(graduation.format(&config.format_config.markdown_config.dates.date_format));
         }
-        Ok(text)
+        details.push(element.location.clone());
+        details.extend(element.details_string(!config.format_config.hide_gpa));
+        let mut lines = vec![
+            format!("### {}", element.school),
+            format!("```\n{}\n```", details.join("\n")),
+        ];
+        lines.extend(
+            element
+                .coursework_string()
+                .iter()
+                .map(|x| x.as_str())
+                .chain(element.get_extras().iter().map(|x| x.text.as_str()))
+                .map(|x| {
+                    split_string_across_lines(
+                        x,
+                        config.format_config.markdown_config.width,
+                        Some(String::from("- ")),
+                        Some(String::from("  ")),
+                    )
+                }),
+        );
+
+        Ok(lines.join("\n"))
+    }
+}
+
+impl Renderer<Vec<Certification>, String> for MarkdownRenderer {
+    fn render(&self, element: &Vec<Certification>, config: &Config) -> Result<String, Error> {
+        let certifications = element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n");
+
+        Ok(format!("## Certifications\n{}", certifications))
+    }
+}
+
+impl Renderer<Certification, String> for MarkdownRenderer {
+    fn render(&self, element: &Certification, config: &Config) -> Result<String, Error> {
+        let mut parts = vec![match &element.url {
+            Some(url) => format!("[{}]({})", element.name, add_https_to_url(url)),
+            None => element.name.clone(),
+        }];
+        if !element.issuer.is_empty() {
+            parts.push(element.issuer.clone());
+        }
+        parts.push(element.dates(&config.format_config.markdown_config.dates.date_format));
+        if let Some(credential_id) = &element.credential_id {
+            parts.push(format!("Credential ID: {}", credential_id));
+        }
+
+        Ok(split_string_across_lines(
+            &parts.join(", "),
+            config.format_config.markdown_config.width,
+            Some(String::from("- ")),
+            Some(String::from("  ")),
+        ))
     }
 }
 
@@ -259,9 +386,11 @@ impl Renderer<Education, String> for MarkdownRenderer {
 mod test {
     use crate::config::format_config::{FormatConfig, TextConfig};
     use crate::config::Config;
+    use crate::data::date::ResumeDate;
     use crate::data::{
-        Education, Objective, OtherExperience, OtherPersonalInfo, PersonalInfo,
-        ProfessionalExperience, ProjectInfo, Technologies,
+        Certification, CustomSection, CustomSectionItem, Education, Objective, OtherExperience,
+        OtherPersonalInfo, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, TaggedText,
+        Technologies,
     };
     use crate::renderer::markdown_renderer::MarkdownRenderer;
     use crate::renderer::Renderer;
@@ -279,16 +408,35 @@ mod test {
         let x = PersonalInfo {
             email: String::from("foo@bar.com"),
             github: String::from("github.com/foo"),
+            phone: Some(String::from("555-555-5555")),
+            location: Some(String::from("Seattle, WA")),
+            linkedin: Some(String::from("linkedin.com/in/foo")),
+            pronouns: Some(String::from("they/them")),
             other: Some(vec![a, b]),
+            ..Default::default()
         };
         let rendered = MarkdownRenderer::new().render(&x, &get_config()).unwrap();
 
         assert_eq!(
             rendered,
-            "## Find me here\n- Email: [foo@bar.com](mailto:foo@bar.com)\n- GitHub: [github.com/foo](https://github.com/foo)\n- Foo: [example.com/foo](https://example.com/foo)\n- Bar: [example.com/bar](https://example.com/bar)"
+            "## Find me here\n- Email: [foo@bar.com](mailto:foo@bar.com)\n- GitHub: [github.com/foo](https://github.com/foo)\n- Phone: [555-555-5555](tel:5555555555)\n- Location: Seattle, WA\n- LinkedIn: [linkedin.com/in/foo](https://linkedin.com/in/foo)\n- Pronouns: they/them\n- Foo: [example.com/foo](https://example.com/foo)\n- Bar: [example.com/bar](https://example.com/bar)"
         );
     }
 
+    #[test]
+    fn test_no_contact_items() {
+        let x = Resume {
+            name: String::from("Foo Bar"),
+            objective: Some(Objective {
+                objective: String::from("objective"),
+            }),
+            ..Default::default()
+        };
+        let rendered: String = MarkdownRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert!(!rendered.contains("Find me here"));
+    }
+
     #[test]
     fn test_objective() {
         let x = Objective {
@@ -299,31 +447,45 @@ mod test {
         assert_eq!(rendered, "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut");
     }
 
+    #[test]
+    fn test_markup() {
+        let x = Objective {
+            objective: String::from("Cut **build times** by *half* with `sccache` and [more](example.com/a_b) Cut **build times** by *half* with `sccache` and [more](example.com/a_b)"),
+        };
+        let mut config = get_config();
+        config.format_config.markdown_config.width = 60;
+        let rendered = MarkdownRenderer::new().render(&x, &config).unwrap();
+
+        assert_eq!(rendered, "Cut **build times** by *half* with `sccache` and [more](example.com/a_b) Cut **build\ntimes** by *half* with `sccache` and [more](example.com/a_b)");
+    }
+
     #[test]
     fn test_professional_experience() {
         let a = ProfessionalExperience {
             organization: Some(String::from("organizationA")),
             position: String::from("positionA"),
             location: Some(String::from("locationA")),
-            start: String::from("startA"),
-            end: String::from("endA"),
+            start: ResumeDate::new(2020, 1).unwrap(),
+            end: ResumeDate::Present,
             experience: vec![
-                String::from("experienceA1"),
-                String::from("experienceA2"),
-                String::from("experienceA3"),
+                TaggedText::from("experienceA1"),
+                TaggedText::from("experienceA2"),
+                TaggedText::from("experienceA3"),
             ],
+            tags: vec![],
         };
         let b = ProfessionalExperience {
             organization: Some(String::from("organizationB")),
             position: String::from("positionB"),
             location: Some(String::from("locationB")),
-            start: String::from("startB"),
-            end: String::from("endB"),
+            start: ResumeDate::new(2018, 1).unwrap(),
+            end: ResumeDate::new(2019, 12).unwrap(),
             experience: vec![
-                String::from("experienceB1"),
-                String::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
-                String::from("experienceB3"),
+                TaggedText::from("experienceB1"),
+                TaggedText::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
+                TaggedText::from("experienceB3"),
             ],
+            tags: vec![],
         };
         let x = vec![a, b];
 
@@ -331,7 +493,7 @@ mod test {
 
         assert_eq!(
             rendered,
-            "## Experience\n### organizationA - locationA\n```\npositionA\nstartA - endA\n```\n- experienceA1\n- experienceA2\n- experienceA3\n\n### organizationB - locationB\n```\npositionB\nstartB - endB\n```\n- experienceB1\n- Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n- experienceB3"
+            "## Experience\n### organizationA - locationA\n```\npositionA\nJan 2020 - Present\n```\n- experienceA1\n- experienceA2\n- experienceA3\n\n### organizationB - locationB\n```\npositionB\nJan 2018 - Dec 2019\n```\n- experienceB1\n- Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n- experienceB3"
         );
     }
 
@@ -354,7 +516,7 @@ mod test {
         let c = ProjectInfo {
             project_name: String::from("project_nameC"),
             description: String::from("descriptionC"),
-            url: String::from("example.com"),
+            url: String::new(),
             include_on_resume: true,
             ..Default::default()
         };
@@ -366,26 +528,81 @@ mod test {
 
         assert_eq!(
             rendered,
-            "## Projects\n- [project_nameA](https://example.com) - descriptionA\n- [project_nameB](https://example.com) - Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor\n  incididunt ut\n- [project_nameC](https://example.com) - descriptionC"
+            "## Projects\n- [project_nameA](https://example.com) - descriptionA\n- [project_nameB](https://example.com) - Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n- project_nameC - descriptionC"
+        );
+    }
+
+    #[test]
+    fn test_custom_section() {
+        let x = CustomSection {
+            title: String::from("Awards"),
+            items: vec![
+                CustomSectionItem {
+                    title: Some(String::from("title")),
+                    location: Some(String::from("location")),
+                    subtitle: Some(String::from("subtitle")),
+                    date: Some(String::from("2021")),
+                    bullets: vec![TaggedText::from("bullet")],
+                    ..Default::default()
+                },
+                CustomSectionItem {
+                    title: Some(String::from("other title")),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let rendered = MarkdownRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "## Awards\n### title - location\n```\nsubtitle\n2021\n```\n- bullet\n\n### other title"
+        );
+    }
+
+    #[test]
+    fn test_certifications() {
+        let x = vec![
+            Certification {
+                name: String::from("name"),
+                issuer: String::from("issuer"),
+                issued: ResumeDate::new(2021, 3).unwrap(),
+                expires: ResumeDate::new(2024, 3),
+                credential_id: Some(String::from("ABC-123")),
+                url: Some(String::from("example.com/verify")),
+                ..Default::default()
+            },
+            Certification {
+                name: String::from("other name"),
+                issued: ResumeDate::new(2019, 1).unwrap(),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = MarkdownRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "## Certifications\n- [name](https://example.com/verify), issuer, Mar 2021 - Mar 2024, Credential ID: ABC-123\n- other name, Jan 2019"
         );
     }
 
     #[test]
     fn test_technologies() {
         let tech = vec![
-            String::from("Lorem"),
-            String::from("ipsum"),
-            String::from("dolor"),
-            String::from("sit"),
-            String::from("amet"),
-            String::from("consectetur"),
-            String::from("adipiscing"),
-            String::from("elit"),
-            String::from("sed"),
-            String::from("do"),
-            String::from("eiusmod"),
-            String::from("tempor"),
-            String::from("incididunt"),
+            TaggedText::from("Lorem"),
+            TaggedText::from("ipsum"),
+            TaggedText::from("dolor"),
+            TaggedText::from("sit"),
+            TaggedText::from("amet"),
+            TaggedText::from("consectetur"),
+            TaggedText::from("adipiscing"),
+            TaggedText::from("elit"),
+            TaggedText::from("sed"),
+            TaggedText::from("do"),
+            TaggedText::from("eiusmod"),
+            TaggedText::from("tempor"),
+            TaggedText::from("incididunt"),
         ];
 
         let x = Technologies { technologies: tech };
@@ -400,26 +617,43 @@ mod test {
 
     #[test]
     fn test_education() {
-        let x = Education {
-            school: String::from("school"),
-            location: String::from("location"),
-            major: String::from("major"),
-            graduation: Some(String::from("graduation")),
-            ..Default::default()
-        };
+        let x = vec![
+            Education {
+                school: String::from("school"),
+                location: String::from("location"),
+                degree: Some(String::from("B.S.")),
+                major: String::from("major"),
+                minor: Some(String::from("minor")),
+                graduation: ResumeDate::new(2017, 6),
+                gpa: Some(String::from("3.8")),
+                honors: vec![String::from("Cum Laude")],
+                coursework: vec![TaggedText::from("Course A"), TaggedText::from("Course B")],
+                extras: Some(vec![TaggedText::from("extra")]),
+                ..Default::default()
+            },
+            Education {
+                school: String::from("other school"),
+                location: String::from("other location"),
+                major: String::from("other major"),
+                ..Default::default()
+            },
+        ];
 
         let rendered = MarkdownRenderer::new().render(&x, &get_config()).unwrap();
 
         assert_eq!(
             rendered,
-            "## University\n### school\n```\nmajor\ngraduation\nlocation\n```"
+            "## Education\n### school\n```\nB.S. in major, minor in minor\nJun 2017\nlocation\nGPA: 3.8, Cum Laude\n```\n- Relevant coursework: Course A, Course B\n- extra\n\n### other school\n```\nother major\nother location\n```"
         );
     }
 
     fn get_config() -> Config {
         Config {
             format_config: FormatConfig {
-                text_config: TextConfig { width: 50 },
+                text_config: TextConfig {
+                    width: 50,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             ..Default::default()
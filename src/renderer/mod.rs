@@ -1,29 +1,316 @@
+use crate::config::arguments::{Arguments, OutputFormat};
 use crate::config::Config;
 use crate::data::Resume;
+use crate::error::Error;
 use crate::renderer::github_renderer::GitHubRenderer;
+use crate::renderer::html_renderer::HtmlRenderer;
+use crate::renderer::json_resume_renderer::JsonResumeRenderer;
 use crate::renderer::markdown_renderer::MarkdownRenderer;
 use crate::renderer::pdf_renderer::PdfRenderer;
+use crate::renderer::template_renderer::TemplateRenderer;
 use crate::renderer::text_renderer::TextRenderer;
+use std::fmt;
+use std::iter;
 use std::path::PathBuf;
 
 pub mod github_renderer;
+pub mod html_renderer;
+pub mod json_resume_renderer;
 pub mod markdown_renderer;
+#[cfg(feature = "native-pdf")]
+pub mod native_pdf;
 pub mod pdf_renderer;
+pub mod template_renderer;
 pub mod text_renderer;
 
-trait Renderer<I, O> {
-    fn render(&self, element: &I, config: &Config) -> Result<O, String>;
-}
-
-pub fn render_resume(resume: &Resume, config: &Config) -> Result<(), String> {
-    let renderers: Vec<Box<dyn Renderer<Resume, PathBuf>>> = vec![
-        Box::<TextRenderer>::default(),
-        Box::<PdfRenderer>::default(),
-        Box::<MarkdownRenderer>::default(),
-        Box::<GitHubRenderer>::default(),
-    ];
-    renderers.iter().try_for_each(|x| -> Result<(), String> {
-        x.render(resume, config)?;
-        Ok(())
-    })
+/// Renders an element of the resume (`I`) to some output (`O`).
+///
+/// Implement `Renderer<Resume, PathBuf>` to add a new output format: the implementation renders
+/// the resume, writes it to a file and returns the path of that file. Register it with a
+/// [RendererRegistry] to run it along with the built-in formats.
+pub trait Renderer<I, O> {
+    fn render(&self, element: &I, config: &Config) -> Result<O, Error>;
+
+    /// Render the element, along with notes about the output that aren't errors, e.g. that the
+    /// pdf was shrunk to fit on `max_pages`. The [RendererRegistry] collects the notes in the
+    /// [RenderReport]. Renderers without notes don't need to implement this.
+    fn render_with_notes(&self, element: &I, config: &Config) -> Result<(O, Vec<String>), Error> {
+        self.render(element, config).map(|x| (x, Vec::new()))
+    }
+}
+
+/// Render an element of the resume that may be omitted.
+pub(crate) fn render_option<R, I, O>(
+    renderer: &R,
+    element: &Option<I>,
+    config: &Config,
+) -> Result<Option<O>, Error>
+where
+    R: Renderer<I, O>,
+{
+    element
+        .as_ref()
+        .map(|x| renderer.render(x, config))
+        .transpose()
+}
+
+/// The outcome of one of the renderers in a [RendererRegistry].
+#[derive(Debug)]
+pub struct RenderResult {
+    pub name: String,
+    pub result: Result<PathBuf, Error>,
+    /// Notes about the output that aren't errors, see [Renderer::render_with_notes].
+    pub notes: Vec<String>,
+}
+
+/// The outcome of rendering each of the selected output formats.
+#[derive(Debug, Default)]
+pub struct RenderReport {
+    pub results: Vec<RenderResult>,
+    /// Problems that didn't stop the outputs from being rendered, see [Resume::warnings].
+    pub warnings: Vec<String>,
+}
+
+impl RenderReport {
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|x| x.result.is_ok())
+    }
+
+    /// Convert the report to an error if any of the outputs failed to render.
+    pub fn into_result(self) -> Result<(), Error> {
+        let failed = self.results.iter().filter(|x| x.result.is_err()).count();
+        if failed == 0 {
+            Ok(())
+        } else {
+            Err(Error::Render(format!(
+                "{} of {} outputs failed to render",
+                failed,
+                self.results.len()
+            )))
+        }
+    }
+}
+
+impl fmt::Display for RenderReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .warnings
+            .iter()
+            .map(|x| format!("[warning] {}", x))
+            .chain(self.results.iter().flat_map(|x| {
+                let result = match &x.result {
+                    Ok(path) => format!("[ok] {}: {}", x.name, path.display()),
+                    Err(e) => format!("[failed] {}: {}", x.name, e),
+                };
+                iter::once(result).chain(x.notes.iter().map(|note| format!("  {}", note)))
+            }))
+            .collect::<Vec<String>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// The formats to render. The command line argument takes precedence over the format config.
+fn selected_formats(config: &Config) -> Vec<OutputFormat> {
+    config
+        .args
+        .format
+        .clone()
+        .or_else(|| config.format_config.formats.clone())
+        .unwrap_or_else(OutputFormat::all)
+}
+
+fn renderer_for_format(format: OutputFormat) -> Box<dyn Renderer<Resume, PathBuf>> {
+    match format {
+        OutputFormat::Text => Box::<TextRenderer>::default(),
+        OutputFormat::Pdf => Box::<PdfRenderer>::default(),
+        OutputFormat::Markdown => Box::<MarkdownRenderer>::default(),
+        OutputFormat::Github => Box::<GitHubRenderer>::default(),
+        OutputFormat::Html => Box::<HtmlRenderer>::default(),
+        OutputFormat::JsonResume => Box::<JsonResumeRenderer>::default(),
+    }
+}
+
+/// A list of named renderers that are run together. Use [RendererRegistry::from_config] to get the
+/// built-in renderers, then add custom renderers with [RendererRegistry::with_renderer].
+#[derive(Default)]
+pub struct RendererRegistry {
+    renderers: Vec<(String, Box<dyn Renderer<Resume, PathBuf>>)>,
+}
+
+impl RendererRegistry {
+    /// A registry without any renderers.
+    pub fn new() -> RendererRegistry {
+        RendererRegistry::default()
+    }
+
+    /// A registry with the built-in renderers for the formats selected in the config, or only the
+    /// template renderer if a template is selected.
+    pub fn from_config(config: &Config) -> RendererRegistry {
+        if let Some(template) = &config.args.template {
+            return RendererRegistry::new()
+                .with_renderer("template", TemplateRenderer::new(template));
+        }
+        let renderers = selected_formats(config)
+            .into_iter()
+            .map(|format| (format.to_string(), renderer_for_format(format)))
+            .collect();
+        RendererRegistry { renderers }
+    }
+
+    /// Add a renderer. The name is used to identify the renderer's output in the [RenderReport].
+    pub fn with_renderer<R>(mut self, name: &str, renderer: R) -> RendererRegistry
+    where
+        R: Renderer<Resume, PathBuf> + 'static,
+    {
+        self.renderers.push((name.to_owned(), Box::new(renderer)));
+        self
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.renderers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Run each of the renderers. A failure in one renderer doesn't prevent the others from
+    /// running; the outcome of each is collected in the returned report.
+    pub fn render(&self, resume: &Resume, config: &Config) -> RenderReport {
+        let results = self
+            .renderers
+            .iter()
+            .map(|(name, renderer)| {
+                let (result, notes) = match renderer.render_with_notes(resume, config) {
+                    Ok((path, notes)) => (Ok(path), notes),
+                    Err(e) => (Err(e), Vec::new()),
+                };
+                RenderResult {
+                    name: name.clone(),
+                    result,
+                    notes,
+                }
+            })
+            .collect();
+        RenderReport {
+            results,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Render each of the built-in formats selected in the config.
+pub fn render_resume(resume: &Resume, config: &Config) -> RenderReport {
+    RenderReport {
+        warnings: resume.warnings(),
+        ..RendererRegistry::from_config(config).render(resume, config)
+    }
+}
+
+/// Read the config and resume named in the arguments and render each of the selected formats.
+pub fn render(args: &Arguments) -> Result<RenderReport, Error> {
+    let config = Config::from_args(args.clone())?;
+    let resume = Resume::read_from_config_file(&config.args.resume_input, &config)?;
+    Ok(render_resume(&resume, &config))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::{Arguments, OutputFormat};
+    use crate::config::format_config::FormatConfig;
+    use crate::config::Config;
+    use crate::data::Resume;
+    use crate::error::Error;
+    use crate::renderer::{
+        render, selected_formats, RenderReport, RenderResult, Renderer, RendererRegistry,
+    };
+    use std::path::PathBuf;
+
+    struct TestRenderer;
+
+    impl Renderer<Resume, PathBuf> for TestRenderer {
+        fn render(&self, element: &Resume, _config: &Config) -> Result<PathBuf, Error> {
+            Ok(PathBuf::from(&element.name))
+        }
+    }
+
+    #[test]
+    fn test_selected_formats() {
+        let mut config = Config::default();
+        assert_eq!(selected_formats(&config), OutputFormat::all());
+
+        config.format_config = FormatConfig {
+            formats: Some(vec![OutputFormat::Pdf]),
+            ..Default::default()
+        };
+        assert_eq!(selected_formats(&config), vec![OutputFormat::Pdf]);
+
+        config.args = Arguments {
+            format: Some(vec![OutputFormat::Text, OutputFormat::Github]),
+            ..Default::default()
+        };
+        assert_eq!(
+            selected_formats(&config),
+            vec![OutputFormat::Text, OutputFormat::Github]
+        );
+    }
+
+    #[test]
+    fn test_render_report() {
+        let report = RenderReport {
+            results: vec![
+                RenderResult {
+                    name: String::from("text"),
+                    result: Ok(PathBuf::from("./foo.txt")),
+                    notes: vec![String::from("qux")],
+                },
+                RenderResult {
+                    name: String::from("pdf"),
+                    result: Err(Error::Render(String::from("bar"))),
+                    notes: vec![],
+                },
+            ],
+            warnings: vec![String::from("baz")],
+        };
+
+        assert!(!report.is_success());
+        assert_eq!(
+            report.to_string(),
+            "[warning] baz\n[ok] text: ./foo.txt\n  qux\n[failed] pdf: bar"
+        );
+        assert_eq!(
+            report.into_result().unwrap_err().to_string(),
+            "1 of 2 outputs failed to render"
+        );
+    }
+
+    #[test]
+    fn test_registry() {
+        let config = Config {
+            format_config: FormatConfig {
+                formats: Some(vec![OutputFormat::Text, OutputFormat::JsonResume]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let registry = RendererRegistry::from_config(&config).with_renderer("test", TestRenderer);
+        assert_eq!(registry.names(), vec!["text", "json-resume", "test"]);
+
+        let resume = Resume {
+            name: String::from("foo"),
+            ..Default::default()
+        };
+        let report = RendererRegistry::new()
+            .with_renderer("test", TestRenderer)
+            .render(&resume, &config);
+        assert_eq!(report.to_string(), "[ok] test: foo");
+    }
+
+    #[test]
+    fn test_render_error() {
+        let args = Arguments {
+            resume_input: String::from("tst/test_format_config.toml"),
+            ..Default::default()
+        };
+        assert!(matches!(render(&args), Err(Error::UnknownKeys { .. })));
+    }
 }
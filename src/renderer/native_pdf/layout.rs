@@ -0,0 +1,729 @@
+//! Lays out blocks of text on pages. Text is measured with the embedded fonts, wrapped to the
+//! width of its column, and continued on the next page when a page is full.
+use crate::error::Error;
+use ttf_parser::{Face, GlyphId};
+
+/// The mono font, shared by the serif and sans serif fonts so it's only embedded in the binary once.
+static MONO: &[u8] = include_bytes!("../../../fonts/DejaVuSansMono.ttf");
+/// The embedded DejaVu fonts, in the order of [FontStyle]. See `fonts/LICENSE`.
+static SERIF: [&[u8]; 5] = [
+    include_bytes!("../../../fonts/DejaVuSerif.ttf"),
+    include_bytes!("../../../fonts/DejaVuSerif-Bold.ttf"),
+    include_bytes!("../../../fonts/DejaVuSerif-Italic.ttf"),
+    include_bytes!("../../../fonts/DejaVuSerif-BoldItalic.ttf"),
+    MONO,
+];
+static SANS: [&[u8]; 5] = [
+    include_bytes!("../../../fonts/DejaVuSans.ttf"),
+    include_bytes!("../../../fonts/DejaVuSans-Bold.ttf"),
+    include_bytes!("../../../fonts/DejaVuSans-Oblique.ttf"),
+    include_bytes!("../../../fonts/DejaVuSans-BoldOblique.ttf"),
+    MONO,
+];
+
+/// The space between lines, as a multiple of the font size.
+const LINE_HEIGHT: f32 = 1.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FontStyle {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+impl FontStyle {
+    pub const ALL: [FontStyle; 5] = [
+        FontStyle::Regular,
+        FontStyle::Bold,
+        FontStyle::Italic,
+        FontStyle::BoldItalic,
+        FontStyle::Mono,
+    ];
+
+    pub fn bold(self) -> FontStyle {
+        match self {
+            FontStyle::Regular => FontStyle::Bold,
+            FontStyle::Italic => FontStyle::BoldItalic,
+            x => x,
+        }
+    }
+
+    pub fn italic(self) -> FontStyle {
+        match self {
+            FontStyle::Regular => FontStyle::Italic,
+            FontStyle::Bold => FontStyle::BoldItalic,
+            x => x,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The fonts of the document, parsed once so text can be measured.
+pub struct Fonts {
+    data: [&'static [u8]; 5],
+    faces: Vec<Face<'static>>,
+}
+
+impl Fonts {
+    /// The serif fonts, or the sans serif fonts if `sans` is set.
+    pub fn new(sans: bool) -> Result<Fonts, Error> {
+        let data = if sans { SANS } else { SERIF };
+        let faces = data
+            .iter()
+            .map(|x| {
+                Face::parse(x, 0).map_err(|e| {
+                    Error::Render(format!(
+                        "An error occurred while loading an embedded font: {}",
+                        e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<Face<'static>>, Error>>()?;
+        Ok(Fonts { data, faces })
+    }
+
+    pub fn face(&self, style: FontStyle) -> &Face<'static> {
+        &self.faces[style.index()]
+    }
+
+    pub fn data(&self, style: FontStyle) -> &'static [u8] {
+        self.data[style.index()]
+    }
+
+    /// The glyph for the character. Characters the font doesn't have are shown as '?', like in
+    /// the LaTeX output.
+    pub fn glyph(&self, style: FontStyle, c: char) -> GlyphId {
+        let face = self.face(style);
+        face.glyph_index(c)
+            .or_else(|| face.glyph_index('?'))
+            .unwrap_or(GlyphId(0))
+    }
+
+    /// The advance of the glyph, in thousandths of the font size.
+    pub fn advance(&self, style: FontStyle, glyph: GlyphId) -> f32 {
+        let face = self.face(style);
+        let advance = face.glyph_hor_advance(glyph).unwrap_or_default();
+        f32::from(advance) * 1000.0 / f32::from(face.units_per_em())
+    }
+
+    /// The width of the text in points.
+    pub fn width(&self, style: FontStyle, s: &str, size: f32) -> f32 {
+        s.chars()
+            .map(|c| self.advance(style, self.glyph(style, c)))
+            .sum::<f32>()
+            * size
+            / 1000.0
+    }
+
+    /// How far the font reaches above and below the baseline, as a fraction of the font size.
+    pub fn ascent_descent(&self, style: FontStyle) -> (f32, f32) {
+        let face = self.face(style);
+        let units = f32::from(face.units_per_em());
+        (
+            f32::from(face.ascender()) / units,
+            -f32::from(face.descender()) / units,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub f32, pub f32, pub f32);
+
+impl Color {
+    /// Parse a hex color like "1F4E79".
+    pub fn from_hex(s: &str) -> Option<Color> {
+        let channel = |i: usize| {
+            s.get(i..i + 2)
+                .and_then(|x| u8::from_str_radix(x, 16).ok())
+                .map(|x| f32::from(x) / 255.0)
+        };
+        Some(Color(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// A piece of text in one style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: FontStyle,
+    pub color: Option<Color>,
+    pub link: Option<String>,
+}
+
+impl Span {
+    pub fn new(text: &str, style: FontStyle) -> Span {
+        Span {
+            text: text.to_string(),
+            style,
+            color: None,
+            link: None,
+        }
+    }
+
+    fn with_text(&self, text: String) -> Span {
+        Span {
+            text,
+            ..self.clone()
+        }
+    }
+
+    fn same_format(&self, other: &Span) -> bool {
+        self.style == other.style && self.color == other.color && self.link == other.link
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+}
+
+/// Where a heading's rule goes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeadingRule {
+    None,
+    /// From the end of the heading to the end of the line.
+    After,
+    /// Under the heading, across the column.
+    Below,
+}
+
+/// The content of a column. Each block starts on a new line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Text wrapped to the width of the column.
+    Text {
+        spans: Vec<Span>,
+        size: f32,
+        align: Align,
+    },
+    /// Text flush left and text flush right on the same line, e.g. a position and its dates.
+    Split {
+        left: Vec<Span>,
+        right: Vec<Span>,
+        size: f32,
+    },
+    /// Items spread across the line: the first flush left, the last flush right. Items that
+    /// don't fit go on the next line.
+    Spread { items: Vec<Vec<Span>>, size: f32 },
+    /// A list item.
+    Bullet { spans: Vec<Span>, size: f32 },
+    /// A section header. It's kept on the same page as the line after it.
+    Heading {
+        spans: Vec<Span>,
+        size: f32,
+        align: Align,
+        rule: HeadingRule,
+    },
+    /// A line across the column.
+    Rule { color: Option<Color> },
+    /// Vertical space, in points.
+    Space(f32),
+}
+
+/// Something drawn on a page. `y` is measured from the top of the page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    /// Text on a baseline at `y`.
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        span: Span,
+    },
+    Line {
+        x1: f32,
+        x2: f32,
+        y: f32,
+        color: Option<Color>,
+    },
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Page {
+    pub items: Vec<Item>,
+}
+
+/// The page size and margin, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    pub width: f32,
+    pub height: f32,
+    pub margin: f32,
+}
+
+/// The vertical space between blocks, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spacing {
+    /// Between paragraphs.
+    pub paragraph: f32,
+    /// Between list items.
+    pub item: f32,
+}
+
+/// A column on the page, in points from the left edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub x: f32,
+    pub width: f32,
+}
+
+/// A position in the document: the index of the page and the distance from its top.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub page: usize,
+    pub y: f32,
+}
+
+pub const RULE_THICKNESS: f32 = 0.4;
+
+/// A laid out line of a block, before it's placed on a page. Positions are relative to the
+/// column and the line's baseline.
+#[derive(Debug, Default)]
+struct Line {
+    height: f32,
+    /// The distance from the top of the line to the baseline.
+    ascent: f32,
+    runs: Vec<(f32, f32, Span)>,
+    rules: Vec<(f32, f32, f32, Option<Color>)>,
+}
+
+/// Lay out the blocks in the frame, starting at `start`. Pages are added as needed. Returns the
+/// position after the last block.
+pub fn lay_out(
+    blocks: &[Block],
+    frame: Frame,
+    start: Position,
+    geometry: &Geometry,
+    spacing: &Spacing,
+    fonts: &Fonts,
+    pages: &mut Vec<Page>,
+) -> Position {
+    let bottom = geometry.height - geometry.margin;
+    let mut position = start;
+    let mut previous: Option<&Block> = None;
+    for (i, block) in blocks.iter().enumerate() {
+        if let Block::Space(space) = block {
+            position.y += space;
+            previous = None;
+            continue;
+        }
+        position.y += match (previous, block) {
+            (None, _) => 0.0,
+            (Some(Block::Bullet { .. }), Block::Bullet { .. }) => spacing.item,
+            (Some(_), _) => spacing.paragraph,
+        };
+        let block_lines = lines(block, frame.width, fonts);
+        // Keep a heading with the first line of the block after it
+        let keep = match (block, blocks.get(i + 1)) {
+            (Block::Heading { .. }, Some(next)) => lines(next, frame.width, fonts)
+                .first()
+                .map_or(0.0, |x| x.height + spacing.paragraph),
+            _ => 0.0,
+        };
+        let height = block_lines.iter().map(|x| x.height).sum::<f32>();
+        if position.y + height + keep > bottom && position.y > geometry.margin {
+            position = Position {
+                page: position.page + 1,
+                y: geometry.margin,
+            };
+        }
+        for line in block_lines {
+            if position.y + line.height > bottom && position.y > geometry.margin {
+                position = Position {
+                    page: position.page + 1,
+                    y: geometry.margin,
+                };
+            }
+            while pages.len() <= position.page {
+                pages.push(Page::default());
+            }
+            let page = &mut pages[position.page];
+            let baseline = position.y + line.ascent;
+            for (x, size, span) in line.runs {
+                page.items.push(Item::Text {
+                    x: frame.x + x,
+                    y: baseline,
+                    size,
+                    span,
+                });
+            }
+            for (x1, x2, y, color) in line.rules {
+                page.items.push(Item::Line {
+                    x1: frame.x + x1,
+                    x2: frame.x + x2,
+                    y: baseline + y,
+                    color,
+                });
+            }
+            position.y += line.height;
+        }
+        previous = Some(block);
+    }
+    position
+}
+
+/// Break the block into lines that fit in the width.
+fn lines(block: &Block, width: f32, fonts: &Fonts) -> Vec<Line> {
+    let ascent = |size: f32| fonts.ascent_descent(FontStyle::Regular).0 * size;
+    let text_line = |runs: Vec<Run>, size: f32, offset: f32| Line {
+        height: size * LINE_HEIGHT,
+        ascent: ascent(size),
+        runs: runs
+            .into_iter()
+            .map(|x| (offset + x.x, size, x.span))
+            .collect(),
+        rules: Vec::new(),
+    };
+    match block {
+        Block::Text { spans, size, align } => wrap(spans, width, *size, fonts)
+            .into_iter()
+            .map(|runs| {
+                let offset = match align {
+                    Align::Left => 0.0,
+                    Align::Center => (width - runs_width(&runs, *size, fonts)) / 2.0,
+                };
+                text_line(runs, *size, offset)
+            })
+            .collect(),
+        Block::Split { left, right, size } => {
+            let right = single_line(right, *size, fonts);
+            let right_width = runs_width(&right, *size, fonts);
+            let gap = if right.is_empty() { 0.0 } else { *size };
+            let mut lines = wrap(left, width - right_width - gap, *size, fonts)
+                .into_iter()
+                .map(|runs| text_line(runs, *size, 0.0))
+                .collect::<Vec<Line>>();
+            if lines.is_empty() {
+                lines.push(text_line(Vec::new(), *size, 0.0));
+            }
+            lines[0]
+                .runs
+                .extend(text_line(right, *size, width - right_width).runs);
+            lines
+        }
+        Block::Spread { items, size } => {
+            let items = items
+                .iter()
+                .map(|x| {
+                    let runs = single_line(x, *size, fonts);
+                    (runs_width(&runs, *size, fonts), runs)
+                })
+                .collect::<Vec<(f32, Vec<Run>)>>();
+            // Fill each line with as many items as fit with at least a space of 1em between them
+            let mut rows: Vec<Vec<(f32, Vec<Run>)>> = Vec::new();
+            let mut row_width = 0.0;
+            for item in items {
+                match rows.last_mut() {
+                    Some(row) if row_width + size + item.0 <= width => {
+                        row_width += size + item.0;
+                        row.push(item);
+                    }
+                    _ => {
+                        row_width = item.0;
+                        rows.push(vec![item]);
+                    }
+                }
+            }
+            rows.into_iter()
+                .map(|row| {
+                    let total = row.iter().map(|x| x.0).sum::<f32>();
+                    let gap = if row.len() > 1 {
+                        (width - total) / (row.len() - 1) as f32
+                    } else {
+                        0.0
+                    };
+                    let mut x = 0.0;
+                    let mut line = text_line(Vec::new(), *size, 0.0);
+                    for (item_width, runs) in row {
+                        line.runs.extend(text_line(runs, *size, x).runs);
+                        x += item_width + gap;
+                    }
+                    line
+                })
+                .collect()
+        }
+        Block::Bullet { spans, size } => {
+            let indent = size * 1.5;
+            let mut lines = wrap(spans, width - indent, *size, fonts)
+                .into_iter()
+                .map(|runs| text_line(runs, *size, indent))
+                .collect::<Vec<Line>>();
+            if let Some(first) = lines.first_mut() {
+                first.runs.insert(
+                    0,
+                    (size * 0.5, *size, Span::new("\u{2022}", FontStyle::Regular)),
+                );
+            }
+            lines
+        }
+        Block::Heading {
+            spans,
+            size,
+            align,
+            rule,
+        } => {
+            let color = spans.first().and_then(|x| x.color);
+            let mut lines = wrap(spans, width, *size, fonts)
+                .into_iter()
+                .map(|runs| {
+                    let runs_width = runs_width(&runs, *size, fonts);
+                    let offset = match align {
+                        Align::Left => 0.0,
+                        Align::Center => (width - runs_width) / 2.0,
+                    };
+                    let mut line = text_line(runs, *size, offset);
+                    if *rule == HeadingRule::After {
+                        line.rules
+                            .push((runs_width + size * 0.5, width, -size * 0.3, color));
+                    }
+                    line
+                })
+                .collect::<Vec<Line>>();
+            if *rule == HeadingRule::Below {
+                if let Some(last) = lines.last_mut() {
+                    last.rules.push((0.0, width, size * 0.3, color));
+                    last.height += size * 0.3;
+                }
+            }
+            lines
+        }
+        Block::Rule { color } => vec![Line {
+            height: RULE_THICKNESS * 10.0,
+            ascent: RULE_THICKNESS * 5.0,
+            runs: Vec::new(),
+            rules: vec![(0.0, width, 0.0, *color)],
+        }],
+        Block::Space(_) => Vec::new(),
+    }
+}
+
+/// A piece of a line in one style, `x` points from the start of the line.
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    x: f32,
+    span: Span,
+}
+
+fn runs_width(runs: &[Run], size: f32, fonts: &Fonts) -> f32 {
+    runs.last()
+        .map_or(0.0, |x| x.x + fonts.width(x.span.style, &x.span.text, size))
+}
+
+/// A word split into the parts of it in each style, e.g. "**bold**," is a bold and a regular part.
+struct Word {
+    parts: Vec<Span>,
+    width: f32,
+}
+
+impl Word {
+    fn new(parts: Vec<Span>, size: f32, fonts: &Fonts) -> Word {
+        let width = parts
+            .iter()
+            .map(|x| fonts.width(x.style, &x.text, size))
+            .sum();
+        Word { parts, width }
+    }
+
+    /// Split a word that's wider than the line into pieces that fit, one character at a time.
+    fn split(self, width: f32, size: f32, fonts: &Fonts) -> Vec<Word> {
+        if self.width <= width {
+            return vec![self];
+        }
+        let mut words = Vec::new();
+        let mut parts: Vec<Span> = Vec::new();
+        let mut current = 0.0;
+        for part in self.parts {
+            for c in part.text.chars() {
+                let c_width = fonts.width(part.style, c.encode_utf8(&mut [0; 4]), size);
+                if current + c_width > width && current > 0.0 {
+                    words.push(Word::new(std::mem::take(&mut parts), size, fonts));
+                    current = 0.0;
+                }
+                match parts.last_mut() {
+                    Some(x) if x.same_format(&part) => x.text.push(c),
+                    _ => parts.push(part.with_text(c.to_string())),
+                }
+                current += c_width;
+            }
+        }
+        if !parts.is_empty() {
+            words.push(Word::new(parts, size, fonts));
+        }
+        words
+    }
+}
+
+fn words(spans: &[Span], size: f32, fonts: &Fonts) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut parts: Vec<Span> = Vec::new();
+    for span in spans {
+        let mut text = String::new();
+        for c in span.text.chars() {
+            if c.is_whitespace() {
+                if !text.is_empty() {
+                    parts.push(span.with_text(std::mem::take(&mut text)));
+                }
+                if !parts.is_empty() {
+                    words.push(Word::new(std::mem::take(&mut parts), size, fonts));
+                }
+            } else {
+                text.push(c);
+            }
+        }
+        if !text.is_empty() {
+            parts.push(span.with_text(text));
+        }
+    }
+    if !parts.is_empty() {
+        words.push(Word::new(parts, size, fonts));
+    }
+    words
+}
+
+/// Wrap the text into lines no wider than `width`. Words are separated by a single space, which
+/// is part of the text so it's kept when the text is copied from the pdf.
+fn wrap(spans: &[Span], width: f32, size: f32, fonts: &Fonts) -> Vec<Vec<Run>> {
+    let mut lines: Vec<Vec<Run>> = Vec::new();
+    let mut line: Vec<Run> = Vec::new();
+    let mut x = 0.0;
+    for word in words(spans, size, fonts)
+        .into_iter()
+        .flat_map(|x| x.split(width, size, fonts))
+    {
+        if let Some(last) = line.last_mut() {
+            let space = fonts.width(last.span.style, " ", size);
+            if x + space + word.width > width {
+                lines.push(std::mem::take(&mut line));
+                x = 0.0;
+            } else {
+                last.span.text.push(' ');
+                x += space;
+            }
+        }
+        for part in word.parts {
+            let part_width = fonts.width(part.style, &part.text, size);
+            match line.last_mut() {
+                Some(last) if last.span.same_format(&part) => last.span.text.push_str(&part.text),
+                _ => line.push(Run { x, span: part }),
+            }
+            x += part_width;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// The text on one line, however wide it is.
+fn single_line(spans: &[Span], size: f32, fonts: &Fonts) -> Vec<Run> {
+    wrap(spans, f32::INFINITY, size, fonts)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::renderer::native_pdf::layout::{
+        lay_out, wrap, Block, Color, FontStyle, Fonts, Frame, Geometry, Item, Position, Spacing,
+        Span,
+    };
+
+    fn texts(items: &[Item]) -> Vec<(f32, &str)> {
+        items
+            .iter()
+            .filter_map(|x| match x {
+                Item::Text { y, span, .. } => Some((*y, span.text.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap() {
+        let fonts = Fonts::new(false).unwrap();
+        let spans = vec![
+            Span::new("Cut ", FontStyle::Regular),
+            Span::new("build times", FontStyle::Bold),
+            Span::new(", by half with a cache", FontStyle::Regular),
+        ];
+        let lines = wrap(&spans, 100.0, 10.0, &fonts)
+            .into_iter()
+            .map(|x| x.into_iter().map(|x| x.span.text).collect::<Vec<String>>())
+            .collect::<Vec<Vec<String>>>();
+        assert_eq!(
+            lines,
+            vec![
+                vec!["Cut ", "build times", ","],
+                vec!["by half with a"],
+                vec!["cache"]
+            ]
+        );
+
+        // A word that's wider than the line is split
+        let spans = vec![Span::new("https://example.com/a/long/url", FontStyle::Mono)];
+        let lines = wrap(&spans, 60.0, 10.0, &fonts);
+        assert!(lines.len() > 1);
+        let text = lines
+            .iter()
+            .map(|x| x[0].span.text.as_str())
+            .collect::<String>();
+        assert_eq!(text, "https://example.com/a/long/url");
+    }
+
+    #[test]
+    fn test_lay_out() {
+        let fonts = Fonts::new(true).unwrap();
+        let geometry = Geometry {
+            width: 200.0,
+            height: 100.0,
+            margin: 20.0,
+        };
+        let spacing = Spacing {
+            paragraph: 5.0,
+            item: 1.0,
+        };
+        let blocks = (0..5)
+            .map(|x| Block::Bullet {
+                spans: vec![Span::new(&format!("item {}", x), FontStyle::Regular)],
+                size: 10.0,
+            })
+            .collect::<Vec<Block>>();
+        let mut pages = Vec::new();
+        let end = lay_out(
+            &blocks,
+            Frame {
+                x: 20.0,
+                width: 160.0,
+            },
+            Position { page: 0, y: 20.0 },
+            &geometry,
+            &spacing,
+            &fonts,
+            &mut pages,
+        );
+
+        // Each page fits 4 lines of 12pt with 1pt between them
+        assert_eq!(pages.len(), 2);
+        assert_eq!(end.page, 1);
+        let first = texts(&pages[0].items);
+        assert_eq!(first.len(), 8);
+        assert_eq!(first[0].1, "\u{2022}");
+        assert_eq!(first[1].1, "item 0");
+        assert_eq!(texts(&pages[1].items)[1].1, "item 4");
+        assert!(first[3].0 - first[1].0 > 12.0);
+    }
+
+    #[test]
+    fn test_color() {
+        assert_eq!(Color::from_hex("FF0000"), Some(Color(1.0, 0.0, 0.0)));
+        assert_eq!(Color::from_hex("blue"), None);
+    }
+}
@@ -0,0 +1,900 @@
+//! The native pdf engine. The resume is laid out and written to a pdf directly, with embedded
+//! fonts, so it doesn't need a LaTeX install. The themes, colors and link styles follow the LaTeX
+//! output as closely as the simpler layout allows.
+use crate::config::format_config::{Font, Paper, PdfConfig, Section, Severity, Theme};
+use crate::config::Config;
+use crate::data::markup::{parse, Inline};
+use crate::data::{
+    Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+    OtherExperience, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, TaggedText,
+    Technologies,
+};
+use crate::error::Error;
+use crate::renderer::native_pdf::layout::{
+    lay_out, Align, Block, Color, FontStyle, Fonts, Frame, Geometry, HeadingRule, Page, Position,
+    Spacing, Span,
+};
+use crate::renderer::native_pdf::writer::{write_pdf, Links};
+use crate::renderer::pdf_renderer::{
+    report_page_limit, Shrink, CONTACT, SECTIONS, SHRINK_STEPS, SHRUNK_MARGIN_INCHES, SIDEBAR_RATIO,
+};
+use crate::renderer::{render_option, Renderer};
+use crate::util::{
+    add_https_to_url, cover_letter_file_name, date_string, get_path, time_range_string,
+    write_bytes_to_path,
+};
+use std::path::PathBuf;
+
+mod layout;
+mod writer;
+
+/// The margin used if `pdf_config.margin` can't be read, which the config check reports first.
+const DEFAULT_MARGIN: f32 = 54.0;
+
+/// The font size of the compact theme, and how much a shrunk font size is reduced, relative to
+/// the base font size.
+const SMALLER: f32 = 0.9;
+
+/// The space between the columns of the modern theme.
+const COLUMN_SEP: f32 = 10.0;
+
+#[derive(Default)]
+pub struct NativePdfRenderer {
+    /// Overrides the theme's metrics, e.g. with ones that have been shrunk to fit on fewer pages.
+    metrics: Option<Metrics>,
+}
+
+/// The margin, base font size and spacing of the document, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Metrics {
+    margin: f32,
+    size: f32,
+    /// Use the tighter paragraph and list spacing of the compact theme.
+    tight: bool,
+}
+
+impl Metrics {
+    fn new(pdf_config: &PdfConfig) -> Metrics {
+        let margin = pdf_config.margin_points().unwrap_or(DEFAULT_MARGIN);
+        let size = pdf_config.font_size_points();
+        match pdf_config.theme {
+            Theme::Classic | Theme::Modern => Metrics {
+                margin,
+                size,
+                tight: false,
+            },
+            Theme::Compact => Metrics {
+                margin,
+                size: size * SMALLER,
+                tight: true,
+            },
+        }
+    }
+
+    fn spacing(&self) -> Spacing {
+        if self.tight {
+            Spacing {
+                paragraph: self.size * 0.1,
+                item: 0.0,
+            }
+        } else {
+            Spacing {
+                paragraph: self.size * 0.2,
+                item: self.size * 0.1,
+            }
+        }
+    }
+
+    /// The space between sections: a line, or half a line with the tight spacing.
+    fn section_skip(&self) -> f32 {
+        if self.tight {
+            self.size * 0.6
+        } else {
+            self.size * 1.2
+        }
+    }
+
+    /// Apply a step of `shrink_to_fit`. Returns false if the step wouldn't shrink anything.
+    fn shrink(&mut self, step: Shrink) -> bool {
+        match step {
+            Shrink::Margin => {
+                let margin = SHRUNK_MARGIN_INCHES as f32 * 72.0;
+                if self.margin <= margin {
+                    return false;
+                }
+                self.margin = margin;
+            }
+            Shrink::Spacing => {
+                if self.tight {
+                    return false;
+                }
+                self.tight = true;
+            }
+            Shrink::FontSize => self.size *= SMALLER,
+        }
+        true
+    }
+}
+
+impl NativePdfRenderer {
+    pub fn new() -> NativePdfRenderer {
+        NativePdfRenderer::default()
+    }
+
+    fn metrics(&self, config: &Config) -> Metrics {
+        self.metrics
+            .unwrap_or_else(|| Metrics::new(&config.format_config.pdf_config))
+    }
+
+    fn geometry(&self, config: &Config) -> Geometry {
+        let (width, height) = match config.format_config.pdf_config.paper {
+            Paper::Letter => (612.0, 792.0),
+            Paper::A4 => (595.28, 841.89),
+        };
+        Geometry {
+            width,
+            height,
+            margin: self.metrics(config).margin,
+        }
+    }
+
+    /// Lay out the resume on pages.
+    fn pages(&self, element: &Resume, config: &Config, fonts: &Fonts) -> Result<Vec<Page>, Error> {
+        let pdf_config = &config.format_config.pdf_config;
+        let metrics = self.metrics(config);
+        let geometry = self.geometry(config);
+        let spacing = metrics.spacing();
+        let frame = Frame {
+            x: geometry.margin,
+            width: geometry.width - 2.0 * geometry.margin,
+        };
+        let mut pages = Vec::new();
+
+        // Name
+        let (scale, align, color) = match pdf_config.theme {
+            Theme::Classic => (1.44, Align::Center, None),
+            Theme::Compact => (1.2, Align::Center, None),
+            Theme::Modern => (2.07, Align::Left, accent_color(config)),
+        };
+        let mut name = Span::new(&element.name, FontStyle::Bold);
+        name.color = color;
+        let header = [
+            Block::Text {
+                spans: vec![name],
+                size: metrics.size * scale,
+                align,
+            },
+            Block::Space(metrics.size * 0.5),
+        ];
+        let start = Position {
+            page: 0,
+            y: geometry.margin,
+        };
+        let start = lay_out(
+            &header, frame, start, &geometry, &spacing, fonts, &mut pages,
+        );
+
+        let sections = config
+            .format_config
+            .sections(&pdf_config.content.sections, SECTIONS);
+        if pdf_config.theme == Theme::Modern {
+            // The contact details and technologies go in a narrow column on the left, and both
+            // columns start below the name
+            let (sidebar, main): (Vec<Section>, Vec<Section>) = sections
+                .into_iter()
+                .partition(|x| matches!(x, Section::PersonalInfo | Section::Technologies));
+            let sidebar_width = (frame.width - COLUMN_SEP) * SIDEBAR_RATIO as f32;
+            let sidebar_frame = Frame {
+                x: frame.x,
+                width: sidebar_width,
+            };
+            let main_frame = Frame {
+                x: frame.x + sidebar_width + COLUMN_SEP,
+                width: frame.width - sidebar_width - COLUMN_SEP,
+            };
+            let blocks = self.render_sections(element, &sidebar, config)?;
+            lay_out(
+                &blocks,
+                sidebar_frame,
+                start,
+                &geometry,
+                &spacing,
+                fonts,
+                &mut pages,
+            );
+            let blocks = self.render_sections(element, &main, config)?;
+            lay_out(
+                &blocks, main_frame, start, &geometry, &spacing, fonts, &mut pages,
+            );
+        } else {
+            let blocks = self.render_sections(element, &sections, config)?;
+            lay_out(
+                &blocks, frame, start, &geometry, &spacing, fonts, &mut pages,
+            );
+        }
+        Ok(pages)
+    }
+
+    /// Render the sections in order. In the single column themes the personal info is a header
+    /// line, the other sections are separated by a space.
+    fn render_sections(
+        &self,
+        element: &Resume,
+        sections: &[Section],
+        config: &Config,
+    ) -> Result<Vec<Block>, Error> {
+        let mut blocks = Vec::new();
+        let header_line = config.format_config.pdf_config.theme != Theme::Modern;
+        let mut previous_section = false;
+        for section in sections {
+            let rendered: Option<Vec<Block>> = match section {
+                Section::PersonalInfo if header_line => {
+                    blocks.extend(self.render(&element.personal_info, config)?);
+                    continue;
+                }
+                Section::PersonalInfo => Some(self.render(&element.personal_info, config)?),
+                Section::Objective => render_option(self, &element.objective, config)?,
+                Section::Experience if element.professional_experience.is_empty() => None,
+                Section::Experience => Some(self.render(&element.professional_experience, config)?),
+                Section::Projects => render_option(self, &element.other_experience, config)?,
+                Section::Technologies => render_option(self, &element.technologies, config)?,
+                Section::Education if element.education.is_empty() => None,
+                Section::Education => Some(self.render(&element.education, config)?),
+                Section::Certifications if element.certifications.is_empty() => None,
+                Section::Certifications => Some(self.render(&element.certifications, config)?),
+                Section::Custom if element.custom_sections.is_empty() => None,
+                Section::Custom => Some(self.render(&element.custom_sections, config)?),
+            };
+            if let Some(rendered) = rendered {
+                if previous_section {
+                    blocks.push(Block::Space(self.metrics(config).section_skip()));
+                }
+                blocks.extend(rendered);
+                previous_section = true;
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Write the pages to a pdf named `file_name` in the output directory.
+    fn write(
+        &self,
+        pages: &[Page],
+        title: &str,
+        file_name: &str,
+        config: &Config,
+        fonts: &Fonts,
+    ) -> Result<PathBuf, Error> {
+        let pdf_config = &config.format_config.pdf_config;
+        let links = Links {
+            style: pdf_config.link_style,
+            color: pdf_config.link_color.as_deref().and_then(Color::from_hex),
+        };
+        let pdf = write_pdf(pages, &self.geometry(config), fonts, links, title)?;
+        let path = get_path(
+            config.args.output_dir.as_ref(),
+            file_name,
+            Some(String::from("pdf")).as_ref(),
+        );
+        write_bytes_to_path(&pdf, &path)
+    }
+}
+
+impl Renderer<Resume, PathBuf> for NativePdfRenderer {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
+        self.render_with_notes(element, config)
+            .map(|(path, _)| path)
+    }
+
+    /// Lay out the resume and write it to a pdf, shrinking it to fit on `max_pages` pages first if
+    /// `shrink_to_fit` is set.
+    fn render_with_notes(
+        &self,
+        element: &Resume,
+        config: &Config,
+    ) -> Result<(PathBuf, Vec<String>), Error> {
+        let pdf_config = &config.format_config.pdf_config;
+        let fonts = Fonts::new(pdf_config.font == Font::Helvet)?;
+        if let Some(c) = &element.cover_letter {
+            let blocks: Vec<Block> = self.render(c, config)?;
+            let geometry = self.geometry(config);
+            let frame = Frame {
+                x: geometry.margin,
+                width: geometry.width - 2.0 * geometry.margin,
+            };
+            let start = Position {
+                page: 0,
+                y: geometry.margin,
+            };
+            let spacing = Spacing {
+                paragraph: 0.0,
+                item: 0.0,
+            };
+            let mut pages = Vec::new();
+            lay_out(
+                &blocks, frame, start, &geometry, &spacing, &fonts, &mut pages,
+            );
+            let title = c.name.as_deref().unwrap_or("Cover Letter");
+            self.write(
+                &pages,
+                title,
+                &cover_letter_file_name(config),
+                config,
+                &fonts,
+            )?;
+        }
+
+        let mut metrics = self.metrics(config);
+        let mut pages = self.pages(element, config, &fonts)?;
+        let mut adjustments = Vec::new();
+        if let Some(max_pages) = pdf_config.max_pages {
+            if pdf_config.shrink_to_fit && pdf_config.page_limit != Severity::Off {
+                for step in SHRINK_STEPS {
+                    if pages.len() <= max_pages {
+                        break;
+                    }
+                    if !metrics.shrink(*step) {
+                        continue;
+                    }
+                    adjustments.push(step.to_string());
+                    let renderer = NativePdfRenderer {
+                        metrics: Some(metrics),
+                    };
+                    pages = renderer.pages(element, config, &fonts)?;
+                }
+            }
+        }
+
+        let renderer = NativePdfRenderer {
+            metrics: Some(metrics),
+        };
+        let path = renderer.write(
+            &pages,
+            &element.name,
+            config.args.output_name(),
+            config,
+            &fonts,
+        )?;
+        match pdf_config.max_pages {
+            Some(max_pages) => {
+                report_page_limit(config, path, Some(pages.len()), max_pages, adjustments)
+            }
+            None => Ok((path, Vec::new())),
+        }
+    }
+}
+
+impl Renderer<PersonalInfo, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = Vec::new();
+        let size = self.metrics(config).size;
+        let fields = config
+            .format_config
+            .contact(&config.format_config.pdf_config.content.contact, CONTACT);
+        let items = element
+            .contact_items(&fields)
+            .into_iter()
+            .map(|x| {
+                let mut span = Span::new(&x.plain_text(), FontStyle::Regular);
+                span.link = x.link;
+                vec![span]
+            })
+            .collect::<Vec<Vec<Span>>>();
+        // The sidebar lists one item per line
+        if config.format_config.pdf_config.theme == Theme::Modern {
+            if !items.is_empty() {
+                blocks.push(self.section_header("CONTACT", config));
+                blocks.extend(items.into_iter().map(|spans| Block::Text {
+                    spans,
+                    size,
+                    align: Align::Left,
+                }));
+            }
+            return Ok(blocks);
+        }
+        // Each row is spread across the page: the first item is flush left, the last flush right
+        blocks.extend(
+            items
+                .chunks(config.format_config.pdf_config.contact_columns.max(1))
+                .map(|row| Block::Spread {
+                    items: row.to_vec(),
+                    size,
+                }),
+        );
+        blocks.push(Block::Rule {
+            color: accent_color(config),
+        });
+        Ok(blocks)
+    }
+}
+
+impl Renderer<Objective, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Objective, config: &Config) -> Result<Vec<Block>, Error> {
+        Ok(vec![Block::Text {
+            spans: rich_spans(&element.objective, FontStyle::Regular),
+            size: self.metrics(config).size,
+            align: Align::Left,
+        }])
+    }
+}
+
+impl Renderer<Vec<ProfessionalExperience>, Vec<Block>> for NativePdfRenderer {
+    fn render(
+        &self,
+        element: &Vec<ProfessionalExperience>,
+        config: &Config,
+    ) -> Result<Vec<Block>, Error> {
+        let mut blocks = vec![self.section_header("EXPERIENCE", config)];
+        for experience in element.iter() {
+            blocks.extend(self.render(experience, config)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<ProfessionalExperience, Vec<Block>> for NativePdfRenderer {
+    fn render(
+        &self,
+        element: &ProfessionalExperience,
+        config: &Config,
+    ) -> Result<Vec<Block>, Error> {
+        let mut blocks = Vec::new();
+        let size = self.metrics(config).size;
+        if let (Some(org), Some(location)) = (&element.organization, &element.location) {
+            blocks.push(split(org, location, FontStyle::Bold, size));
+        }
+        let time_range = time_range_string(
+            &element.start,
+            &element.end,
+            &config.format_config.pdf_config.dates.date_format,
+            config.format_config.pdf_config.dates.show_duration,
+        );
+        blocks.push(split(
+            &element.position,
+            &time_range,
+            FontStyle::Italic,
+            size,
+        ));
+        blocks.extend(bullets(&element.experience, size));
+        Ok(blocks)
+    }
+}
+
+impl Renderer<Vec<CustomSection>, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Vec<CustomSection>, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = Vec::new();
+        for (i, section) in element.iter().enumerate() {
+            if i > 0 {
+                blocks.push(Block::Space(self.metrics(config).section_skip()));
+            }
+            blocks.extend(self.render(section, config)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<CustomSection, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &CustomSection, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = vec![self.section_header(&element.title.to_uppercase(), config)];
+        for item in element.items.iter() {
+            blocks.extend(self.render(item, config)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<CustomSectionItem, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &CustomSectionItem, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = Vec::new();
+        let size = self.metrics(config).size;
+        if element.title.is_some() || element.location.is_some() {
+            blocks.push(split(
+                element.title.as_deref().unwrap_or_default(),
+                element.location.as_deref().unwrap_or_default(),
+                FontStyle::Bold,
+                size,
+            ));
+        }
+        if element.subtitle.is_some() || element.date.is_some() {
+            blocks.push(split(
+                element.subtitle.as_deref().unwrap_or_default(),
+                element.date.as_deref().unwrap_or_default(),
+                FontStyle::Italic,
+                size,
+            ));
+        }
+        blocks.extend(bullets(&element.bullets, size));
+        Ok(blocks)
+    }
+}
+
+impl Renderer<OtherExperience, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &OtherExperience, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = vec![self.section_header("PROJECTS", config)];
+        for project in element.get_projects_for_resume() {
+            blocks.push(self.render(project, config)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<ProjectInfo, Block> for NativePdfRenderer {
+    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<Block, Error> {
+        let mut name = Span::new(&element.project_name, FontStyle::Regular);
+        if !element.url.is_empty() {
+            name.link = Some(add_https_to_url(&element.url));
+        }
+        let mut spans = vec![name, Span::new(": ", FontStyle::Regular)];
+        spans.extend(rich_spans(&element.description, FontStyle::Regular));
+        Ok(Block::Bullet {
+            spans,
+            size: self.metrics(config).size,
+        })
+    }
+}
+
+impl Renderer<Technologies, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Technologies, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = vec![self.section_header("TECHNOLOGIES", config)];
+        let size = self.metrics(config).size;
+        let technologies = element.get_technologies();
+        if config.format_config.pdf_config.theme == Theme::Modern {
+            blocks.extend(technologies.iter().map(|x| Block::Text {
+                spans: vec![Span::new(x, FontStyle::Regular)],
+                size,
+                align: Align::Left,
+            }));
+        } else {
+            blocks.push(Block::Text {
+                spans: vec![Span::new(&technologies.join(", "), FontStyle::Regular)],
+                size,
+                align: Align::Center,
+            });
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<Vec<Education>, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Vec<Education>, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = vec![self.section_header("EDUCATION", config)];
+        for education in element.iter() {
+            blocks.extend(self.render(education, config)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<Education, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Education, config: &Config) -> Result<Vec<Block>, Error> {
+        let size = self.metrics(config).size;
+        let graduation = element
+            .graduation
+            .map(|x| x.format(&config.format_config.pdf_config.dates.date_format))
+            .unwrap_or_default();
+        let mut blocks = vec![
+            split(&element.school, &element.location, FontStyle::Bold, size),
+            split(
+                &element.degree_string(),
+                &graduation,
+                FontStyle::Italic,
+                size,
+            ),
+        ];
+        let paragraphs = element
+            .details_string(!config.format_config.hide_gpa)
+            .into_iter()
+            .chain(element.coursework_string());
+        blocks.extend(paragraphs.map(|x| Block::Text {
+            spans: vec![Span::new(&x, FontStyle::Regular)],
+            size,
+            align: Align::Left,
+        }));
+        blocks.extend(bullets(element.get_extras(), size));
+        Ok(blocks)
+    }
+}
+
+impl Renderer<Vec<Certification>, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Vec<Certification>, config: &Config) -> Result<Vec<Block>, Error> {
+        let mut blocks = vec![self.section_header("CERTIFICATIONS", config)];
+        for certification in element.iter() {
+            blocks.extend(self.render(certification, config)?);
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<Certification, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &Certification, config: &Config) -> Result<Vec<Block>, Error> {
+        let size = self.metrics(config).size;
+        let mut name = Span::new(&element.name, FontStyle::Bold);
+        name.link = element.url.as_deref().map(add_https_to_url);
+        let dates = element.dates(&config.format_config.pdf_config.dates.date_format);
+        let mut blocks = vec![Block::Split {
+            left: vec![name],
+            right: vec![Span::new(&dates, FontStyle::Regular)],
+            size,
+        }];
+        if !element.issuer.is_empty() || element.credential_id.is_some() {
+            let credential_id = element
+                .credential_id
+                .as_ref()
+                .map(|x| format!("Credential ID: {}", x))
+                .unwrap_or_default();
+            blocks.push(split(
+                &element.issuer,
+                &credential_id,
+                FontStyle::Italic,
+                size,
+            ));
+        }
+        Ok(blocks)
+    }
+}
+
+impl Renderer<CoverLetter, Vec<Block>> for NativePdfRenderer {
+    fn render(&self, element: &CoverLetter, config: &Config) -> Result<Vec<Block>, Error> {
+        let size = self.metrics(config).size;
+        let paragraph = |spans: Vec<Span>| Block::Text {
+            spans,
+            size,
+            align: Align::Left,
+        };
+        let plain = |s: &str| paragraph(vec![Span::new(s, FontStyle::Regular)]);
+
+        let mut blocks = Vec::new();
+        blocks.extend(element.name.as_deref().map(plain));
+        blocks.extend(element.email.as_deref().map(plain));
+        blocks.push(plain(&date_string()));
+        blocks.push(Block::Space(size * 2.0));
+        blocks.push(plain(&element.salutation));
+        for p in element.paragraphs.iter() {
+            blocks.push(Block::Space(size));
+            blocks.push(paragraph(rich_spans(p, FontStyle::Regular)));
+        }
+        blocks.push(Block::Space(size * 2.0));
+        blocks.push(plain(&element.closing));
+        blocks.extend(element.name.as_deref().map(plain));
+        Ok(blocks)
+    }
+}
+
+impl NativePdfRenderer {
+    /// A centered header in the classic theme, a header followed by a rule in the compact theme,
+    /// and a larger header above a rule in the modern theme.
+    fn section_header(&self, header: &str, config: &Config) -> Block {
+        let size = self.metrics(config).size;
+        let mut span = Span::new(header, FontStyle::Bold);
+        span.color = accent_color(config);
+        let (size, align, rule) = match config.format_config.pdf_config.theme {
+            Theme::Classic => (size, Align::Center, HeadingRule::None),
+            Theme::Compact => (size, Align::Left, HeadingRule::After),
+            Theme::Modern => (size * 1.2, Align::Left, HeadingRule::Below),
+        };
+        Block::Heading {
+            spans: vec![span],
+            size,
+            align,
+            rule,
+        }
+    }
+}
+
+fn accent_color(config: &Config) -> Option<Color> {
+    config
+        .format_config
+        .pdf_config
+        .accent_color
+        .as_deref()
+        .and_then(Color::from_hex)
+}
+
+/// A line with `left` in the style flush left and `right` flush right, e.g. a position and its
+/// dates.
+fn split(left: &str, right: &str, style: FontStyle, size: f32) -> Block {
+    Block::Split {
+        left: vec![Span::new(left, style)],
+        right: vec![Span::new(right, FontStyle::Regular)],
+        size,
+    }
+}
+
+fn bullets(items: &[TaggedText], size: f32) -> Vec<Block> {
+    items
+        .iter()
+        .map(|x| Block::Bullet {
+            spans: rich_spans(x, FontStyle::Regular),
+            size,
+        })
+        .collect()
+}
+
+/// Turn text with inline markup into spans.
+fn rich_spans(s: &str, style: FontStyle) -> Vec<Span> {
+    let mut spans = Vec::new();
+    push_spans(&parse(s), style, None, &mut spans);
+    spans
+}
+
+fn push_spans(inlines: &[Inline], style: FontStyle, link: Option<&str>, spans: &mut Vec<Span>) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(x) => {
+                let mut span = Span::new(x, style);
+                span.link = link.map(String::from);
+                spans.push(span);
+            }
+            Inline::Bold(x) => push_spans(x, style.bold(), link, spans),
+            Inline::Emphasis(x) => push_spans(x, style.italic(), link, spans),
+            Inline::Code(x) => {
+                let mut span = Span::new(x, FontStyle::Mono);
+                span.link = link.map(String::from);
+                spans.push(span);
+            }
+            Inline::Link { text, url } => {
+                push_spans(text, style, Some(&add_https_to_url(url)), spans)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::format_config::{Engine, Theme};
+    use crate::config::Config;
+    use crate::data::date::ResumeDate;
+    use crate::data::{PersonalInfo, ProfessionalExperience, Resume, TaggedText, Technologies};
+    use crate::renderer::native_pdf::layout::{Fonts, Item, Page, Span};
+    use crate::renderer::native_pdf::{Metrics, NativePdfRenderer};
+    use crate::renderer::pdf_renderer::{PdfRenderer, Shrink};
+    use crate::renderer::Renderer;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_pages() {
+        let pages = NativePdfRenderer::new()
+            .pages(
+                &get_resume(),
+                &Config::default(),
+                &Fonts::new(false).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            spans(&pages[0])
+                .iter()
+                .map(|x| x.text.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "Foo Bar",
+                "foo@bar.com",
+                "EXPERIENCE",
+                "position",
+                "Jan 2020 - Present",
+                "\u{2022}",
+                "Sped up ",
+                "builds ",
+                "with a ",
+                "cache",
+                "TECHNOLOGIES",
+                "Rust",
+            ]
+        );
+        let link = spans(&pages[0])
+            .into_iter()
+            .find(|x| x.text == "cache")
+            .unwrap();
+        assert_eq!(link.link.as_deref(), Some("https://example.com"));
+        let email = spans(&pages[0])
+            .into_iter()
+            .find(|x| x.text == "foo@bar.com")
+            .unwrap();
+        assert_eq!(email.link.as_deref(), Some("mailto:foo@bar.com"));
+    }
+
+    #[test]
+    fn test_zero_contact_columns() {
+        let mut config = Config::default();
+        config.format_config.pdf_config.contact_columns = 0;
+        let pages = NativePdfRenderer::new()
+            .pages(&get_resume(), &config, &Fonts::new(false).unwrap())
+            .unwrap();
+        assert!(spans(&pages[0]).iter().any(|x| x.text == "foo@bar.com"));
+    }
+
+    #[test]
+    fn test_modern_columns() {
+        let mut config = Config::default();
+        config.format_config.pdf_config.theme = Theme::Modern;
+        let pages = NativePdfRenderer::new()
+            .pages(&get_resume(), &config, &Fonts::new(false).unwrap())
+            .unwrap();
+        let position = |s: &str| {
+            pages[0]
+                .items
+                .iter()
+                .find_map(|x| match x {
+                    Item::Text { x, y, span, .. } if span.text == s => Some((*x, *y)),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        // The sidebar and the main column start at the same height
+        assert_eq!(position("CONTACT").1, position("EXPERIENCE").1);
+        assert!(position("CONTACT").0 < position("EXPERIENCE").0);
+        assert!(position("CONTACT").1 < position("TECHNOLOGIES").1);
+    }
+
+    #[test]
+    fn test_shrink() {
+        let mut metrics = Metrics {
+            margin: 54.0,
+            size: 10.0,
+            tight: false,
+        };
+        assert!(metrics.shrink(Shrink::Margin));
+        assert!(!metrics.shrink(Shrink::Margin));
+        assert!(metrics.shrink(Shrink::Spacing));
+        assert!(!metrics.shrink(Shrink::Spacing));
+        assert!(metrics.shrink(Shrink::FontSize));
+        assert_eq!(
+            metrics,
+            Metrics {
+                margin: 36.0,
+                size: 9.0,
+                tight: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let dir = std::env::temp_dir().join("resume_generator_test_native_pdf");
+        let mut config = Config::default();
+        config.args.output_dir = Some(dir.to_string_lossy().to_string());
+        config.args.output_name = Some(String::from("resume"));
+        config.format_config.pdf_config.engine = Engine::Native;
+
+        // The pdf renderer hands off to the native engine, so pdflatex isn't needed
+        let path: PathBuf = PdfRenderer::new().render(&get_resume(), &config).unwrap();
+        assert_eq!(path, dir.join("resume.pdf"));
+        let pdf = fs::read(&path).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn spans(page: &Page) -> Vec<&Span> {
+        page.items
+            .iter()
+            .filter_map(|x| match x {
+                Item::Text { span, .. } => Some(span),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn get_resume() -> Resume {
+        Resume {
+            name: String::from("Foo Bar"),
+            personal_info: PersonalInfo {
+                email: String::from("foo@bar.com"),
+                ..Default::default()
+            },
+            professional_experience: vec![ProfessionalExperience {
+                position: String::from("position"),
+                start: ResumeDate::new(2020, 1).unwrap(),
+                end: ResumeDate::Present,
+                experience: vec![TaggedText::from(
+                    "Sped up **builds** with a [cache](example.com)",
+                )],
+                ..Default::default()
+            }],
+            technologies: Some(Technologies {
+                technologies: vec![TaggedText::from("Rust")],
+            }),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! Writes laid out pages to a pdf. Each font is embedded as a subset with only the glyphs the
+//! document uses, along with a map back to Unicode so the text can be selected, searched and
+//! parsed.
+use crate::config::format_config::LinkStyle;
+use crate::error::Error;
+use crate::renderer::native_pdf::layout::{
+    Color, FontStyle, Fonts, Geometry, Item, Page, RULE_THICKNESS,
+};
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use pdf_writer::types::{
+    ActionType, AnnotationType, BorderType, CidFontType, FontFlags, SystemInfo, UnicodeCmap,
+};
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use std::collections::BTreeMap;
+
+/// How links look, from `link_style` and `link_color`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Links {
+    pub style: LinkStyle,
+    pub color: Option<Color>,
+}
+
+/// The color of links with the `color` style when `link_color` isn't set, like hyperref's.
+const DEFAULT_LINK_COLOR: Color = Color(0.0, 0.0, 1.0);
+/// The color of link boxes when `link_color` isn't set, like hyperref's.
+const DEFAULT_BORDER_COLOR: Color = Color(1.0, 0.0, 0.0);
+
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// The glyphs of a font that the document uses, and the character each one was used for.
+#[derive(Default)]
+struct UsedGlyphs {
+    glyphs: BTreeMap<u16, char>,
+}
+
+/// Ids for the pdf objects, handed out in order.
+struct Ids(i32);
+
+impl Ids {
+    fn next(&mut self) -> Ref {
+        self.0 += 1;
+        Ref::new(self.0)
+    }
+}
+
+pub fn write_pdf(
+    pages: &[Page],
+    geometry: &Geometry,
+    fonts: &Fonts,
+    links: Links,
+    title: &str,
+) -> Result<Vec<u8>, Error> {
+    let mut pdf = Pdf::new();
+    let mut ids = Ids(0);
+    let catalog_id = ids.next();
+    let page_tree_id = ids.next();
+    let info_id = ids.next();
+    let page_ids = pages.iter().map(|_| ids.next()).collect::<Vec<Ref>>();
+
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_ids.len() as i32);
+    pdf.document_info(info_id)
+        .title(TextStr(title))
+        .creator(TextStr("resume_generator"));
+
+    // Draw the pages first, so only the fonts that are used get embedded
+    let mut used = FontStyle::ALL
+        .iter()
+        .map(|_| UsedGlyphs::default())
+        .collect::<Vec<UsedGlyphs>>();
+    let contents = pages
+        .iter()
+        .map(|x| page_content(x, geometry, fonts, links, &mut used))
+        .collect::<Vec<Vec<u8>>>();
+    let font_ids = FontStyle::ALL
+        .iter()
+        .filter(|x| !used[**x as usize].glyphs.is_empty())
+        .map(|x| (*x, ids.next()))
+        .collect::<Vec<(FontStyle, Ref)>>();
+
+    for ((page, page_id), content) in pages.iter().zip(page_ids).zip(contents) {
+        let content_id = ids.next();
+        pdf.stream(
+            content_id,
+            &compress_to_vec_zlib(&content, COMPRESSION_LEVEL),
+        )
+        .filter(Filter::FlateDecode);
+
+        let mut page_writer = pdf.page(page_id);
+        page_writer
+            .media_box(Rect::new(0.0, 0.0, geometry.width, geometry.height))
+            .parent(page_tree_id)
+            .contents(content_id);
+        let mut annotations = page_writer.annotations();
+        for (rect, url) in link_rects(page, geometry, fonts) {
+            let mut annotation = annotations.push();
+            annotation.subtype(AnnotationType::Link).rect(rect);
+            annotation
+                .action()
+                .action_type(ActionType::Uri)
+                .uri(Str(url.as_bytes()));
+            let border_color = links.color.unwrap_or(DEFAULT_BORDER_COLOR);
+            match links.style {
+                LinkStyle::Box => {
+                    annotation.color_rgb(border_color.0, border_color.1, border_color.2);
+                    annotation
+                        .border_style()
+                        .width(1.0)
+                        .style(BorderType::Solid);
+                }
+                LinkStyle::Underline => {
+                    annotation.color_rgb(border_color.0, border_color.1, border_color.2);
+                    annotation
+                        .border_style()
+                        .width(1.0)
+                        .style(BorderType::Underline);
+                }
+                LinkStyle::Color | LinkStyle::Hidden => {
+                    annotation.border_style().width(0.0);
+                }
+            }
+        }
+        annotations.finish();
+        let mut resources = page_writer.resources();
+        let mut page_fonts = resources.fonts();
+        for (style, id) in &font_ids {
+            page_fonts.pair(font_name(*style), *id);
+        }
+    }
+
+    for (style, id) in font_ids {
+        write_font(&mut pdf, &mut ids, id, style, fonts, &used[style as usize])?;
+    }
+    Ok(pdf.finish())
+}
+
+fn font_name(style: FontStyle) -> Name<'static> {
+    match style {
+        FontStyle::Regular => Name(b"F0"),
+        FontStyle::Bold => Name(b"F1"),
+        FontStyle::Italic => Name(b"F2"),
+        FontStyle::BoldItalic => Name(b"F3"),
+        FontStyle::Mono => Name(b"F4"),
+    }
+}
+
+/// Draw the page. Text is written as glyph ids, which are recorded in `used` for the font subsets.
+fn page_content(
+    page: &Page,
+    geometry: &Geometry,
+    fonts: &Fonts,
+    links: Links,
+    used: &mut [UsedGlyphs],
+) -> Vec<u8> {
+    let mut content = Content::new();
+    for item in &page.items {
+        match item {
+            Item::Text { x, y, size, span } => {
+                let color = match (&span.link, links.style) {
+                    (Some(_), LinkStyle::Color) => Some(links.color.unwrap_or(DEFAULT_LINK_COLOR)),
+                    _ => span.color,
+                }
+                .unwrap_or(Color(0.0, 0.0, 0.0));
+                let mut glyphs = Vec::new();
+                for c in span.text.chars() {
+                    let glyph = fonts.glyph(span.style, c);
+                    used[span.style as usize].glyphs.entry(glyph.0).or_insert(c);
+                    glyphs.extend_from_slice(&glyph.0.to_be_bytes());
+                }
+                content
+                    .begin_text()
+                    .set_fill_rgb(color.0, color.1, color.2)
+                    .set_font(font_name(span.style), *size)
+                    .next_line(*x, geometry.height - y)
+                    .show(Str(&glyphs))
+                    .end_text();
+            }
+            Item::Line { x1, x2, y, color } => {
+                let color = color.unwrap_or(Color(0.0, 0.0, 0.0));
+                content
+                    .set_stroke_rgb(color.0, color.1, color.2)
+                    .set_line_width(RULE_THICKNESS)
+                    .move_to(*x1, geometry.height - y)
+                    .line_to(*x2, geometry.height - y)
+                    .stroke();
+            }
+        }
+    }
+    content.finish()
+}
+
+/// The clickable area of each link on the page.
+fn link_rects(page: &Page, geometry: &Geometry, fonts: &Fonts) -> Vec<(Rect, String)> {
+    page.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Text { x, y, size, span } => span.link.as_ref().map(|url| {
+                let (ascent, descent) = fonts.ascent_descent(span.style);
+                let width = fonts.width(span.style, span.text.trim_end(), *size);
+                let rect = Rect::new(
+                    *x,
+                    geometry.height - y - descent * size,
+                    x + width,
+                    geometry.height - y + ascent * size,
+                );
+                (rect, url.clone())
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Embed the subset of the font with the used glyphs as a CID font. The glyph ids are used as
+/// character codes, and the ToUnicode map turns them back into text.
+fn write_font(
+    pdf: &mut Pdf,
+    ids: &mut Ids,
+    id: Ref,
+    style: FontStyle,
+    fonts: &Fonts,
+    used: &UsedGlyphs,
+) -> Result<(), Error> {
+    let cid_id = ids.next();
+    let descriptor_id = ids.next();
+    let cmap_id = ids.next();
+    let file_id = ids.next();
+    let face = fonts.face(style);
+    let postscript_name = face
+        .names()
+        .into_iter()
+        .filter(|x| x.name_id == ttf_parser::name_id::POST_SCRIPT_NAME)
+        .find_map(|x| x.to_string())
+        .unwrap_or_else(|| String::from("DejaVu"));
+    // Subset fonts are named with a tag of six capital letters
+    let base_font = format!("{}+{}", subset_tag(style), postscript_name);
+    let system_info = SystemInfo {
+        registry: Str(b"Adobe"),
+        ordering: Str(b"Identity"),
+        supplement: 0,
+    };
+
+    pdf.type0_font(id)
+        .base_font(Name(base_font.as_bytes()))
+        .encoding_predefined(Name(b"Identity-H"))
+        .descendant_font(cid_id)
+        .to_unicode(cmap_id);
+
+    let mut cid = pdf.cid_font(cid_id);
+    cid.subtype(CidFontType::Type2)
+        .base_font(Name(base_font.as_bytes()))
+        .system_info(system_info)
+        .font_descriptor(descriptor_id)
+        .default_width(0.0)
+        .cid_to_gid_map_predefined(Name(b"Identity"));
+    let mut widths = cid.widths();
+    for glyph in used.glyphs.keys() {
+        widths.consecutive(*glyph, [fonts.advance(style, ttf_parser::GlyphId(*glyph))]);
+    }
+    widths.finish();
+    cid.finish();
+
+    let units = f32::from(face.units_per_em());
+    let scale = |x: i16| f32::from(x) * 1000.0 / units;
+    let bbox = face.global_bounding_box();
+    let mut flags = FontFlags::SYMBOLIC;
+    if style == FontStyle::Mono {
+        flags |= FontFlags::FIXED_PITCH;
+    }
+    if matches!(style, FontStyle::Italic | FontStyle::BoldItalic) {
+        flags |= FontFlags::ITALIC;
+    }
+    let weight = face.weight().to_number();
+    pdf.font_descriptor(descriptor_id)
+        .name(Name(base_font.as_bytes()))
+        .flags(flags)
+        .bbox(Rect::new(
+            scale(bbox.x_min),
+            scale(bbox.y_min),
+            scale(bbox.x_max),
+            scale(bbox.y_max),
+        ))
+        .italic_angle(face.italic_angle().unwrap_or_default())
+        .ascent(scale(face.ascender()))
+        .descent(scale(face.descender()))
+        .cap_height(scale(face.capital_height().unwrap_or(face.ascender())))
+        .stem_v(10.0 + 0.244 * (f32::from(weight) - 50.0))
+        .font_file2(file_id);
+
+    let mut cmap = UnicodeCmap::new(Name(b"Custom"), system_info);
+    for (glyph, c) in &used.glyphs {
+        cmap.pair(*glyph, *c);
+    }
+    pdf.cmap(cmap_id, &cmap.finish());
+
+    let glyphs = used.glyphs.keys().copied().collect::<Vec<u16>>();
+    let subset = subsetter::subset(fonts.data(style), 0, subsetter::Profile::pdf(&glyphs))
+        .map_err(|e| {
+            Error::Render(format!(
+                "An error occurred while embedding the font [{}]: {}",
+                postscript_name, e
+            ))
+        })?;
+    pdf.stream(file_id, &compress_to_vec_zlib(&subset, COMPRESSION_LEVEL))
+        .filter(Filter::FlateDecode);
+    Ok(())
+}
+
+fn subset_tag(style: FontStyle) -> String {
+    let letter = (b'A' + style as u8) as char;
+    letter.to_string().repeat(6)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::format_config::LinkStyle;
+    use crate::renderer::native_pdf::layout::{FontStyle, Fonts, Geometry, Item, Page, Span};
+    use crate::renderer::native_pdf::writer::{link_rects, write_pdf, Links};
+
+    #[test]
+    fn test_write_pdf() {
+        let fonts = Fonts::new(false).unwrap();
+        let geometry = Geometry {
+            width: 612.0,
+            height: 792.0,
+            margin: 54.0,
+        };
+        let mut link = Span::new("example.com", FontStyle::Bold);
+        link.link = Some(String::from("https://example.com"));
+        let page = Page {
+            items: vec![
+                Item::Text {
+                    x: 54.0,
+                    y: 64.0,
+                    size: 10.0,
+                    span: Span::new("Foo Bar", FontStyle::Regular),
+                },
+                Item::Text {
+                    x: 54.0,
+                    y: 76.0,
+                    size: 10.0,
+                    span: link,
+                },
+            ],
+        };
+
+        let rects = link_rects(&page, &geometry, &fonts);
+        assert_eq!(rects.len(), 1);
+        assert_eq!(rects[0].1, "https://example.com");
+
+        let pdf = write_pdf(
+            &[page.clone(), page],
+            &geometry,
+            &fonts,
+            Links {
+                style: LinkStyle::Box,
+                color: None,
+            },
+            "Foo Bar",
+        )
+        .unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("/URI (https://example.com)"));
+        assert!(text.contains("/BaseFont /AAAAAA+DejaVuSerif"));
+        assert!(text.contains("/ToUnicode"));
+        // A subset is much smaller than the whole font
+        assert!(pdf.len() < 100_000);
+    }
+}
@@ -1,131 +1,654 @@
+use crate::config::format_config::{
+    length_points, ContactField, Engine, Font, LinkStyle, Paper, Section, Severity, Theme,
+};
 use crate::config::Config;
+use crate::data::markup::{parse, render, Style};
 use crate::data::{
-    CoverLetter, Education, Objective, OtherExperience, PersonalInfo, ProfessionalExperience,
-    ProjectInfo, Resume, Technologies,
+    Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+    OtherExperience, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, Technologies,
 };
-use crate::renderer::Renderer;
+use crate::error::Error;
+#[cfg(feature = "native-pdf")]
+use crate::renderer::native_pdf::NativePdfRenderer;
+use crate::renderer::{render_option, Renderer};
 use crate::util::{
-    cover_letter_file_name, date_string, escape_special_chars, get_path, time_range_string,
-    write_string_to_path,
+    add_https_to_url, cover_letter_file_name, date_string, escape_special_chars, escape_url,
+    get_path, time_range_string, write_string_to_path,
 };
 use latex::{print, Document, Element, Paragraph, PreambleElement};
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use std::{env, fmt, fs, io, thread};
+use tempfile::TempDir;
+
+/// The default order of the sections.
+pub(crate) const SECTIONS: &[Section] = &[
+    Section::PersonalInfo,
+    Section::Objective,
+    Section::Experience,
+    Section::Projects,
+    Section::Technologies,
+    Section::Education,
+    Section::Certifications,
+    Section::Custom,
+];
+
+/// The default contact fields and their order.
+pub(crate) const CONTACT: &[ContactField] = &[
+    ContactField::Github,
+    ContactField::Email,
+    ContactField::Phone,
+    ContactField::Location,
+    ContactField::Linkedin,
+    ContactField::Website,
+    ContactField::Pronouns,
+];
 
 #[derive(Default)]
-pub struct PdfRenderer;
+pub struct PdfRenderer {
+    /// Overrides the theme's layout, e.g. with one that's been shrunk to fit on fewer pages.
+    layout: Option<Layout>,
+}
 
 impl PdfRenderer {
     pub fn new() -> PdfRenderer {
-        PdfRenderer
+        PdfRenderer::default()
+    }
+
+    fn layout(&self, config: &Config) -> Layout {
+        self.layout
+            .clone()
+            .unwrap_or_else(|| Layout::new(config.format_config.pdf_config.theme))
+    }
+
+    /// Render the sections in order. In the single column themes the personal info is a header
+    /// line, the other sections are separated by a space.
+    fn render_sections(
+        &self,
+        element: &Resume,
+        sections: &[Section],
+        layout: &Layout,
+        config: &Config,
+    ) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        let header_line = config.format_config.pdf_config.theme != Theme::Modern;
+        let mut previous_section = false;
+        for section in sections {
+            let rendered: Option<Document> = match section {
+                Section::PersonalInfo if header_line => {
+                    doc.push_doc(&self.render(&element.personal_info, config)?);
+                    continue;
+                }
+                Section::PersonalInfo => Some(self.render(&element.personal_info, config)?),
+                Section::Objective => render_option(self, &element.objective, config)?,
+                Section::Experience if element.professional_experience.is_empty() => None,
+                Section::Experience => Some(self.render(&element.professional_experience, config)?),
+                Section::Projects => render_option(self, &element.other_experience, config)?,
+                Section::Technologies => render_option(self, &element.technologies, config)?,
+                Section::Education if element.education.is_empty() => None,
+                Section::Education => Some(self.render(&element.education, config)?),
+                Section::Certifications if element.certifications.is_empty() => None,
+                Section::Certifications => Some(self.render(&element.certifications, config)?),
+                Section::Custom if element.custom_sections.is_empty() => None,
+                Section::Custom => Some(self.render(&element.custom_sections, config)?),
+            };
+            if let Some(rendered) = rendered {
+                if previous_section {
+                    doc.push_doc(&vspace(layout));
+                }
+                doc.push_doc(&rendered);
+                previous_section = true;
+            }
+        }
+        Ok(doc)
+    }
+
+    /// Shrink the layout one step at a time until the resume fits on `max_pages` pages (if
+    /// `shrink_to_fit` is set), then report a resume that is still too long as set by `page_limit`.
+    fn limit_pages(
+        &self,
+        element: &Resume,
+        config: &Config,
+        path: PathBuf,
+        mut pages: Option<usize>,
+        max_pages: usize,
+    ) -> Result<(PathBuf, Vec<String>), Error> {
+        let pdf_config = &config.format_config.pdf_config;
+        if pdf_config.page_limit == Severity::Off {
+            return Ok((path, Vec::new()));
+        }
+        let too_long = |pages: Option<usize>| pages.is_some_and(|x| x > max_pages);
+
+        let mut layout = self.layout(config);
+        let mut adjustments = Vec::new();
+        if pdf_config.shrink_to_fit {
+            for step in SHRINK_STEPS {
+                if !too_long(pages) {
+                    break;
+                }
+                if !step.apply(&mut layout, &pdf_config.margin) {
+                    continue;
+                }
+                adjustments.push(step.to_string());
+                let renderer = PdfRenderer {
+                    layout: Some(layout.clone()),
+                };
+                let resume: String = renderer.render(element, config)?;
+                pages = render_tex_and_pdf(&resume, config.args.output_name(), config)?.1;
+            }
+        }
+
+        report_page_limit(config, path, pages, max_pages, adjustments)
+    }
+}
+
+/// Report a resume that's longer than `max_pages` as set by `page_limit`, or the adjustments that
+/// were made to fit it. A warning and the adjustments are returned as notes.
+pub(crate) fn report_page_limit(
+    config: &Config,
+    path: PathBuf,
+    pages: Option<usize>,
+    max_pages: usize,
+    adjustments: Vec<String>,
+) -> Result<(PathBuf, Vec<String>), Error> {
+    match pages {
+        Some(pages) if pages > max_pages => {
+            let e = Error::PageLimit {
+                path: path.clone(),
+                pages,
+                max_pages,
+                adjustments,
+            };
+            match config.format_config.pdf_config.page_limit {
+                Severity::Off => Ok((path, Vec::new())),
+                Severity::Error => Err(e),
+                _ => Ok((path, vec![format!("Warning: {}", e)])),
+            }
+        }
+        _ if adjustments.is_empty() => Ok((path, Vec::new())),
+        _ => {
+            let note = format!(
+                "Shrunk the resume to fit on {} page(s): {}.",
+                max_pages,
+                adjustments.join(", ")
+            );
+            Ok((path, vec![note]))
+        }
     }
 }
 
 impl Renderer<Resume, PathBuf> for PdfRenderer {
-    /// Write the LaTeX to a file, then run a command to generate a pdf from the LaTeX file
-    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
+        self.render_with_notes(element, config)
+            .map(|(path, _)| path)
+    }
+
+    /// Write the LaTeX to a file, then run a command to generate a pdf from the LaTeX file. The
+    /// notes report a resume that's longer than `max_pages`, or how it was shrunk to fit.
+    fn render_with_notes(
+        &self,
+        element: &Resume,
+        config: &Config,
+    ) -> Result<(PathBuf, Vec<String>), Error> {
+        #[cfg(feature = "native-pdf")]
+        if config.format_config.pdf_config.engine == Engine::Native {
+            return NativePdfRenderer::new().render_with_notes(element, config);
+        }
         if let Some(c) = &element.cover_letter {
             let cover_letter: String = self.render(c, config)?;
             render_tex_and_pdf(&cover_letter, &cover_letter_file_name(config), config)?;
         }
 
         let resume: String = self.render(element, config)?;
-        render_tex_and_pdf(&resume, &config.args.output_name, config)
+        let (path, pages) = render_tex_and_pdf(&resume, config.args.output_name(), config)?;
+        match config.format_config.pdf_config.max_pages {
+            Some(max_pages) => self.limit_pages(element, config, path, pages, max_pages),
+            None => Ok((path, Vec::new())),
+        }
     }
 }
 
-fn render_tex_and_pdf(s: &str, file_name: &str, config: &Config) -> Result<PathBuf, String> {
-    let path = get_path(
+/// The spacing and font size of the document. The defaults can be shrunk to fit more on a page.
+#[derive(Debug, Clone, PartialEq)]
+struct Layout {
+    /// Overrides `pdf_config.margin`.
+    margin: Option<String>,
+    /// How much the space between paragraphs is reduced around section headers and lists.
+    par_mod: &'static str,
+    /// The space between list items.
+    itemsep: &'static str,
+    /// A LaTeX font size command, e.g. "small", applied to the whole resume.
+    font_size: Option<&'static str>,
+    /// The space between sections.
+    section_skip: &'static str,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            margin: None,
+            par_mod: "0.1in",
+            itemsep: "-0.05in",
+            font_size: None,
+            section_skip: "\\baselineskip",
+        }
+    }
+}
+
+impl Layout {
+    fn new(theme: Theme) -> Layout {
+        match theme {
+            Theme::Classic | Theme::Modern => Layout::default(),
+            Theme::Compact => Layout {
+                par_mod: TIGHT_PAR_MOD,
+                itemsep: TIGHT_ITEMSEP,
+                font_size: Some("small"),
+                section_skip: "0.5\\baselineskip",
+                ..Default::default()
+            },
+        }
+    }
+
+    fn itemsep(&self) -> String {
+        format!("\\setlength\\itemsep{{{}}}", self.itemsep)
+    }
+}
+
+/// The adjustments that are tried, in order, to make the resume fit on `max_pages` pages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Shrink {
+    Margin,
+    Spacing,
+    FontSize,
+}
+
+pub(crate) const SHRINK_STEPS: &[Shrink] = &[Shrink::Margin, Shrink::Spacing, Shrink::FontSize];
+
+pub(crate) const SHRUNK_MARGIN_INCHES: f64 = 0.5;
+
+/// The paragraph and list spacing of the compact theme, and of other themes once they're shrunk.
+const TIGHT_PAR_MOD: &str = "0.15in";
+const TIGHT_ITEMSEP: &str = "-0.1in";
+
+/// The width of the modern theme's sidebar, as a fraction of the text width.
+pub(crate) const SIDEBAR_RATIO: f64 = 0.3;
+
+impl Shrink {
+    /// Apply the step to the layout. Returns false if the step wouldn't shrink anything, e.g. when
+    /// the configured margin is already smaller than the shrunk one or the spacing is already tight.
+    /// A margin in a unit other than in, cm, mm or pt isn't changed, since it can't be compared.
+    fn apply(self, layout: &mut Layout, margin: &str) -> bool {
+        match self {
+            Shrink::Margin => {
+                let shrunk = (SHRUNK_MARGIN_INCHES * 72.0) as f32;
+                if !length_points(margin).is_some_and(|x| x > shrunk) {
+                    return false;
+                }
+                layout.margin = Some(format!("{}in", SHRUNK_MARGIN_INCHES));
+            }
+            Shrink::Spacing => {
+                if layout.par_mod == TIGHT_PAR_MOD && layout.itemsep == TIGHT_ITEMSEP {
+                    return false;
+                }
+                layout.par_mod = TIGHT_PAR_MOD;
+                layout.itemsep = TIGHT_ITEMSEP;
+            }
+            Shrink::FontSize => {
+                layout.font_size = Some(match layout.font_size {
+                    None => "small",
+                    Some(_) => "footnotesize",
+                })
+            }
+        }
+        true
+    }
+}
+
+impl fmt::Display for Shrink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shrink::Margin => write!(f, "reduced the margin to {}in", SHRUNK_MARGIN_INCHES),
+            Shrink::Spacing => write!(f, "tightened the paragraph and list spacing"),
+            Shrink::FontSize => write!(f, "used a smaller font"),
+        }
+    }
+}
+
+/// Write the LaTeX to a file in the build directory and run the LaTeX engine on it, as many times
+/// as it takes for the references to settle. Only the pdf, and the .tex file if `keep_tex` is set,
+/// are copied to the output directory. Returns the path of the pdf and its page count, if the
+/// engine reported one. If the engine fails, the .tex and .log files are kept for the error.
+pub(crate) fn render_tex_and_pdf(
+    s: &str,
+    file_name: &str,
+    config: &Config,
+) -> Result<(PathBuf, Option<usize>), Error> {
+    let pdf_config = &config.format_config.pdf_config;
+    let build_dir = BuildDir::new(config)?;
+    let tex_path = build_dir.path().join(file_name).with_extension("tex");
+    write_string_to_path(s, &tex_path)?;
+    let pdf_path = get_path(
         config.args.output_dir.as_ref(),
         file_name,
-        Some(String::from("tex")).as_ref(),
+        Some(String::from("pdf")).as_ref(),
     );
-    write_string_to_path(s, &path)?;
-
-    let x = Command::new("pdflatex")
-        .arg("-output-directory")
-        .arg(path.parent().unwrap_or_else(|| Path::new(".")).as_os_str())
-        .arg(path.as_os_str())
-        .output()
-        .map_err(|e| {
-            format!(
-                "An error occurred while running the pdflatex command: {}",
-                e
-            )
+
+    // latexmk does its own reruns
+    let runs = match pdf_config.engine {
+        Engine::Latexmk => 1,
+        _ => MAX_LATEX_RUNS,
+    };
+    let mut log = String::new();
+    for _ in 0..runs {
+        log = run_latex(&tex_path, build_dir.path(), config)
+            .map_err(|e| build_dir.keep_failed(e, &tex_path, &pdf_path))?;
+        if !needs_rerun(&log) {
+            break;
+        }
+    }
+
+    copy_file(&tex_path.with_extension("pdf"), &pdf_path)?;
+    if pdf_config.keep_tex {
+        copy_file(&tex_path, &pdf_path.with_extension("tex"))?;
+    }
+    Ok((pdf_path, page_count(&log)))
+}
+
+/// The most times the LaTeX engine is run to let the references settle.
+const MAX_LATEX_RUNS: usize = 3;
+
+/// How often a running LaTeX engine is checked on.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The directory the LaTeX engine runs in: `build_dir`, or a new directory in the system's temp
+/// directory, so that runs at the same time don't overwrite each other's files. The new directory
+/// is removed when it's dropped.
+enum BuildDir {
+    Configured(PathBuf),
+    Temp(TempDir),
+}
+
+impl BuildDir {
+    fn new(config: &Config) -> Result<BuildDir, Error> {
+        match &config.format_config.pdf_config.build_dir {
+            Some(dir) => Ok(BuildDir::Configured(PathBuf::from(dir))),
+            None => tempfile::Builder::new()
+                .prefix("resume_generator")
+                .tempdir()
+                .map(BuildDir::Temp)
+                .map_err(|e| Error::io(&env::temp_dir(), e)),
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            BuildDir::Configured(dir) => dir,
+            BuildDir::Temp(dir) => dir.path(),
+        }
+    }
+
+    /// The temp directory is removed after a failed run, so copy the .tex file and the engine's
+    /// .log file next to where the pdf would have gone, and point the error at the copy.
+    fn keep_failed(&self, e: Error, tex_path: &Path, pdf_path: &Path) -> Error {
+        if let BuildDir::Configured(_) = self {
+            return e;
+        }
+        let kept = pdf_path.with_extension("tex");
+        if let Err(e) = copy_file(tex_path, &kept) {
+            return e;
+        }
+        // The log is missing if the engine couldn't be run at all
+        let _ = copy_file(&tex_path.with_extension("log"), &kept.with_extension("log"));
+        match e {
+            Error::Latex {
+                command,
+                source,
+                log,
+                ..
+            } => Error::Latex {
+                command,
+                path: kept,
+                source,
+                log,
+            },
+            Error::LatexTimeout {
+                command, seconds, ..
+            } => Error::LatexTimeout {
+                command,
+                path: kept,
+                seconds,
+            },
+            e => e,
+        }
+    }
+}
+
+/// The LaTeX engine's command. A LaTeX template is run with pdflatex when the native engine is
+/// selected.
+fn latex_command(engine: Engine) -> &'static str {
+    match engine {
+        Engine::Pdflatex | Engine::Native => "pdflatex",
+        Engine::Xelatex => "xelatex",
+        Engine::Lualatex => "lualatex",
+        Engine::Latexmk => "latexmk",
+    }
+}
+
+/// The command that builds the .tex file into a pdf in the build directory. nonstopmode keeps the
+/// engine from waiting for input on an error.
+fn build_command(tex_path: &Path, build_dir: &Path, config: &Config) -> Command {
+    let pdf_config = &config.format_config.pdf_config;
+    let mut command = Command::new(latex_command(pdf_config.engine));
+    if pdf_config.engine == Engine::Latexmk {
+        // -g makes latexmk run even if the pdf in a reused build_dir looks up to date, otherwise
+        // it doesn't print the page count that max_pages is checked with
+        command.arg("-pdf").arg("-g");
+    }
+    command
+        .arg("-interaction=nonstopmode")
+        .arg(format!("-output-directory={}", build_dir.display()))
+        .args(&pdf_config.engine_args)
+        .arg(tex_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    command
+}
+
+/// Run the LaTeX engine once. Returns its output.
+fn run_latex(tex_path: &Path, build_dir: &Path, config: &Config) -> Result<String, Error> {
+    let pdf_config = &config.format_config.pdf_config;
+    let command = latex_command(pdf_config.engine).to_string();
+    let timeout = Duration::from_secs(pdf_config.timeout_secs);
+    let output = run_with_timeout(&mut build_command(tex_path, build_dir, config), timeout)
+        .map_err(|e| Error::Latex {
+            command: command.clone(),
+            path: tex_path.to_path_buf(),
+            source: Some(e),
+            log: None,
         })?;
-    if !x.status.success() {
-        Err(format!(
-            "An error occurred while running the pdflatex command: {:?}",
-            x
-        ))
-    } else {
-        Ok(path.with_extension("pdf"))
+    match output {
+        None => Err(Error::LatexTimeout {
+            command,
+            path: tex_path.to_path_buf(),
+            seconds: pdf_config.timeout_secs,
+        }),
+        Some((status, log)) if !status.success() => Err(Error::Latex {
+            command,
+            path: tex_path.to_path_buf(),
+            source: None,
+            log: Some(pdflatex_log_excerpt(&log)),
+        }),
+        Some((_, log)) => Ok(log),
+    }
+}
+
+/// Run the command and collect its output. The command is killed if it takes longer than
+/// `timeout`, and None is returned. On unix the command runs in its own process group, so the
+/// processes it started, like latexmk's engine runs, are killed with it.
+fn run_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+) -> Result<Option<(ExitStatus, String)>, io::Error> {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command.spawn()?;
+    // The output is read on another thread, so the command can't block on a full pipe
+    let stdout = child.stdout.take();
+    let reader = thread::spawn(move || {
+        let mut output = Vec::new();
+        if let Some(mut stdout) = stdout {
+            let _ = stdout.read_to_end(&mut output);
+        }
+        String::from_utf8_lossy(&output).to_string()
+    });
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait()? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                kill(&mut child);
+                let _ = child.wait();
+                return Ok(None);
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    };
+    Ok(Some((status, reader.join().unwrap_or_default())))
+}
+
+/// Kill the child and, on unix, the rest of its process group.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = <libc::pid_t as std::convert::TryFrom<u32>>::try_from(child.id()) {
+        // SAFETY: kill only sends a signal; a negative pid targets the child's process group
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+        return;
     }
+    let _ = child.kill();
+}
+
+/// Whether the LaTeX output asks for another run, e.g. because hyperref's outlines or the labels
+/// changed.
+fn needs_rerun(log: &str) -> bool {
+    // The output is wrapped, so a message can be split across lines
+    let log = log.replace('\n', "");
+    ["Rerun to get", "Label(s) may have changed"]
+        .iter()
+        .any(|x| log.contains(x))
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<(), Error> {
+    let dir = to.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    fs::copy(from, to).map_err(|e| Error::io(from, e))?;
+    Ok(())
+}
+
+/// Get the page count from pdflatex's "Output written on resume.pdf (2 pages, 34567 bytes)." line.
+fn page_count(log: &str) -> Option<usize> {
+    let start = log.rfind("Output written on")?;
+    // pdflatex wraps long lines, so a long path can push the count onto the next line
+    let line = log[start..].replace('\n', "");
+    let end = line.find(" page")?;
+    line[..end].rsplit('(').next()?.trim().parse().ok()
+}
+
+const LOG_EXCERPT_LINES: usize = 10;
+
+/// Get the part of the pdflatex output that describes the error. pdflatex marks errors with lines
+/// that start with '!', followed by the context of the error. If there are no such lines, the end
+/// of the output is used instead.
+fn pdflatex_log_excerpt(log: &str) -> String {
+    let lines = log.lines().collect::<Vec<&str>>();
+    let excerpt = match lines.iter().position(|x| x.starts_with('!')) {
+        Some(start) => &lines[start..(start + LOG_EXCERPT_LINES).min(lines.len())],
+        None => &lines[lines.len().saturating_sub(LOG_EXCERPT_LINES)..],
+    };
+    excerpt.join("\n")
 }
 
 impl Renderer<Resume, String> for PdfRenderer {
     /// Render a LaTeX string
-    fn render(&self, element: &Resume, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<String, Error> {
         // Build the document
         let doc: Document = self.render(element, config)?;
 
         // convert to a string
         print(&doc).map_err(|e| {
-            format!(
+            Error::Render(format!(
                 "An error occurred while rendering the LaTeX resume to a string: {}",
                 e
-            )
+            ))
         })
     }
 }
 
 impl Renderer<CoverLetter, String> for PdfRenderer {
-    fn render(&self, element: &CoverLetter, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &CoverLetter, config: &Config) -> Result<String, Error> {
         let doc: Document = self.render(element, config)?;
 
         print(&doc).map_err(|e| {
-            format!(
+            Error::Render(format!(
                 "An error occurred while rendering the LaTeX cover letter to a string: {}",
                 e
-            )
+            ))
         })
     }
 }
 
 impl Renderer<Resume, Document> for PdfRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<Document, String> {
-        let mut doc = document_preamble(config);
+    fn render(&self, element: &Resume, config: &Config) -> Result<Document, Error> {
+        let layout = self.layout(config);
+        let theme = config.format_config.pdf_config.theme;
+        let mut doc = document_preamble(config, &layout);
+        if let Some(size) = layout.font_size {
+            doc.push(Element::UserDefined(format!("\\{}", size)));
+        }
 
         // Name
-        doc.push(Element::Environment(
-            String::from("center"),
-            vec![format!("\\bf\\Large {}", element.name)],
-        ));
-
-        // Header
-        doc.push_doc(&self.render(&element.personal_info, config)?);
-
-        // We want everything to be flush to the left side (except for a few outliers)
-        doc.push(Element::UserDefined(String::from("\\begin{flushleft}")));
-        {
-            if let Some(obj) = &element.objective {
-                doc.push_doc(&self.render(obj, config)?);
-                doc.push_doc(&vspace());
-            }
-            doc.push_doc(&self.render(&element.professional_experience, config)?);
-            if let Some(e) = &element.other_experience {
-                doc.push_doc(&vspace());
-                doc.push_doc(&self.render(e, config)?);
-            }
-            if let Some(e) = &element.technologies {
-                doc.push_doc(&vspace());
-                doc.push_doc(&self.render(e, config)?);
-            }
-            if let Some(e) = &element.education {
-                doc.push_doc(&vspace());
-                doc.push_doc(&self.render(e, config)?);
-            }
+        let name = latex(config, &element.name);
+        match theme {
+            Theme::Classic => doc.push(Element::Environment(
+                String::from("center"),
+                vec![format!("\\bf\\Large {}", name)],
+            )),
+            Theme::Compact => doc.push(Element::Environment(
+                String::from("center"),
+                vec![format!("\\bf\\large {}", name)],
+            )),
+            Theme::Modern => doc.push(Element::UserDefined(format!(
+                "\\noindent{{\\bf\\huge {}}}\n",
+                accented(config, &name)
+            ))),
+        };
 
+        let sections = config
+            .format_config
+            .sections(&config.format_config.pdf_config.content.sections, SECTIONS);
+        if theme == Theme::Modern {
+            // The contact details and technologies go in a narrow column on the left. paracol's
+            // columns can break across pages, unlike minipages.
+            let (sidebar, main): (Vec<Section>, Vec<Section>) = sections
+                .into_iter()
+                .partition(|x| matches!(x, Section::PersonalInfo | Section::Technologies));
+            doc.preamble.use_package("paracol");
+            doc.push(Element::UserDefined(format!(
+                "\\raggedright\n\\setlength\\parindent{{0pt}}\n\\columnratio{{{}}}\n\\begin{{paracol}}{{2}}",
+                SIDEBAR_RATIO
+            )));
+            doc.push_doc(&self.render_sections(element, &sidebar, &layout, config)?);
+            doc.push(Element::UserDefined(String::from("\\switchcolumn")));
+            doc.push_doc(&self.render_sections(element, &main, &layout, config)?);
+            doc.push(Element::UserDefined(String::from("\\end{paracol}")));
+        } else {
+            // We want everything to be flush to the left side (except for a few outliers)
+            doc.push(Element::UserDefined(String::from("\\begin{flushleft}")));
+            doc.push_doc(&self.render_sections(element, &sections, &layout, config)?);
             doc.push(Element::UserDefined(String::from("\\end{flushleft}")));
         }
 
@@ -134,14 +657,34 @@ impl Renderer<Resume, Document> for PdfRenderer {
 }
 
 impl Renderer<PersonalInfo, Document> for PdfRenderer {
-    fn render(&self, element: &PersonalInfo, _config: &Config) -> Result<Document, String> {
+    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<Document, Error> {
         let mut doc = Document::default();
-        // todo: display something else instead of phone number?
-        doc.push(Element::UserDefined(format!(
-            "{} \\hfill {}",
-            element.github, element.email
-        )));
-        doc.push(Element::UserDefined(String::from(
+        let fields = config
+            .format_config
+            .contact(&config.format_config.pdf_config.content.contact, CONTACT);
+        let items = element
+            .contact_items(&fields)
+            .iter()
+            .map(|x| latex(config, &x.plain_text()))
+            .collect::<Vec<String>>();
+        // The sidebar lists one item per line
+        if config.format_config.pdf_config.theme == Theme::Modern {
+            if !items.is_empty() {
+                doc.push_doc(&section_header("CONTACT", &self.layout(config), config));
+                doc.push(Element::UserDefined(items.join(" \\\\\n")));
+            }
+            return Ok(doc);
+        }
+        // Each row is spread across the page: the first item is flush left, the last flush right
+        if !items.is_empty() {
+            let rows = items
+                .chunks(config.format_config.pdf_config.contact_columns.max(1))
+                .map(|row| row.join(" \\hfill "))
+                .collect::<Vec<String>>();
+            doc.push(Element::UserDefined(rows.join(" \\\\\n")));
+        }
+        doc.push(Element::UserDefined(accented(
+            config,
             "\\rule{\\textwidth}{0.4pt}",
         )));
 
@@ -150,9 +693,11 @@ impl Renderer<PersonalInfo, Document> for PdfRenderer {
 }
 
 impl Renderer<Objective, Document> for PdfRenderer {
-    fn render(&self, element: &Objective, _config: &Config) -> Result<Document, String> {
+    fn render(&self, element: &Objective, config: &Config) -> Result<Document, Error> {
         let mut doc = Document::default();
-        doc.push(Paragraph::from(element.objective.as_str()));
+        doc.push(Paragraph::from(
+            rich_latex(config, &element.objective).as_str(),
+        ));
         Ok(doc)
     }
 }
@@ -162,9 +707,9 @@ impl Renderer<Vec<ProfessionalExperience>, Document> for PdfRenderer {
         &self,
         element: &Vec<ProfessionalExperience>,
         config: &Config,
-    ) -> Result<Document, String> {
+    ) -> Result<Document, Error> {
         let mut doc = Document::default();
-        doc.push_doc(&section_header("EXPERIENCE"));
+        doc.push_doc(&section_header("EXPERIENCE", &self.layout(config), config));
 
         let reduced = element
             .iter()
@@ -175,10 +720,10 @@ impl Renderer<Vec<ProfessionalExperience>, Document> for PdfRenderer {
                 Ok(a)
             })
             .unwrap_or_else(|| {
-                Err(
+                Err(Error::Render(
                     "An error occurred while rendering professional experience to LaTeX."
                         .to_string(),
-                )
+                ))
             })?;
         doc.push_doc(&reduced);
 
@@ -187,46 +732,110 @@ impl Renderer<Vec<ProfessionalExperience>, Document> for PdfRenderer {
 }
 
 impl Renderer<ProfessionalExperience, Document> for PdfRenderer {
-    fn render(
-        &self,
-        element: &ProfessionalExperience,
-        _config: &Config,
-    ) -> Result<Document, String> {
+    fn render(&self, element: &ProfessionalExperience, config: &Config) -> Result<Document, Error> {
         let mut doc = Document::default();
+        let layout = self.layout(config);
         if let (Some(org), Some(location)) = (&element.organization, &element.location) {
             doc.push(Element::UserDefined(format!(
                 "{{\\bf {}}} \\hfill {}\n",
-                org, location
+                latex(config, org),
+                latex(config, location)
             )));
         }
         doc.push(Element::UserDefined(format!(
             "\\emph{{{}}} \\hfill {}\n",
-            element.position,
-            time_range_string(&element.start, &element.end)
+            latex(config, &element.position),
+            time_range_string(
+                &element.start,
+                &element.end,
+                &config.format_config.pdf_config.dates.date_format,
+                config.format_config.pdf_config.dates.show_duration,
+            )
         )));
-        doc.push_doc(&par_skip_start());
-        let mut itemize_content = vec![String::from("\\setlength\\itemsep{-0.05in}")];
+        doc.push_doc(&par_skip_start(&layout));
+        let mut itemize_content = vec![layout.itemsep()];
         let mut exp = element
             .experience
             .iter()
-            .map(|e| format!("\\item {}", e))
+            .map(|e| item(&rich_latex(config, e)))
             .collect::<Vec<String>>();
         itemize_content.append(&mut exp);
         doc.push(Element::Environment(
             String::from("itemize"),
             itemize_content,
         ));
-        doc.push_doc(&par_skip_end());
+        doc.push_doc(&par_skip_end(&layout));
+        Ok(doc)
+    }
+}
+
+impl Renderer<Vec<CustomSection>, Document> for PdfRenderer {
+    fn render(&self, element: &Vec<CustomSection>, config: &Config) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        for (i, section) in element.iter().enumerate() {
+            if i > 0 {
+                doc.push_doc(&vspace(&self.layout(config)));
+            }
+            doc.push_doc(&self.render(section, config)?);
+        }
+        Ok(doc)
+    }
+}
+
+impl Renderer<CustomSection, Document> for PdfRenderer {
+    fn render(&self, element: &CustomSection, config: &Config) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        doc.push_doc(&section_header(
+            &latex(config, &element.title.to_uppercase()),
+            &self.layout(config),
+            config,
+        ));
+        for item in element.items.iter() {
+            doc.push_doc(&self.render(item, config)?);
+        }
+        Ok(doc)
+    }
+}
+
+impl Renderer<CustomSectionItem, Document> for PdfRenderer {
+    fn render(&self, element: &CustomSectionItem, config: &Config) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        let layout = self.layout(config);
+        if element.title.is_some() || element.location.is_some() {
+            doc.push(Element::UserDefined(format!(
+                "{{\\bf {}}} \\hfill {}\n",
+                latex(config, element.title.as_deref().unwrap_or_default()),
+                latex(config, element.location.as_deref().unwrap_or_default())
+            )));
+        }
+        if element.subtitle.is_some() || element.date.is_some() {
+            doc.push(Element::UserDefined(format!(
+                "\\emph{{{}}} \\hfill {}\n",
+                latex(config, element.subtitle.as_deref().unwrap_or_default()),
+                latex(config, element.date.as_deref().unwrap_or_default())
+            )));
+        }
+        if !element.bullets.is_empty() {
+            doc.push_doc(&par_skip_start(&layout));
+            let mut itemize_content = vec![layout.itemsep()];
+            itemize_content.extend(element.bullets.iter().map(|e| item(&rich_latex(config, e))));
+            doc.push(Element::Environment(
+                String::from("itemize"),
+                itemize_content,
+            ));
+            doc.push_doc(&par_skip_end(&layout));
+        }
         Ok(doc)
     }
 }
 
 impl Renderer<OtherExperience, Document> for PdfRenderer {
-    fn render(&self, element: &OtherExperience, config: &Config) -> Result<Document, String> {
+    fn render(&self, element: &OtherExperience, config: &Config) -> Result<Document, Error> {
         let mut doc = Document::default();
-        doc.push_doc(&section_header("PROJECTS"));
+        let layout = self.layout(config);
+        doc.push_doc(&section_header("PROJECTS", &layout, config));
 
-        let mut itemize_content = vec![String::from("\\setlength\\itemsep{-0.05in}")];
+        let mut itemize_content = vec![layout.itemsep()];
         let mut projects = element
             .get_projects_for_resume()
             .into_iter()
@@ -243,68 +852,165 @@ impl Renderer<OtherExperience, Document> for PdfRenderer {
 }
 
 impl Renderer<ProjectInfo, String> for PdfRenderer {
-    fn render(&self, element: &ProjectInfo, _config: &Config) -> Result<String, String> {
+    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, Error> {
+        let name = latex(config, &element.project_name);
+        let description = rich_latex(config, &element.description);
+        if element.url.is_empty() {
+            return Ok(format!("{}\n", item(&format!("{}: {}", name, description))));
+        }
         Ok(format!(
             "\\item \\href{{{}}}{{{}}}: {}\n",
-            element.url, element.project_name, element.description
+            latex_url(config, &element.url),
+            name,
+            description
         ))
     }
 }
 
 impl Renderer<Technologies, Document> for PdfRenderer {
-    fn render(&self, element: &Technologies, _config: &Config) -> Result<Document, String> {
+    fn render(&self, element: &Technologies, config: &Config) -> Result<Document, Error> {
         let mut doc = Document::default();
-        doc.push_doc(&section_header("TECHNOLOGIES"));
-        let technologies = element.technologies.join(", ");
-        let technologies = escape_special_chars(&technologies);
-        doc.push(Element::Environment(
-            String::from("center"),
-            vec![technologies],
+        doc.push_doc(&section_header(
+            "TECHNOLOGIES",
+            &self.layout(config),
+            config,
         ));
+        let technologies = element.get_technologies();
+        if config.format_config.pdf_config.theme == Theme::Modern {
+            let technologies = technologies
+                .iter()
+                .map(|x| latex(config, x))
+                .collect::<Vec<String>>();
+            doc.push(Element::UserDefined(technologies.join(" \\\\\n")));
+        } else {
+            doc.push(Element::Environment(
+                String::from("center"),
+                vec![latex(config, &technologies.join(", "))],
+            ));
+        }
+        Ok(doc)
+    }
+}
+
+impl Renderer<Vec<Education>, Document> for PdfRenderer {
+    fn render(&self, element: &Vec<Education>, config: &Config) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        doc.push_doc(&section_header("EDUCATION", &self.layout(config), config));
+        for education in element.iter() {
+            doc.push_doc(&self.render(education, config)?);
+        }
         Ok(doc)
     }
 }
 
 impl Renderer<Education, Document> for PdfRenderer {
-    fn render(&self, element: &Education, _config: &Config) -> Result<Document, String> {
+    fn render(&self, element: &Education, config: &Config) -> Result<Document, Error> {
         let mut doc = Document::default();
-        doc.push_doc(&section_header("UNIVERSITY"));
-        let education = if element.graduation.is_some() {
-            format!(
-                "{} -- {} -- {} -- {}",
-                element.school,
-                element.location,
-                element.major,
-                element.graduation.as_ref().unwrap()
-            )
-        } else {
-            format!(
-                "{} -- {} -- {}",
-                element.school, element.location, element.major
-            )
-        };
-        doc.push(Element::Environment(
-            String::from("center"),
-            vec![education],
+        let layout = self.layout(config);
+        let graduation = element
+            .graduation
+            .map(|x| x.format(&config.format_config.pdf_config.dates.date_format))
+            .unwrap_or_default();
+        doc.push(Element::UserDefined(format!(
+            "{{\\bf {}}} \\hfill {}\n",
+            latex(config, &element.school),
+            latex(config, &element.location)
+        )));
+        doc.push(Element::UserDefined(format!(
+            "\\emph{{{}}} \\hfill {}\n",
+            latex(config, &element.degree_string()),
+            graduation
+        )));
+        let paragraphs = element
+            .details_string(!config.format_config.hide_gpa)
+            .into_iter()
+            .chain(element.coursework_string());
+        for paragraph in paragraphs {
+            doc.push(Element::UserDefined(format!(
+                "{}\n",
+                latex(config, &paragraph)
+            )));
+        }
+        if !element.get_extras().is_empty() {
+            doc.push_doc(&par_skip_start(&layout));
+            let mut itemize_content = vec![layout.itemsep()];
+            itemize_content.extend(
+                element
+                    .get_extras()
+                    .iter()
+                    .map(|e| item(&rich_latex(config, e))),
+            );
+            doc.push(Element::Environment(
+                String::from("itemize"),
+                itemize_content,
+            ));
+            doc.push_doc(&par_skip_end(&layout));
+        }
+        Ok(doc)
+    }
+}
+
+impl Renderer<Vec<Certification>, Document> for PdfRenderer {
+    fn render(&self, element: &Vec<Certification>, config: &Config) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        doc.push_doc(&section_header(
+            "CERTIFICATIONS",
+            &self.layout(config),
+            config,
         ));
+        for certification in element.iter() {
+            doc.push_doc(&self.render(certification, config)?);
+        }
+        Ok(doc)
+    }
+}
+
+impl Renderer<Certification, Document> for PdfRenderer {
+    fn render(&self, element: &Certification, config: &Config) -> Result<Document, Error> {
+        let mut doc = Document::default();
+        let name = match &element.url {
+            Some(url) => format!(
+                "\\href{{{}}}{{{}}}",
+                latex_url(config, &add_https_to_url(url)),
+                latex(config, &element.name)
+            ),
+            None => latex(config, &element.name),
+        };
+        doc.push(Element::UserDefined(format!(
+            "{{\\bf {}}} \\hfill {}\n",
+            name,
+            element.dates(&config.format_config.pdf_config.dates.date_format)
+        )));
+        if !element.issuer.is_empty() || element.credential_id.is_some() {
+            let credential_id = element
+                .credential_id
+                .as_ref()
+                .map(|x| format!("Credential ID: {}", latex(config, x)))
+                .unwrap_or_default();
+            doc.push(Element::UserDefined(format!(
+                "\\emph{{{}}} \\hfill {}\n",
+                latex(config, &element.issuer),
+                credential_id
+            )));
+        }
         Ok(doc)
     }
 }
 
 impl Renderer<CoverLetter, Document> for PdfRenderer {
-    fn render(&self, element: &CoverLetter, config: &Config) -> Result<Document, String> {
-        let mut doc = document_preamble(config);
+    fn render(&self, element: &CoverLetter, config: &Config) -> Result<Document, Error> {
+        let mut doc = document_preamble(config, &self.layout(config));
 
         doc.push(Element::UserDefined(String::from(
             "\\setlength\\parindent{0pt}",
         )));
 
         if let Some(name) = &element.name {
-            doc.push(Element::UserDefined(name.to_owned()));
+            doc.push(Element::UserDefined(latex(config, name)));
             doc.push(Element::UserDefined(String::new()));
         }
         if let Some(email) = &element.email {
-            doc.push(Element::UserDefined(email.to_owned()));
+            doc.push(Element::UserDefined(latex(config, email)));
             doc.push(Element::UserDefined(String::new()));
         }
         doc.push(Element::UserDefined(date_string()));
@@ -314,7 +1020,7 @@ impl Renderer<CoverLetter, Document> for PdfRenderer {
             "\\setlength\\parskip{2em}",
         )));
 
-        doc.push(Element::UserDefined(element.salutation.to_owned()));
+        doc.push(Element::UserDefined(latex(config, &element.salutation)));
         doc.push(Element::UserDefined(String::new()));
 
         doc.push(Element::UserDefined(String::from(
@@ -322,7 +1028,7 @@ impl Renderer<CoverLetter, Document> for PdfRenderer {
         )));
 
         element.paragraphs.iter().for_each(|p| {
-            doc.push(Element::UserDefined(p.to_owned()));
+            doc.push(Element::UserDefined(rich_latex(config, p)));
             doc.push(Element::UserDefined(String::new()));
         });
 
@@ -330,7 +1036,7 @@ impl Renderer<CoverLetter, Document> for PdfRenderer {
             "\\setlength\\parskip{2em}",
         )));
 
-        doc.push(Element::UserDefined(element.closing.to_owned()));
+        doc.push(Element::UserDefined(latex(config, &element.closing)));
         doc.push(Element::UserDefined(String::new()));
 
         doc.push(Element::UserDefined(String::from(
@@ -338,7 +1044,7 @@ impl Renderer<CoverLetter, Document> for PdfRenderer {
         )));
 
         if let Some(name) = &element.name {
-            doc.push(Element::UserDefined(name.to_owned()));
+            doc.push(Element::UserDefined(latex(config, name)));
             doc.push(Element::UserDefined(String::new()));
         }
 
@@ -346,22 +1052,123 @@ impl Renderer<CoverLetter, Document> for PdfRenderer {
     }
 }
 
-fn document_preamble(config: &Config) -> Document {
+/// What LaTeX templates need to follow the pdf config: the preamble of the built-in documents,
+/// which sets the paper, margin, font, colors and link style, and the spacing of the theme.
+#[derive(Debug, Serialize)]
+pub(crate) struct TemplateLatex {
+    preamble: String,
+    par_mod: &'static str,
+    itemsep: &'static str,
+    font_size: Option<&'static str>,
+    section_skip: &'static str,
+}
+
+impl TemplateLatex {
+    pub(crate) fn new(config: &Config) -> Result<TemplateLatex, Error> {
+        let layout = Layout::new(config.format_config.pdf_config.theme);
+        let printed = print(&document_preamble(config, &layout)).map_err(|e| {
+            Error::Render(format!(
+                "An error occurred while rendering the LaTeX preamble to a string: {}",
+                e
+            ))
+        })?;
+        // Leave out the document class and the empty document
+        let preamble = printed
+            .lines()
+            .skip(1)
+            .take_while(|x| *x != "\\begin{document}")
+            .collect::<Vec<&str>>()
+            .join("\n");
+        Ok(TemplateLatex {
+            preamble,
+            par_mod: layout.par_mod,
+            itemsep: layout.itemsep,
+            font_size: layout.font_size,
+            section_skip: layout.section_skip,
+        })
+    }
+}
+
+fn document_preamble(config: &Config, layout: &Layout) -> Document {
+    let pdf_config = &config.format_config.pdf_config;
     let mut doc = Document::default();
     doc.preamble
-        // Set the margins
+        // Set the margins and paper size
         .push(PreambleElement::UsePackage {
             package: String::from("geometry"),
-            argument: Some(format!("margin={}", config.format_config.pdf_config.margin)),
-        })
-        // Set up the font
-        .push(PreambleElement::UsePackage {
-            package: String::from("times"),
-            argument: None,
-        })
-        // Set up the footer and remove the header
-        .use_package("fancyhdr")
-        .use_package("hyperref")
+            argument: Some(format!(
+                "margin={},{}",
+                layout.margin.as_ref().unwrap_or(&pdf_config.margin),
+                match pdf_config.paper {
+                    Paper::Letter => "letterpaper",
+                    Paper::A4 => "a4paper",
+                }
+            )),
+        });
+    // Set up the font. xelatex and lualatex read Unicode and load OpenType fonts with fontspec
+    if pdf_config.engine.is_unicode() {
+        doc.preamble.use_package("fontspec");
+        let command = match pdf_config.font {
+            Font::Helvet => "setsansfont",
+            _ => "setmainfont",
+        };
+        if let Some(font) = opentype_font(pdf_config.font) {
+            doc.preamble.push(PreambleElement::UserDefined(format!(
+                r"\{}{{{}}}",
+                command, font
+            )));
+        }
+    } else {
+        doc.preamble
+            .push(PreambleElement::UsePackage {
+                package: String::from("fontenc"),
+                argument: Some(String::from("T1")),
+            })
+            .push(PreambleElement::UsePackage {
+                package: String::from("inputenc"),
+                argument: Some(String::from("utf8")),
+            })
+            .push(PreambleElement::UsePackage {
+                package: String::from(font_package(pdf_config.font)),
+                argument: None,
+            });
+    }
+    if pdf_config.font == Font::Helvet {
+        doc.preamble.push(PreambleElement::UserDefined(String::from(
+            r"\renewcommand{\familydefault}{\sfdefault}",
+        )));
+    }
+    if let Some(size) = &pdf_config.font_size {
+        doc.preamble.push(PreambleElement::UsePackage {
+            package: String::from("scrextend"),
+            argument: Some(format!("fontsize={}", size)),
+        });
+    }
+    // Set up the colors
+    if pdf_config.accent_color.is_some() || pdf_config.link_color.is_some() {
+        doc.preamble.use_package("xcolor");
+    }
+    if let Some(color) = &pdf_config.accent_color {
+        doc.preamble.push(PreambleElement::UserDefined(format!(
+            r"\definecolor{{{}}}{{HTML}}{{{}}}",
+            ACCENT_COLOR, color
+        )));
+    }
+    if let Some(color) = &pdf_config.link_color {
+        doc.preamble.push(PreambleElement::UserDefined(format!(
+            r"\definecolor{{{}}}{{HTML}}{{{}}}",
+            LINK_COLOR, color
+        )));
+    }
+    // Set up the footer and remove the header
+    doc.preamble.use_package("fancyhdr").use_package("hyperref");
+    if let Some(setup) = hyperref_setup(pdf_config.link_style, pdf_config.link_color.is_some()) {
+        doc.preamble.push(PreambleElement::UserDefined(format!(
+            r"\hypersetup{{{}}}",
+            setup
+        )));
+    }
+    doc.preamble
         .push(PreambleElement::UserDefined(String::from("\\fancyhf{}")))
         .push(PreambleElement::UserDefined(String::from(
             r"\pagestyle{fancy}",
@@ -372,57 +1179,198 @@ fn document_preamble(config: &Config) -> Document {
     doc
 }
 
-fn vspace() -> Document {
+/// Escape text from the resume for LaTeX, unless `raw_latex` is set.
+fn latex(config: &Config, s: &str) -> String {
+    let pdf_config = &config.format_config.pdf_config;
+    if pdf_config.raw_latex {
+        s.to_string()
+    } else {
+        escape_special_chars(s, pdf_config.engine.is_unicode())
+    }
+}
+
+/// Like `latex`, but also turns inline markup into LaTeX commands.
+fn rich_latex(config: &Config, s: &str) -> String {
+    let pdf_config = &config.format_config.pdf_config;
+    markup_to_latex(s, pdf_config.raw_latex, pdf_config.engine.is_unicode())
+}
+
+/// Turn text with inline markup into LaTeX. The text is escaped unless `raw` is set, keeping
+/// characters outside of T1 if `unicode` is set.
+pub(crate) fn markup_to_latex(s: &str, raw: bool, unicode: bool) -> String {
+    let escape =
+        |x: &str, escape: &dyn Fn(&str) -> String| if raw { x.to_string() } else { escape(x) };
+    render(
+        &parse(s),
+        &Style {
+            text: &|x| escape(x, &|x| escape_special_chars(x, unicode)),
+            bold: &|x| format!("\\textbf{{{}}}", x),
+            emphasis: &|x| format!("\\emph{{{}}}", x),
+            code: &|x| format!("\\texttt{{{}}}", x),
+            link: &|text, url| {
+                format!(
+                    "\\href{{{}}}{{{}}}",
+                    escape(&add_https_to_url(url), &escape_url),
+                    text
+                )
+            },
+        },
+    )
+}
+
+/// Escape a url for the first argument of `\href`, unless `raw_latex` is set.
+fn latex_url(config: &Config, s: &str) -> String {
+    if config.format_config.pdf_config.raw_latex {
+        s.to_string()
+    } else {
+        escape_url(s)
+    }
+}
+
+/// A list item. Text starting with '[' would be read as the item's label, so it's put in a group.
+fn item(s: &str) -> String {
+    if s.starts_with('[') {
+        format!("\\item {{}}{}", s)
+    } else {
+        format!("\\item {}", s)
+    }
+}
+
+/// The names of the colors defined from `accent_color` and `link_color`.
+pub(crate) const ACCENT_COLOR: &str = "accent";
+const LINK_COLOR: &str = "link";
+
+fn font_package(font: Font) -> &'static str {
+    match font {
+        Font::Times => "times",
+        Font::Helvet => "helvet",
+        Font::Lmodern => "lmodern",
+        Font::Palatino => "palatino",
+        Font::Charter => "charter",
+        Font::Bookman => "bookman",
+    }
+}
+
+/// The OpenType version of the font that comes with TeX Live, for fontspec. Latin Modern is
+/// fontspec's default, so it doesn't need to be set.
+fn opentype_font(font: Font) -> Option<&'static str> {
+    match font {
+        Font::Times => Some("TeX Gyre Termes"),
+        Font::Helvet => Some("TeX Gyre Heros"),
+        Font::Lmodern => None,
+        Font::Palatino => Some("TeX Gyre Pagella"),
+        Font::Charter => Some("XCharter"),
+        Font::Bookman => Some("TeX Gyre Bonum"),
+    }
+}
+
+/// The hyperref options for the link style, or None if hyperref's defaults are used.
+fn hyperref_setup(style: LinkStyle, has_color: bool) -> Option<String> {
+    match (style, has_color) {
+        (LinkStyle::Box, false) => None,
+        (LinkStyle::Box, true) => Some(format!("urlbordercolor={}", LINK_COLOR)),
+        (LinkStyle::Color, has_color) => Some(format!(
+            "colorlinks=true, urlcolor={}",
+            if has_color { LINK_COLOR } else { "blue" }
+        )),
+        (LinkStyle::Underline, false) => Some(String::from("pdfborderstyle={/S/U/W 1}")),
+        (LinkStyle::Underline, true) => Some(format!(
+            "pdfborderstyle={{/S/U/W 1}}, urlbordercolor={}",
+            LINK_COLOR
+        )),
+        (LinkStyle::Hidden, _) => Some(String::from("hidelinks")),
+    }
+}
+
+fn vspace(layout: &Layout) -> Document {
     let mut doc = Document::default();
-    doc.push(Element::UserDefined(String::from(
-        "\\vspace*{\\baselineskip}",
+    doc.push(Element::UserDefined(format!(
+        "\\vspace*{{{}}}",
+        layout.section_skip
     )));
     doc
 }
 
-fn section_header(header: &str) -> Document {
+/// A centered header in the classic theme, a header followed by a rule in the compact theme, and a
+/// larger header above a rule in the modern theme.
+fn section_header(header: &str, layout: &Layout, config: &Config) -> Document {
     let mut doc = Document::default();
-    doc.push_doc(&par_skip_start());
-    doc.push(Element::Environment(
-        String::from("center"),
-        vec![format!("{{\\bf {}}}", header)],
-    ));
-    doc.push_doc(&par_skip_end());
+    doc.push_doc(&par_skip_start(layout));
+    match config.format_config.pdf_config.theme {
+        Theme::Classic => doc.push(Element::Environment(
+            String::from("center"),
+            vec![format!("{{\\bf {}}}", accented(config, header))],
+        )),
+        Theme::Compact => doc.push(Element::UserDefined(format!(
+            "{}\n",
+            accented(config, &format!("{{\\bf {}}} \\hrulefill", header))
+        ))),
+        Theme::Modern => doc.push(Element::UserDefined(format!(
+            "{}\n",
+            accented(
+                config,
+                &format!(
+                    "{{\\large\\bf {}}}\\\\[-0.6em]\n\\rule{{\\linewidth}}{{0.4pt}}",
+                    header
+                )
+            )
+        ))),
+    };
+    doc.push_doc(&par_skip_end(layout));
     doc
 }
 
-const PAR_MOD: &str = "0.1in";
+/// Color the LaTeX with the accent color, if one is set.
+fn accented(config: &Config, s: &str) -> String {
+    match config.format_config.pdf_config.accent_color {
+        Some(_) => format!("{{\\color{{{}}} {}}}", ACCENT_COLOR, s),
+        None => s.to_string(),
+    }
+}
 
-fn par_skip_start() -> Document {
+fn par_skip_start(layout: &Layout) -> Document {
     let mut doc = Document::default();
     doc.push(Element::UserDefined(format!(
         "\\addtolength{{\\parskip}}{{ -{} }}",
-        PAR_MOD
+        layout.par_mod
     )));
     doc
 }
 
-fn par_skip_end() -> Document {
+fn par_skip_end(layout: &Layout) -> Document {
     let mut doc = Document::default();
     doc.push(Element::UserDefined(format!(
         "\\addtolength{{\\parskip}}{{ {} }}",
-        PAR_MOD
+        layout.par_mod
     )));
     doc
 }
 
 #[cfg(test)]
 mod test {
-    use crate::config::format_config::{FormatConfig, TextConfig};
+    use crate::config::format_config::{
+        Engine, Font, FormatConfig, LinkStyle, Paper, Severity, TextConfig, Theme,
+    };
     use crate::config::Config;
+    use crate::data::date::ResumeDate;
     use crate::data::{
-        CoverLetter, Education, Objective, OtherExperience, PersonalInfo, ProfessionalExperience,
-        ProjectInfo, Technologies,
+        Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+        OtherExperience, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, TaggedText,
+        Technologies,
+    };
+    use crate::error::Error;
+    use crate::renderer::pdf_renderer::{
+        build_command, document_preamble, hyperref_setup, needs_rerun, page_count,
+        pdflatex_log_excerpt, report_page_limit, run_with_timeout, section_header, BuildDir,
+        Layout, PdfRenderer, Shrink, SHRINK_STEPS,
     };
-    use crate::renderer::pdf_renderer::PdfRenderer;
     use crate::renderer::Renderer;
     use crate::util::date_string;
-    use latex::print;
+    use latex::{print, Document};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+    use std::{fs, thread};
 
     #[test]
     fn test_text_renderer() {}
@@ -443,6 +1391,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_personal_info_columns() {
+        let x = PersonalInfo {
+            email: String::from("foo@bar.com"),
+            github: String::from("github.com/foo"),
+            phone: Some(String::from("555-555-5555")),
+            location: Some(String::from("Seattle, WA")),
+            linkedin: Some(String::from("linkedin.com/in/foo")),
+            ..Default::default()
+        };
+        let mut config = get_config();
+        config.format_config.pdf_config.contact_columns = 3;
+        let rendered = PdfRenderer::new().render(&x, &config).unwrap();
+        let rendered = print(&rendered).unwrap();
+
+        assert_eq!(
+            rendered,
+            "\\documentclass{article}\n\\begin{document}\ngithub.com/foo \\hfill foo@bar.com \\hfill 555-555-5555 \\\\\nSeattle, WA \\hfill linkedin.com/in/foo\n\\rule{\\textwidth}{0.4pt}\n\\end{document}\n"
+        );
+
+        // No columns is treated as one column instead of panicking
+        config.format_config.pdf_config.contact_columns = 0;
+        let rendered = PdfRenderer::new().render(&x, &config).unwrap();
+        assert_eq!(print(&rendered).unwrap().matches(" \\\\\n").count(), 4);
+    }
+
     #[test]
     fn test_objective() {
         let x = Objective {
@@ -454,31 +1428,46 @@ mod test {
         assert_eq!(rendered, "\\documentclass{article}\n\\begin{document}\nLorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n\\end{document}\n");
     }
 
+    #[test]
+    fn test_markup() {
+        let x = Objective {
+            objective: String::from(
+                "Cut **build times** by *half* with `sccache` and [more](example.com/a_b)",
+            ),
+        };
+        let rendered = PdfRenderer::new().render(&x, &get_config()).unwrap();
+        let rendered = print(&rendered).unwrap();
+
+        assert_eq!(rendered, "\\documentclass{article}\n\\begin{document}\nCut \\textbf{build times} by \\emph{half} with \\texttt{sccache} and \\href{https://example.com/a_b}{more}\n\\end{document}\n");
+    }
+
     #[test]
     fn test_professional_experience() {
         let a = ProfessionalExperience {
             organization: Some(String::from("organizationA")),
             position: String::from("positionA"),
             location: Some(String::from("locationA")),
-            start: String::from("startA"),
-            end: String::from("endA"),
+            start: ResumeDate::new(2020, 1).unwrap(),
+            end: ResumeDate::Present,
             experience: vec![
-                String::from("experienceA1"),
-                String::from("experienceA2"),
-                String::from("experienceA3"),
+                TaggedText::from("experienceA1"),
+                TaggedText::from("experienceA2"),
+                TaggedText::from("experienceA3"),
             ],
+            tags: vec![],
         };
         let b = ProfessionalExperience {
                 organization: Some(String::from("organizationB")),
                 position: String::from("positionB"),
                 location: Some(String::from("locationB")),
-                start: String::from("startB"),
-                end: String::from("endB"),
+                start: ResumeDate::new(2018, 1).unwrap(),
+                end: ResumeDate::new(2019, 12).unwrap(),
                 experience: vec![
-                    String::from("experienceB1"),
-                    String::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
-                    String::from("experienceB3"),
+                    TaggedText::from("experienceB1"),
+                    TaggedText::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
+                    TaggedText::from("experienceB3"),
                 ],
+                tags: vec![],
             };
         let x = vec![a, b];
 
@@ -487,7 +1476,7 @@ mod test {
 
         assert_eq!(
             rendered,
-            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf EXPERIENCE}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf organizationA} \\hfill locationA\n\n\\emph{positionA} \\hfill startA - endA\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item experienceA1\n\\item experienceA2\n\\item experienceA3\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf organizationB} \\hfill locationB\n\n\\emph{positionB} \\hfill startB - endB\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item experienceB1\n\\item Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n\\item experienceB3\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n\\end{document}\n"
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf EXPERIENCE}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf organizationA} \\hfill locationA\n\n\\emph{positionA} \\hfill Jan 2020 - Present\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item experienceA1\n\\item experienceA2\n\\item experienceA3\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf organizationB} \\hfill locationB\n\n\\emph{positionB} \\hfill Jan 2018 - Dec 2019\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item experienceB1\n\\item Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n\\item experienceB3\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n\\end{document}\n"
         );
     }
 
@@ -503,7 +1492,7 @@ mod test {
         let b = ProjectInfo {
             project_name: String::from("project_nameB"),
             description: String::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
-            url: String::from("example.com"),
+            url: String::new(),
             include_on_resume: true,
             ..Default::default()
         };
@@ -523,26 +1512,26 @@ mod test {
 
         assert_eq!(
             rendered,
-            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf PROJECTS}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item \\href{example.com}{project_nameA}: descriptionA\n\n\\item \\href{example.com}{project_nameB}: Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n\n\\end{itemize}\n\\end{document}\n"
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf PROJECTS}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item \\href{example.com}{project\\_nameA}: descriptionA\n\n\\item project\\_nameB: Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut\n\n\\end{itemize}\n\\end{document}\n"
         );
     }
 
     #[test]
     fn test_technologies() {
         let tech = vec![
-            String::from("Lorem"),
-            String::from("ipsum"),
-            String::from("dolor"),
-            String::from("sit"),
-            String::from("amet"),
-            String::from("consectetur"),
-            String::from("adipiscing"),
-            String::from("elit"),
-            String::from("sed"),
-            String::from("do"),
-            String::from("eiusmod"),
-            String::from("tempor"),
-            String::from("incididunt"),
+            TaggedText::from("Lorem"),
+            TaggedText::from("ipsum"),
+            TaggedText::from("dolor"),
+            TaggedText::from("sit"),
+            TaggedText::from("amet"),
+            TaggedText::from("consectetur"),
+            TaggedText::from("adipiscing"),
+            TaggedText::from("elit"),
+            TaggedText::from("sed"),
+            TaggedText::from("do"),
+            TaggedText::from("eiusmod"),
+            TaggedText::from("tempor"),
+            TaggedText::from("incididunt"),
         ];
 
         let x = Technologies { technologies: tech };
@@ -558,12 +1547,83 @@ mod test {
 
     #[test]
     fn test_education() {
-        let x = Education {
-            school: String::from("school"),
-            location: String::from("location"),
-            major: String::from("major"),
-            graduation: Some(String::from("graduation")),
-            ..Default::default()
+        let x = vec![
+            Education {
+                school: String::from("school"),
+                location: String::from("location"),
+                degree: Some(String::from("B.S.")),
+                major: String::from("major"),
+                minor: Some(String::from("minor")),
+                graduation: ResumeDate::new(2017, 6),
+                gpa: Some(String::from("3.8")),
+                honors: vec![String::from("Cum Laude")],
+                coursework: vec![TaggedText::from("Course A"), TaggedText::from("Course B")],
+                extras: Some(vec![TaggedText::from("extra")]),
+                ..Default::default()
+            },
+            Education {
+                school: String::from("other school"),
+                location: String::from("other location"),
+                major: String::from("other major"),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = PdfRenderer::new().render(&x, &get_config()).unwrap();
+        let rendered = print(&rendered).unwrap();
+
+        assert_eq!(
+            rendered,
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf EDUCATION}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf school} \\hfill location\n\n\\emph{B.S. in major, minor in minor} \\hfill Jun 2017\n\nGPA: 3.8, Cum Laude\n\nRelevant coursework: Course A, Course B\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item extra\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf other school} \\hfill other location\n\n\\emph{other major} \\hfill \n\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn test_certifications() {
+        let x = vec![
+            Certification {
+                name: String::from("name"),
+                issuer: String::from("issuer"),
+                issued: ResumeDate::new(2021, 3).unwrap(),
+                expires: ResumeDate::new(2024, 3),
+                credential_id: Some(String::from("ABC-123")),
+                url: Some(String::from("example.com/verify")),
+                ..Default::default()
+            },
+            Certification {
+                name: String::from("other name"),
+                issued: ResumeDate::new(2019, 1).unwrap(),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = PdfRenderer::new().render(&x, &get_config()).unwrap();
+        let rendered = print(&rendered).unwrap();
+
+        assert_eq!(
+            rendered,
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf CERTIFICATIONS}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf \\href{https://example.com/verify}{name}} \\hfill Mar 2021 - Mar 2024\n\n\\emph{issuer} \\hfill Credential ID: ABC-123\n\n{\\bf other name} \\hfill Jan 2019\n\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_section() {
+        let x = CustomSection {
+            title: String::from("Awards"),
+            items: vec![
+                CustomSectionItem {
+                    title: Some(String::from("title")),
+                    location: Some(String::from("location")),
+                    subtitle: Some(String::from("subtitle")),
+                    date: Some(String::from("2021")),
+                    bullets: vec![TaggedText::from("bullet")],
+                    ..Default::default()
+                },
+                CustomSectionItem {
+                    title: Some(String::from("other title")),
+                    ..Default::default()
+                },
+            ],
         };
 
         let rendered = PdfRenderer::new().render(&x, &get_config()).unwrap();
@@ -571,7 +1631,7 @@ mod test {
 
         assert_eq!(
             rendered,
-            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf UNIVERSITY}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n\\begin{center}\nschool -- location -- major -- graduation\n\\end{center}\n\\end{document}\n"
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf AWARDS}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf title} \\hfill location\n\n\\emph{subtitle} \\hfill 2021\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item bullet\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n{\\bf other title} \\hfill \n\n\\end{document}\n"
         );
     }
 
@@ -591,16 +1651,393 @@ mod test {
         let rendered = PdfRenderer::new().render(&x, &get_config()).unwrap();
         let rendered = print(&rendered).unwrap();
 
-        let expected_prefix = "\\documentclass{article}\n\\usepackage[margin=0.75in]{geometry}\n\\usepackage{times}\n\\usepackage{fancyhdr}\n\\usepackage{hyperref}\n\\fancyhf{}\n\\pagestyle{fancy}\n\\renewcommand{\\headrulewidth}{0pt}\n\\begin{document}\n\\setlength\\parindent{0pt}\nFoo Bar\n\nfoo@bar.com\n\n";
+        let expected_prefix = "\\documentclass{article}\n\\usepackage[margin=0.75in,letterpaper]{geometry}\n\\usepackage[T1]{fontenc}\n\\usepackage[utf8]{inputenc}\n\\usepackage{times}\n\\usepackage{fancyhdr}\n\\usepackage{hyperref}\n\\fancyhf{}\n\\pagestyle{fancy}\n\\renewcommand{\\headrulewidth}{0pt}\n\\begin{document}\n\\setlength\\parindent{0pt}\nFoo Bar\n\nfoo@bar.com\n\n";
         let expected_suffix = "\n\n\\setlength\\parskip{2em}\nHello,\n\n\\setlength\\parskip{1em}\nfoo\n\nbar\n\nbaz\n\n\\setlength\\parskip{2em}\nFrom,\n\n\\setlength\\parskip{0em}\nFoo Bar\n\n\\end{document}\n";
         let expected = format!("{}{}{}", expected_prefix, date_string(), expected_suffix);
         assert_eq!(rendered, expected);
     }
 
+    #[test]
+    fn test_pdflatex_log_excerpt() {
+        let log = "This is pdfTeX\n(./foo.tex\n! Undefined control sequence.\nl.5 \\foo\n\n? \n! Emergency stop.";
+        assert_eq!(
+            pdflatex_log_excerpt(log),
+            "! Undefined control sequence.\nl.5 \\foo\n\n? \n! Emergency stop."
+        );
+
+        let log = (1..=20)
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert_eq!(
+            pdflatex_log_excerpt(&log),
+            "11\n12\n13\n14\n15\n16\n17\n18\n19\n20"
+        );
+    }
+
+    #[test]
+    fn test_escaping() {
+        let x = ProfessionalExperience {
+            organization: Some(String::from("R&D Labs")),
+            location: Some(String::from("~Remote")),
+            position: String::from("C# Developer"),
+            start: ResumeDate::new(2020, 1).unwrap(),
+            end: ResumeDate::Present,
+            experience: vec![
+                TaggedText::from("Cut costs by 50%"),
+                TaggedText::from("[Confidential] project"),
+            ],
+            tags: vec![],
+        };
+        let mut config = get_config();
+        let rendered: Document = PdfRenderer::new().render(&x, &config).unwrap();
+        assert_eq!(
+            print(&rendered).unwrap(),
+            "\\documentclass{article}\n\\begin{document}\n{\\bf R\\&D Labs} \\hfill \\textasciitilde{}Remote\n\n\\emph{C\\# Developer} \\hfill Jan 2020 - Present\n\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{itemize}\n\\setlength\\itemsep{-0.05in}\n\\item Cut costs by 50\\%\n\\item {}[Confidential] project\n\\end{itemize}\n\\addtolength{\\parskip}{ 0.1in }\n\\end{document}\n"
+        );
+
+        let x = ProjectInfo {
+            project_name: String::from("snake_case"),
+            description: String::from("\\textbf{bold}"),
+            url: String::from("example.com/a%20b#readme"),
+            ..Default::default()
+        };
+        let rendered: String = PdfRenderer::new().render(&x, &config).unwrap();
+        assert_eq!(
+            rendered,
+            "\\item \\href{example.com/a\\%20b\\#readme}{snake\\_case}: \\textbackslash{}textbf\\{bold\\}\n"
+        );
+
+        config.format_config.pdf_config.raw_latex = true;
+        let rendered: String = PdfRenderer::new().render(&x, &config).unwrap();
+        assert_eq!(
+            rendered,
+            "\\item \\href{example.com/a%20b#readme}{snake_case}: \\textbf{bold}\n"
+        );
+    }
+
+    #[test]
+    fn test_typography() {
+        let mut config = get_config();
+        let pdf_config = &mut config.format_config.pdf_config;
+        pdf_config.font = Font::Helvet;
+        pdf_config.font_size = Some(String::from("11pt"));
+        pdf_config.paper = Paper::A4;
+        pdf_config.accent_color = Some(String::from("1F4E79"));
+        pdf_config.link_color = Some(String::from("0000AA"));
+        pdf_config.link_style = LinkStyle::Color;
+
+        let doc = document_preamble(&config, &Layout::default());
+        assert_eq!(
+            print(&doc).unwrap(),
+            "\\documentclass{article}\n\\usepackage[margin=0.75in,a4paper]{geometry}\n\\usepackage[T1]{fontenc}\n\\usepackage[utf8]{inputenc}\n\\usepackage{helvet}\n\\renewcommand{\\familydefault}{\\sfdefault}\n\\usepackage[fontsize=11pt]{scrextend}\n\\usepackage{xcolor}\n\\definecolor{accent}{HTML}{1F4E79}\n\\definecolor{link}{HTML}{0000AA}\n\\usepackage{fancyhdr}\n\\usepackage{hyperref}\n\\hypersetup{colorlinks=true, urlcolor=link}\n\\fancyhf{}\n\\pagestyle{fancy}\n\\renewcommand{\\headrulewidth}{0pt}\n\\begin{document}\n\\end{document}\n"
+        );
+
+        // xelatex and lualatex load the OpenType fonts with fontspec
+        config.format_config.pdf_config.engine = Engine::Xelatex;
+        let doc = document_preamble(&config, &Layout::default());
+        assert!(print(&doc).unwrap().starts_with(
+            "\\documentclass{article}\n\\usepackage[margin=0.75in,a4paper]{geometry}\n\\usepackage{fontspec}\n\\setsansfont{TeX Gyre Heros}\n\\renewcommand{\\familydefault}{\\sfdefault}\n"
+        ));
+        config.format_config.pdf_config.engine = Engine::Pdflatex;
+
+        let doc = section_header("EXPERIENCE", &Layout::default(), &config);
+        assert_eq!(
+            print(&doc).unwrap(),
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n\\begin{center}\n{\\bf {\\color{accent} EXPERIENCE}}\n\\end{center}\n\\addtolength{\\parskip}{ 0.1in }\n\\end{document}\n"
+        );
+
+        assert_eq!(hyperref_setup(LinkStyle::Box, false), None);
+        assert_eq!(
+            hyperref_setup(LinkStyle::Color, false).unwrap(),
+            "colorlinks=true, urlcolor=blue"
+        );
+        assert_eq!(
+            hyperref_setup(LinkStyle::Underline, true).unwrap(),
+            "pdfborderstyle={/S/U/W 1}, urlbordercolor=link"
+        );
+        assert_eq!(
+            hyperref_setup(LinkStyle::Hidden, true).unwrap(),
+            "hidelinks"
+        );
+    }
+
+    #[test]
+    fn test_needs_rerun() {
+        assert!(needs_rerun(
+            "Package rerunfilecheck Warning: File `resume.out' has changed.\n(rerunfilecheck)                Rerun to get outlines right"
+        ));
+        assert!(needs_rerun(
+            "LaTeX Warning: Label(s) may have changed. Rerun to get cross-references right."
+        ));
+        assert!(needs_rerun("LaTeX Warning: Label(s) may have chan\nged."));
+        assert!(!needs_rerun(
+            "Output written on resume.pdf (1 page, 23456 bytes)."
+        ));
+    }
+
+    #[test]
+    fn test_build_command() {
+        let mut config = get_config();
+        let command = build_command(Path::new("build/resume.tex"), Path::new("build"), &config);
+        assert_eq!(command.get_program(), "pdflatex");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![
+                "-interaction=nonstopmode",
+                "-output-directory=build",
+                "build/resume.tex"
+            ]
+        );
+
+        config.format_config.pdf_config.engine = Engine::Latexmk;
+        config.format_config.pdf_config.engine_args = vec![String::from("-silent")];
+        let command = build_command(Path::new("build/resume.tex"), Path::new("build"), &config);
+        assert_eq!(command.get_program(), "latexmk");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![
+                "-pdf",
+                "-g",
+                "-interaction=nonstopmode",
+                "-output-directory=build",
+                "-silent",
+                "build/resume.tex"
+            ]
+        );
+
+        // The native engine runs LaTeX templates with pdflatex
+        config.format_config.pdf_config.engine = Engine::Native;
+        let command = build_command(Path::new("resume.tex"), Path::new("."), &config);
+        assert_eq!(command.get_program(), "pdflatex");
+    }
+
+    #[test]
+    fn test_build_dir() {
+        let mut config = get_config();
+        let a = BuildDir::new(&config).unwrap();
+        let b = BuildDir::new(&config).unwrap();
+        assert_ne!(a.path(), b.path());
+        assert!(a.path().is_dir());
+        let path = a.path().to_path_buf();
+        drop(a);
+        assert!(!path.exists());
+
+        config.format_config.pdf_config.build_dir = Some(String::from("build"));
+        assert_eq!(BuildDir::new(&config).unwrap().path(), Path::new("build"));
+    }
+
+    #[test]
+    fn test_keep_failed_build() {
+        let config = get_config();
+        let build_dir = BuildDir::new(&config).unwrap();
+        let tex_path = build_dir.path().join("resume.tex");
+        fs::write(&tex_path, "\\documentclass{article}").unwrap();
+        fs::write(
+            tex_path.with_extension("log"),
+            "! Undefined control sequence.",
+        )
+        .unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        let pdf_path = output_dir.path().join("resume.pdf");
+        let e = Error::Latex {
+            command: String::from("pdflatex"),
+            path: tex_path,
+            source: None,
+            log: None,
+        };
+
+        let e = build_dir.keep_failed(e, &build_dir.path().join("resume.tex"), &pdf_path);
+        let kept = output_dir.path().join("resume.tex");
+        assert!(matches!(&e, Error::Latex { path, .. } if path == &kept));
+        assert!(kept.exists());
+        assert!(kept.with_extension("log").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_timeout() {
+        let mut command = Command::new("echo");
+        command.arg("foo").stdout(std::process::Stdio::piped());
+        let (status, output) = run_with_timeout(&mut command, Duration::from_secs(10))
+            .unwrap()
+            .unwrap();
+        assert!(status.success());
+        assert_eq!(output, "foo\n");
+
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        assert!(run_with_timeout(&mut command, Duration::from_millis(100))
+            .unwrap()
+            .is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_run_with_timeout_kills_process_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_path = dir.path().join("pid");
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("sleep 10 & echo $! > {}; wait", pid_path.display()));
+        let start = Instant::now();
+        assert!(run_with_timeout(&mut command, Duration::from_millis(200))
+            .unwrap()
+            .is_none());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // The shell's own child is killed too, and is either gone or waiting to be reaped
+        let pid = fs::read_to_string(&pid_path).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        let state = fs::read_to_string(format!("/proc/{}/stat", pid.trim())).unwrap_or_default();
+        assert!(state.is_empty() || state.contains(") Z "), "{}", state);
+    }
+
+    #[test]
+    fn test_page_count() {
+        let log = "[1] [2] )\nOutput written on resume.pdf (2 pages, 34567 bytes).\nTranscript written on resume.log.";
+        assert_eq!(page_count(log), Some(2));
+
+        let log = "Output written on /a/very/long/output/directory/for/the/generated/files/resume.pd\nf (1 page, 23456 bytes).";
+        assert_eq!(page_count(log), Some(1));
+
+        assert_eq!(page_count("No pages of output."), None);
+    }
+
+    #[test]
+    fn test_report_page_limit() {
+        let mut config = get_config();
+        let path = PathBuf::from("resume.pdf");
+        let adjustments = vec![String::from("used a smaller font")];
+
+        let (_, notes) =
+            report_page_limit(&config, path.clone(), Some(1), 1, adjustments.clone()).unwrap();
+        assert_eq!(
+            notes,
+            vec!["Shrunk the resume to fit on 1 page(s): used a smaller font."]
+        );
+        assert!(report_page_limit(&config, path.clone(), Some(2), 1, vec![]).is_err());
+
+        config.format_config.pdf_config.page_limit = Severity::Warning;
+        let (_, notes) = report_page_limit(&config, path.clone(), Some(2), 1, adjustments).unwrap();
+        assert!(notes[0].starts_with("Warning: "));
+        assert!(notes[0].ends_with("even after shrinking it (used a smaller font)."));
+
+        config.format_config.pdf_config.page_limit = Severity::Off;
+        let (_, notes) = report_page_limit(&config, path, Some(2), 1, vec![]).unwrap();
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn test_shrink() {
+        let mut layout = Layout::default();
+        assert!(!Shrink::Margin.apply(&mut layout, "0.5in"));
+        assert!(!Shrink::Margin.apply(&mut layout, "1cm"));
+        assert!(!Shrink::Margin.apply(&mut layout, "8mm"));
+        assert!(!Shrink::Margin.apply(&mut layout, "3em"));
+        assert!(layout.margin.is_none());
+        assert!(Shrink::Margin.apply(&mut layout, "2cm"));
+        layout.margin = None;
+        for step in SHRINK_STEPS {
+            assert!(step.apply(&mut layout, "0.75in"));
+        }
+        assert_eq!(
+            layout,
+            Layout {
+                margin: Some(String::from("0.5in")),
+                par_mod: "0.15in",
+                itemsep: "-0.1in",
+                font_size: Some("small"),
+                section_skip: "\\baselineskip",
+            }
+        );
+
+        let mut compact = Layout::new(Theme::Compact);
+        assert!(!Shrink::Spacing.apply(&mut compact, "0.75in"));
+        assert!(Shrink::FontSize.apply(&mut compact, "0.75in"));
+        assert_eq!(compact.font_size, Some("footnotesize"));
+
+        let renderer = PdfRenderer {
+            layout: Some(layout),
+        };
+        let x = Technologies {
+            technologies: vec![TaggedText::from("Rust")],
+        };
+        let rendered: Document = renderer.render(&x, &get_config()).unwrap();
+        assert_eq!(
+            print(&rendered).unwrap(),
+            "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.15in }\n\\begin{center}\n{\\bf TECHNOLOGIES}\n\\end{center}\n\\addtolength{\\parskip}{ 0.15in }\n\\begin{center}\nRust\n\\end{center}\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn test_themes() {
+        let mut config = get_config();
+        config.format_config.pdf_config.theme = Theme::Compact;
+        let doc = section_header("EXPERIENCE", &Layout::new(Theme::Compact), &config);
+        assert_eq!(print(&doc).unwrap(), "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.15in }\n{\\bf EXPERIENCE} \\hrulefill\n\n\\addtolength{\\parskip}{ 0.15in }\n\\end{document}\n");
+
+        config.format_config.pdf_config.theme = Theme::Modern;
+        let doc = section_header("EXPERIENCE", &Layout::new(Theme::Modern), &config);
+        assert_eq!(print(&doc).unwrap(), "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n{\\large\\bf EXPERIENCE}\\\\[-0.6em]\n\\rule{\\linewidth}{0.4pt}\n\n\\addtolength{\\parskip}{ 0.1in }\n\\end{document}\n");
+
+        let x = PersonalInfo {
+            email: String::from("foo@bar.com"),
+            github: String::from("github.com/foo"),
+            ..Default::default()
+        };
+        let rendered: Document = PdfRenderer::new().render(&x, &config).unwrap();
+        assert_eq!(print(&rendered).unwrap(), "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n{\\large\\bf CONTACT}\\\\[-0.6em]\n\\rule{\\linewidth}{0.4pt}\n\n\\addtolength{\\parskip}{ 0.1in }\ngithub.com/foo \\\\\nfoo@bar.com\n\\end{document}\n");
+
+        let x = Technologies {
+            technologies: vec![TaggedText::from("C#"), TaggedText::from("Rust")],
+        };
+        let rendered: Document = PdfRenderer::new().render(&x, &config).unwrap();
+        assert_eq!(print(&rendered).unwrap(), "\\documentclass{article}\n\\begin{document}\n\\addtolength{\\parskip}{ -0.1in }\n{\\large\\bf TECHNOLOGIES}\\\\[-0.6em]\n\\rule{\\linewidth}{0.4pt}\n\n\\addtolength{\\parskip}{ 0.1in }\nC\\# \\\\\nRust\n\\end{document}\n");
+    }
+
+    #[test]
+    fn test_modern_sidebar() {
+        let x = Resume {
+            name: String::from("Foo Bar"),
+            personal_info: PersonalInfo {
+                email: String::from("foo@bar.com"),
+                ..Default::default()
+            },
+            professional_experience: vec![ProfessionalExperience {
+                position: String::from("position"),
+                start: ResumeDate::new(2020, 1).unwrap(),
+                end: ResumeDate::Present,
+                experience: vec![TaggedText::from("experience")],
+                ..Default::default()
+            }],
+            technologies: Some(Technologies {
+                technologies: vec![TaggedText::from("Rust")],
+            }),
+            ..Default::default()
+        };
+        let mut config = get_config();
+        config.format_config.pdf_config.theme = Theme::Modern;
+        let rendered: String = PdfRenderer::new().render(&x, &config).unwrap();
+
+        assert!(rendered.contains("\\usepackage{paracol}\n\\begin{document}"));
+        let position = |s: &str| rendered.find(s).unwrap();
+        assert!(position("\\begin{paracol}{2}") < position("CONTACT"));
+        assert!(position("CONTACT") < position("TECHNOLOGIES"));
+        assert!(position("TECHNOLOGIES") < position("\\switchcolumn"));
+        assert!(position("\\switchcolumn") < position("EXPERIENCE"));
+        assert!(position("EXPERIENCE") < position("\\end{paracol}"));
+        assert!(!rendered.contains("flushleft"));
+    }
+
     fn get_config() -> Config {
         Config {
             format_config: FormatConfig {
-                text_config: TextConfig { width: 50 },
+                text_config: TextConfig {
+                    width: 50,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             ..Default::default()
@@ -0,0 +1,522 @@
+//! Renders the resume through a template, so the layout can be changed without touching Rust. The
+//! templates use the Jinja syntax of [minijinja]; the resume's fields are the template's context.
+//!
+//! Each template stands in for one of the built-in formats, picked by its extension: a .tex
+//! template for the pdf, .md for markdown, .html for html and anything else for text. The template
+//! follows that format's config, and the cover letter is rendered by that format's renderer.
+use crate::config::arguments::OutputFormat;
+use crate::config::format_config::{ContactField, DateConfig, Section};
+use crate::config::Config;
+use crate::data::date::{validate_date_format, ResumeDate};
+use crate::data::markup::plain_text;
+use crate::data::{ContactItem, CoverLetter, Resume};
+use crate::error::Error;
+use crate::renderer::html_renderer::{html, HtmlRenderer};
+use crate::renderer::pdf_renderer::{
+    markup_to_latex, render_tex_and_pdf, PdfRenderer, TemplateLatex, ACCENT_COLOR,
+};
+use crate::renderer::text_renderer::TextRenderer;
+use crate::renderer::{html_renderer, markdown_renderer, pdf_renderer, text_renderer, Renderer};
+use crate::util::{
+    add_https_to_url, cover_letter_file_name, date_string, escape_url, get_path,
+    split_string_across_lines, time_range_string, write_string_to_file, write_string_to_path,
+};
+use minijinja::{AutoEscape, Environment, ErrorKind, UndefinedBehavior};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The built-in templates: their name, the extension of their output, and their source. They
+/// follow the layouts of the built-in formats, and are meant as a starting point for new templates.
+const BUILTIN_TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "text",
+        "txt",
+        include_str!("../../templates/resume.txt.jinja"),
+    ),
+    (
+        "markdown",
+        "md",
+        include_str!("../../templates/resume.md.jinja"),
+    ),
+    (
+        "latex",
+        "tex",
+        include_str!("../../templates/resume.tex.jinja"),
+    ),
+    (
+        "html",
+        "html",
+        include_str!("../../templates/resume.html.jinja"),
+    ),
+];
+
+/// The extensions that mark a file as a template, e.g. `resume.tex.jinja`.
+const TEMPLATE_EXTENSIONS: &[&str] = &["jinja", "jinja2", "j2"];
+
+pub struct TemplateRenderer {
+    template: String,
+}
+
+impl TemplateRenderer {
+    /// `template` is the path of a template file, or the name of a built-in template.
+    pub fn new(template: &str) -> TemplateRenderer {
+        TemplateRenderer {
+            template: template.to_owned(),
+        }
+    }
+
+    /// The source of the template and the extension of the file it renders to. The extension is
+    /// taken from the file name without the template extension, e.g. "tex" for
+    /// `resume.tex.jinja`, and defaults to "txt".
+    fn source(&self) -> Result<(String, String), Error> {
+        let path = Path::new(&self.template);
+        if !path.exists() {
+            if let Some((_, extension, source)) = BUILTIN_TEMPLATES
+                .iter()
+                .find(|(name, _, _)| *name == self.template)
+            {
+                return Ok((source.to_string(), extension.to_string()));
+            }
+        }
+        let source = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        let file_name = path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .unwrap_or_default();
+        let file_name = TEMPLATE_EXTENSIONS
+            .iter()
+            .find_map(|x| file_name.strip_suffix(&format!(".{}", x)))
+            .unwrap_or(file_name);
+        let extension = Path::new(file_name)
+            .extension()
+            .and_then(|x| x.to_str())
+            .unwrap_or("txt");
+        Ok((source, extension.to_owned()))
+    }
+}
+
+impl Renderer<Resume, PathBuf> for TemplateRenderer {
+    /// Render the template to a file. A LaTeX template is then compiled to a pdf.
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
+        let (source, extension) = self.source()?;
+        let format = template_format(&extension);
+        let rendered = render_template(&self.template, &source, element, config, format)?;
+        let path = if extension == "tex" {
+            render_tex_and_pdf(&rendered, config.args.output_name(), config)?.0
+        } else {
+            let path = get_path(
+                config.args.output_dir.as_ref(),
+                config.args.output_name(),
+                Some(&extension),
+            );
+            write_string_to_path(&rendered, &path)?
+        };
+        if let Some(c) = &element.cover_letter {
+            render_cover_letter(c, format, config)?;
+        }
+        Ok(path)
+    }
+}
+
+/// The built-in format that a template with this extension stands in for.
+fn template_format(extension: &str) -> OutputFormat {
+    match extension {
+        "tex" => OutputFormat::Pdf,
+        "md" => OutputFormat::Markdown,
+        "html" => OutputFormat::Html,
+        _ => OutputFormat::Text,
+    }
+}
+
+/// Render the cover letter with the built-in renderer of the template's format. Markdown doesn't
+/// have a cover letter, so it gets the text one.
+fn render_cover_letter(
+    cover_letter: &CoverLetter,
+    format: OutputFormat,
+    config: &Config,
+) -> Result<(), Error> {
+    let file_name = cover_letter_file_name(config);
+    match format {
+        OutputFormat::Pdf => {
+            let s: String = PdfRenderer::new().render(cover_letter, config)?;
+            render_tex_and_pdf(&s, &file_name, config)?;
+        }
+        OutputFormat::Html => {
+            let s: String = HtmlRenderer::new().render(cover_letter, config)?;
+            write_string_to_file(
+                &s,
+                config.args.output_dir.as_ref(),
+                &file_name,
+                Some(&String::from("html")),
+            )?;
+        }
+        _ => {
+            let s: String = TextRenderer::new().render(cover_letter, config)?;
+            write_string_to_file(
+                &s,
+                config.args.output_dir.as_ref(),
+                &file_name,
+                Some(&String::from("txt")),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Render the template with the resume as its context, following the config of `format`.
+pub fn render_template(
+    name: &str,
+    source: &str,
+    resume: &Resume,
+    config: &Config,
+    format: OutputFormat,
+) -> Result<String, Error> {
+    let template_error = |source| Error::Template {
+        path: PathBuf::from(name),
+        source,
+    };
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    // Printing a misspelled field fails instead of printing nothing
+    env.set_undefined_behavior(UndefinedBehavior::SemiStrict);
+    // The filters escape for each format
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    let (sections, contact, dates) = format_content(config, format);
+    add_filters(&mut env, config, dates);
+
+    let template = env
+        .template_from_named_str(name, source)
+        .map_err(template_error)?;
+    let context = context(resume, config, format, &sections, &contact)?;
+    template.render(context).map_err(template_error)
+}
+
+/// The sections, contact fields and date options of the format, resolved the same way as the
+/// format's renderer resolves them.
+fn format_content(
+    config: &Config,
+    format: OutputFormat,
+) -> (Vec<Section>, Vec<ContactField>, &DateConfig) {
+    let format_config = &config.format_config;
+    let (content, dates, sections, contact) = match format {
+        OutputFormat::Pdf => (
+            &format_config.pdf_config.content,
+            &format_config.pdf_config.dates,
+            pdf_renderer::SECTIONS,
+            pdf_renderer::CONTACT,
+        ),
+        OutputFormat::Markdown => (
+            &format_config.markdown_config.content,
+            &format_config.markdown_config.dates,
+            markdown_renderer::SECTIONS,
+            markdown_renderer::CONTACT,
+        ),
+        OutputFormat::Html => (
+            &format_config.html_config.content,
+            &format_config.html_config.dates,
+            html_renderer::SECTIONS,
+            html_renderer::CONTACT,
+        ),
+        _ => (
+            &format_config.text_config.content,
+            &format_config.text_config.dates,
+            text_renderer::SECTIONS,
+            text_renderer::CONTACT,
+        ),
+    };
+    (
+        format_config.sections(&content.sections, sections),
+        format_config.contact(&content.contact, contact),
+        dates,
+    )
+}
+
+/// The resume as the template's context. Tagged text is replaced by its text, since the tags have
+/// already been applied. On top of the resume's fields there are:
+/// - `today`: the render date
+/// - `format_config`: the whole format config
+/// - `sections`: the names of the sections to render, in order
+/// - `contact`: the contact details to show, in order, each with a `label`, `text`, `link` and
+///   `needs_label`
+/// - `latex`: for LaTeX templates, the `preamble` and the theme's spacing (`par_mod`, `itemsep`,
+///   `font_size` and `section_skip`)
+fn context(
+    resume: &Resume,
+    config: &Config,
+    format: OutputFormat,
+    sections: &[Section],
+    contact: &[ContactField],
+) -> Result<Value, Error> {
+    let mut context = to_value(resume)?;
+    untag(&mut context);
+    if let Value::Object(x) = &mut context {
+        x.insert(String::from("today"), Value::String(date_string()));
+        x.insert(
+            String::from("format_config"),
+            to_value(&config.format_config)?,
+        );
+        x.insert(String::from("sections"), to_value(&sections)?);
+        let contact = resume
+            .personal_info
+            .contact_items(contact)
+            .iter()
+            .map(contact_value)
+            .collect();
+        x.insert(String::from("contact"), Value::Array(contact));
+        if format == OutputFormat::Pdf {
+            x.insert(
+                String::from("latex"),
+                to_value(&TemplateLatex::new(config)?)?,
+            );
+        }
+    }
+    Ok(context)
+}
+
+fn to_value<T: Serialize>(x: &T) -> Result<Value, Error> {
+    serde_json::to_value(x).map_err(|source| Error::Json { path: None, source })
+}
+
+fn contact_value(item: &ContactItem) -> Value {
+    json!({
+        "label": item.label,
+        "text": item.text,
+        "link": item.link,
+        "needs_label": item.needs_label(),
+    })
+}
+
+fn untag(value: &mut Value) {
+    match value {
+        Value::Object(x) if x.len() == 2 && x.contains_key("tags") => {
+            if let Some(Value::String(text)) = x.get("text") {
+                *value = Value::String(text.clone());
+            }
+        }
+        Value::Object(x) => x.values_mut().for_each(untag),
+        Value::Array(x) => x.iter_mut().for_each(untag),
+        _ => {}
+    }
+}
+
+/// Add the filters. `date` and `time_range` default to the format's date options.
+fn add_filters(env: &mut Environment, config: &Config, dates: &DateConfig) {
+    let raw_latex = config.format_config.pdf_config.raw_latex;
+    let unicode = config.format_config.pdf_config.engine.is_unicode();
+    env.add_filter("latex", move |s: String| {
+        markup_to_latex(&s, raw_latex, unicode)
+    });
+    env.add_filter(
+        "latex_url",
+        move |s: String| {
+            if raw_latex {
+                s
+            } else {
+                escape_url(&s)
+            }
+        },
+    );
+    env.add_filter("url", |s: String| add_https_to_url(&s));
+    env.add_filter("html", |s: String| html(&s));
+    env.add_filter("plain", |s: String| plain_text(&s));
+    env.add_filter(
+        "wrap",
+        |s: String,
+         width: usize,
+         first_line_prefix: Option<String>,
+         line_prefix: Option<String>| {
+            split_string_across_lines(&s, width, first_line_prefix, line_prefix)
+        },
+    );
+    let default_format = dates.date_format.clone();
+    env.add_filter(
+        "date",
+        move |date: String, format: Option<String>| -> Result<String, minijinja::Error> {
+            let format = date_format(format.unwrap_or_else(|| default_format.clone()))?;
+            Ok(parse_date(&date)?.format(&format))
+        },
+    );
+    let default_format = dates.date_format.clone();
+    let default_show_duration = dates.show_duration;
+    env.add_filter(
+        "time_range",
+        move |start: String,
+              end: String,
+              format: Option<String>,
+              show_duration: Option<bool>|
+              -> Result<String, minijinja::Error> {
+            Ok(time_range_string(
+                &parse_date(&start)?,
+                &parse_date(&end)?,
+                &date_format(format.unwrap_or_else(|| default_format.clone()))?,
+                show_duration.unwrap_or(default_show_duration),
+            ))
+        },
+    );
+    let accent = config.format_config.pdf_config.accent_color.is_some();
+    env.add_filter("accent", move |s: String| {
+        if accent {
+            format!("{{\\color{{{}}} {}}}", ACCENT_COLOR, s)
+        } else {
+            s
+        }
+    });
+}
+
+fn parse_date(date: &str) -> Result<ResumeDate, minijinja::Error> {
+    ResumeDate::parse(date).ok_or_else(|| {
+        minijinja::Error::new(
+            ErrorKind::InvalidOperation,
+            format!("\"{}\" isn't a date", date),
+        )
+    })
+}
+
+fn date_format(format: String) -> Result<String, minijinja::Error> {
+    validate_date_format(&format)
+        .map_err(|e| minijinja::Error::new(ErrorKind::InvalidOperation, e))?;
+    Ok(format)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::OutputFormat;
+    use crate::config::format_config::Section;
+    use crate::config::Config;
+    use crate::data::{ProfessionalExperience, Resume, TaggedText};
+    use crate::error::Error;
+    use crate::renderer::template_renderer::{
+        render_template, template_format, TemplateRenderer, BUILTIN_TEMPLATES,
+    };
+
+    fn render(source: &str) -> Result<String, Error> {
+        render_with_config(source, &Config::default())
+    }
+
+    /// Render `source` as a text template.
+    fn render_with_config(source: &str, config: &Config) -> Result<String, Error> {
+        let x = Resume {
+            name: String::from("Foo & Bar"),
+            professional_experience: vec![ProfessionalExperience {
+                position: String::from("position"),
+                experience: vec![TaggedText::from("Cut **build times** by 50%")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        render_template("test", source, &x, config, OutputFormat::Text)
+    }
+
+    #[test]
+    fn test_builtin_templates() {
+        let x = Resume::read_from_config_file("tst/test_resume.toml", &Default::default()).unwrap();
+        for (name, extension, source) in BUILTIN_TEMPLATES {
+            let format = template_format(extension);
+            let rendered = render_template(name, source, &x, &Config::default(), format).unwrap();
+            assert!(rendered.contains("Foo Bar"), "{}", name);
+            assert!(rendered.contains("Jan 2020 - Present"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_builtin_templates_follow_format_config() {
+        let x = Resume::read_from_config_file("tst/test_resume.toml", &Default::default()).unwrap();
+        let mut config = Config::default();
+        config.format_config.hide_gpa = true;
+        config.format_config.text_config.content.sections =
+            Some(vec![Section::Education, Section::Experience]);
+        config.format_config.text_config.dates.date_format = String::from("%m/%Y");
+        config.format_config.pdf_config.margin = String::from("1in");
+        let text = BUILTIN_TEMPLATES[0].2;
+        let rendered = render_template("text", text, &x, &config, OutputFormat::Text).unwrap();
+        assert!(rendered.find("EDUCATION").unwrap() < rendered.find("EXPERIENCE").unwrap());
+        assert!(!rendered.contains("PROJECTS"));
+        assert!(!rendered.contains("GPA"));
+        assert!(rendered.contains("01/2020 - Present"));
+
+        let latex = BUILTIN_TEMPLATES[2].2;
+        let rendered = render_template("latex", latex, &x, &config, OutputFormat::Pdf).unwrap();
+        assert!(rendered.contains("margin=1in"));
+    }
+
+    #[test]
+    fn test_format_context() {
+        let mut config = Config::default();
+        config.format_config.text_config.content.sections =
+            Some(vec![Section::Objective, Section::Custom]);
+        config.format_config.text_config.width = 60;
+        config.format_config.text_config.dates.date_format = String::from("%Y");
+        assert_eq!(
+            render_with_config(
+                "{{ sections | join(\",\") }} {{ format_config.text_config.width }}",
+                &config
+            )
+            .unwrap(),
+            "objective,custom 60"
+        );
+        assert_eq!(
+            render_with_config("{{ \"2021-03\" | date }}", &config).unwrap(),
+            "2021"
+        );
+        assert_eq!(render("{{ latex is defined }}").unwrap(), "false");
+    }
+
+    #[test]
+    fn test_context() {
+        assert_eq!(
+            render("{{ name }}: {% for job in professional_experience %}{{ job.experience[0] }}{% endfor %}")
+                .unwrap(),
+            "Foo & Bar: Cut **build times** by 50%"
+        );
+        assert!(matches!(render("{{ nmae }}"), Err(Error::Template { .. })));
+    }
+
+    #[test]
+    fn test_escaping_filters() {
+        assert_eq!(
+            render("{{ professional_experience[0].experience[0] | latex }}").unwrap(),
+            "Cut \\textbf{build times} by 50\\%"
+        );
+        assert_eq!(render("{{ name | html }}").unwrap(), "Foo &amp; Bar");
+        assert_eq!(
+            render("{{ professional_experience[0].experience[0] | plain }}").unwrap(),
+            "Cut build times by 50%"
+        );
+        assert_eq!(
+            render("{{ \"example.com/#a_b\" | url | latex_url }}").unwrap(),
+            "https://example.com/\\#a_b"
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            render("{{ \"foo bar baz\" | wrap(7, \"- \", \"  \") }}").unwrap(),
+            "- foo\n  bar\n  baz"
+        );
+    }
+
+    #[test]
+    fn test_date_filters() {
+        assert_eq!(render("{{ \"2021-03\" | date }}").unwrap(), "Mar 2021");
+        assert_eq!(render("{{ \"2021-03\" | date(\"%Y\") }}").unwrap(), "2021");
+        assert_eq!(
+            render("{{ \"2020-01\" | time_range(\"2021-03\", \"%m/%Y\", true) }}").unwrap(),
+            "01/2020 - 03/2021 (1 yr 3 mos)"
+        );
+        assert!(render("{{ \"March\" | date }}").is_err());
+        assert!(render("{{ \"2021-03\" | date(\"%Q\") }}").is_err());
+    }
+
+    #[test]
+    fn test_source() {
+        let (source, extension) = TemplateRenderer::new("latex").source().unwrap();
+        assert!(source.contains("\\documentclass"));
+        assert_eq!(extension, "tex");
+        assert!(TemplateRenderer::new("no_such_template").source().is_err());
+    }
+}
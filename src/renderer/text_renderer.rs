@@ -1,15 +1,41 @@
+use crate::config::format_config::{ContactField, Section};
 use crate::config::Config;
+use crate::data::markup::plain_text;
 use crate::data::{
-    CoverLetter, Education, Objective, OtherExperience, PersonalInfo, ProfessionalExperience,
-    ProjectInfo, Resume, Technologies,
+    Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+    OtherExperience, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, Technologies,
 };
-use crate::renderer::Renderer;
+use crate::error::Error;
+use crate::renderer::{render_option, Renderer};
 use crate::util::{
     cover_letter_file_name, date_string, split_string_across_lines, time_range_string,
     write_string_to_file,
 };
 use std::path::PathBuf;
 
+/// The default order of the sections.
+pub(crate) const SECTIONS: &[Section] = &[
+    Section::PersonalInfo,
+    Section::Objective,
+    Section::Experience,
+    Section::Projects,
+    Section::Technologies,
+    Section::Education,
+    Section::Certifications,
+    Section::Custom,
+];
+
+/// The default contact fields and their order.
+pub(crate) const CONTACT: &[ContactField] = &[
+    ContactField::Github,
+    ContactField::Email,
+    ContactField::Phone,
+    ContactField::Location,
+    ContactField::Linkedin,
+    ContactField::Website,
+    ContactField::Pronouns,
+];
+
 #[derive(Default)]
 pub struct TextRenderer;
 
@@ -20,7 +46,7 @@ impl TextRenderer {
 }
 
 impl Renderer<Resume, PathBuf> for TextRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<PathBuf, Error> {
         let ext = String::from("txt");
 
         if let Some(c) = &element.cover_letter {
@@ -37,56 +63,63 @@ impl Renderer<Resume, PathBuf> for TextRenderer {
         write_string_to_file(
             &resume,
             config.args.output_dir.as_ref(),
-            &config.args.output_name,
+            config.args.output_name(),
             Some(&ext),
         )
     }
 }
 
 impl Renderer<Resume, String> for TextRenderer {
-    fn render(&self, element: &Resume, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Resume, config: &Config) -> Result<String, Error> {
         let mut text = centered_string(&element.name, config.format_config.text_config.width);
-        text = format!(
-            "{}\n\n{}",
-            text,
-            self.render(&element.personal_info, config)?
-        );
-        if let Some(obj) = &element.objective {
-            text = format!("{}\n\n{}", text, self.render(obj, config)?);
-        }
-        text = format!(
-            "{}\n\n{}",
-            text,
-            self.render(&element.professional_experience, config)?
-        );
-        if let Some(e) = &element.other_experience {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
-        }
-        if let Some(e) = &element.technologies {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
-        }
-        if let Some(e) = &element.education {
-            text = format!("{}\n\n{}", text, self.render(e, config)?);
+        let sections = config
+            .format_config
+            .sections(&config.format_config.text_config.content.sections, SECTIONS);
+        for section in sections {
+            let rendered = match section {
+                Section::PersonalInfo => Some(self.render(&element.personal_info, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+                Section::Objective => render_option(self, &element.objective, config)?,
+                Section::Experience if element.professional_experience.is_empty() => None,
+                Section::Experience => Some(self.render(&element.professional_experience, config)?),
+                Section::Projects => render_option(self, &element.other_experience, config)?,
+                Section::Technologies => render_option(self, &element.technologies, config)?,
+                Section::Education if element.education.is_empty() => None,
+                Section::Education => Some(self.render(&element.education, config)?),
+                Section::Certifications if element.certifications.is_empty() => None,
+                Section::Certifications => Some(self.render(&element.certifications, config)?),
+                Section::Custom => Some(self.render(&element.custom_sections, config)?)
+                    .filter(|x: &String| !x.is_empty()),
+            };
+            if let Some(rendered) = rendered {
+                text = format!("{}\n\n{}", text, rendered);
+            }
         }
         Ok(text)
     }
 }
 
 impl Renderer<PersonalInfo, String> for TextRenderer {
-    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, String> {
-        let text = right_and_left_aligned(
-            &element.github,
-            Some(&element.email),
+    fn render(&self, element: &PersonalInfo, config: &Config) -> Result<String, Error> {
+        let fields = config
+            .format_config
+            .contact(&config.format_config.text_config.content.contact, CONTACT);
+        let items = element
+            .contact_items(&fields)
+            .iter()
+            .map(|x| x.plain_text())
+            .collect::<Vec<String>>();
+        Ok(spread_across_lines(
+            &items,
             config.format_config.text_config.width,
-        );
-        Ok(text)
+        ))
     }
 }
 
 impl Renderer<Objective, String> for TextRenderer {
-    fn render(&self, element: &Objective, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Objective, config: &Config) -> Result<String, Error> {
         let text = split_string_across_lines(
-            &element.objective,
+            &plain_text(&element.objective),
             config.format_config.text_config.width,
             None,
             None,
@@ -100,7 +133,7 @@ impl Renderer<Vec<ProfessionalExperience>, String> for TextRenderer {
         &self,
         element: &Vec<ProfessionalExperience>,
         config: &Config,
-    ) -> Result<String, String> {
+    ) -> Result<String, Error> {
         let mut text = centered_string("EXPERIENCE", config.format_config.text_config.width);
 
         let exp = element
@@ -108,10 +141,10 @@ impl Renderer<Vec<ProfessionalExperience>, String> for TextRenderer {
             .map(|e| self.render(e, config))
             .reduce(|a, b| Ok(format!("{}\n\n{}", a?, b?)))
             .unwrap_or_else(|| {
-                Err(
+                Err(Error::Render(
                     "An error occurred while rendering professional experience to plain text."
                         .to_string(),
-                )
+                ))
             })?;
 
         text = format!("{}\n{}", text, exp);
@@ -121,7 +154,7 @@ impl Renderer<Vec<ProfessionalExperience>, String> for TextRenderer {
 }
 
 impl Renderer<ProfessionalExperience, String> for TextRenderer {
-    fn render(&self, element: &ProfessionalExperience, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &ProfessionalExperience, config: &Config) -> Result<String, Error> {
         let mut text = if let (Some(org), Some(location)) =
             (&element.organization, &element.location)
         {
@@ -136,7 +169,12 @@ impl Renderer<ProfessionalExperience, String> for TextRenderer {
             text,
             right_and_left_aligned(
                 &element.position,
-                Some(&time_range_string(&element.start, &element.end)),
+                Some(&time_range_string(
+                    &element.start,
+                    &element.end,
+                    &config.format_config.text_config.dates.date_format,
+                    config.format_config.text_config.dates.show_duration,
+                )),
                 config.format_config.text_config.width
             )
         );
@@ -146,7 +184,7 @@ impl Renderer<ProfessionalExperience, String> for TextRenderer {
             .iter()
             .map(|e| {
                 split_string_across_lines(
-                    e,
+                    &plain_text(e),
                     config.format_config.text_config.width,
                     Some(String::from("- ")),
                     Some(String::from("  ")),
@@ -161,8 +199,56 @@ impl Renderer<ProfessionalExperience, String> for TextRenderer {
     }
 }
 
+impl Renderer<Vec<CustomSection>, String> for TextRenderer {
+    fn render(&self, element: &Vec<CustomSection>, config: &Config) -> Result<String, Error> {
+        Ok(element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n"))
+    }
+}
+
+impl Renderer<CustomSection, String> for TextRenderer {
+    fn render(&self, element: &CustomSection, config: &Config) -> Result<String, Error> {
+        let header = centered_string(
+            &element.title.to_uppercase(),
+            config.format_config.text_config.width,
+        );
+        let items = element
+            .items
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n");
+
+        Ok(format!("{}\n{}", header, items))
+    }
+}
+
+impl Renderer<CustomSectionItem, String> for TextRenderer {
+    fn render(&self, element: &CustomSectionItem, config: &Config) -> Result<String, Error> {
+        let width = config.format_config.text_config.width;
+        let mut lines = element
+            .header_lines()
+            .into_iter()
+            .map(|(l, r)| right_and_left_aligned(l, r, width))
+            .collect::<Vec<String>>();
+        lines.extend(element.bullets.iter().map(|e| {
+            split_string_across_lines(
+                &plain_text(e),
+                width,
+                Some(String::from("- ")),
+                Some(String::from("  ")),
+            )
+        }));
+
+        Ok(lines.join("\n"))
+    }
+}
+
 impl Renderer<OtherExperience, String> for TextRenderer {
-    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &OtherExperience, config: &Config) -> Result<String, Error> {
         let header = centered_string("PROJECTS", config.format_config.text_config.width);
         // todo: handle long lines?
         // todo: clean up?
@@ -172,7 +258,9 @@ impl Renderer<OtherExperience, String> for TextRenderer {
             .map(|s| self.render(*s, config))
             .reduce(|a, b| Ok(format!("{}\n{}", a?, b?)))
             .unwrap_or_else(|| {
-                Err("An error occurred while rendering other experience to plain text.".to_string())
+                Err(Error::Render(
+                    "An error occurred while rendering other experience to plain text.".to_string(),
+                ))
             })?;
 
         Ok(format!("{}\n{}", header, projects))
@@ -180,9 +268,13 @@ impl Renderer<OtherExperience, String> for TextRenderer {
 }
 
 impl Renderer<ProjectInfo, String> for TextRenderer {
-    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &ProjectInfo, config: &Config) -> Result<String, Error> {
         Ok(split_string_across_lines(
-            &format!("{}: {}", &element.project_name, &element.description),
+            &format!(
+                "{}: {}",
+                &element.project_name,
+                plain_text(&element.description)
+            ),
             config.format_config.text_config.width,
             Some(String::from("- ")),
             Some(String::from("  ")),
@@ -191,9 +283,9 @@ impl Renderer<ProjectInfo, String> for TextRenderer {
 }
 
 impl Renderer<Technologies, String> for TextRenderer {
-    fn render(&self, element: &Technologies, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &Technologies, config: &Config) -> Result<String, Error> {
         let header = centered_string("TECHNOLOGIES", config.format_config.text_config.width);
-        let technologies = element.technologies.join(", ");
+        let technologies = element.get_technologies().join(", ");
         let technologies = split_string_across_lines(
             &technologies,
             config.format_config.text_config.width,
@@ -209,30 +301,90 @@ impl Renderer<Technologies, String> for TextRenderer {
     }
 }
 
+impl Renderer<Vec<Education>, String> for TextRenderer {
+    fn render(&self, element: &Vec<Education>, config: &Config) -> Result<String, Error> {
+        let header = centered_string("EDUCATION", config.format_config.text_config.width);
+        let education = element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n");
+
+        Ok(format!("{}\n{}", header, education))
+    }
+}
+
 impl Renderer<Education, String> for TextRenderer {
-    fn render(&self, element: &Education, config: &Config) -> Result<String, String> {
-        let title = centered_string("UNIVERSITY", config.format_config.text_config.width);
-        let education = if element.graduation.is_some() {
-            format!(
-                "{} - {} - {} - {}",
-                element.school,
-                element.location,
-                element.major,
-                element.graduation.as_ref().unwrap()
-            )
-        } else {
-            format!(
-                "{} - {} - {}",
-                element.school, element.location, element.major
+    fn render(&self, element: &Education, config: &Config) -> Result<String, Error> {
+        let width = config.format_config.text_config.width;
+        let graduation = element
+            .graduation
+            .map(|x| x.format(&config.format_config.text_config.dates.date_format));
+        let mut lines = vec![
+            right_and_left_aligned(
+                &element.school,
+                Some(element.location.as_str()).filter(|x| !x.is_empty()),
+                width,
+            ),
+            right_and_left_aligned(&element.degree_string(), graduation.as_deref(), width),
+        ];
+        lines.extend(
+            element
+                .details_string(!config.format_config.hide_gpa)
+                .into_iter()
+                .chain(element.coursework_string())
+                .map(|x| split_string_across_lines(&x, width, None, None)),
+        );
+        lines.extend(element.get_extras().iter().map(|e| {
+            split_string_across_lines(
+                &plain_text(e),
+                width,
+                Some(String::from("- ")),
+                Some(String::from("  ")),
             )
-        };
-        let education = centered_string(&education, config.format_config.text_config.width);
-        Ok(format!("{}\n{}", title, education))
+        }));
+
+        Ok(lines.join("\n"))
+    }
+}
+
+impl Renderer<Vec<Certification>, String> for TextRenderer {
+    fn render(&self, element: &Vec<Certification>, config: &Config) -> Result<String, Error> {
+        let header = centered_string("CERTIFICATIONS", config.format_config.text_config.width);
+        let certifications = element
+            .iter()
+            .map(|x| self.render(x, config))
+            .collect::<Result<Vec<String>, Error>>()?
+            .join("\n\n");
+
+        Ok(format!("{}\n{}", header, certifications))
+    }
+}
+
+impl Renderer<Certification, String> for TextRenderer {
+    fn render(&self, element: &Certification, config: &Config) -> Result<String, Error> {
+        let width = config.format_config.text_config.width;
+        let dates = element.dates(&config.format_config.text_config.dates.date_format);
+        let mut lines = vec![right_and_left_aligned(&element.name, Some(&dates), width)];
+        let credential_id = element
+            .credential_id
+            .as_ref()
+            .map(|x| format!("Credential ID: {}", x));
+        if !element.issuer.is_empty() || credential_id.is_some() {
+            lines.push(right_and_left_aligned(
+                &element.issuer,
+                credential_id.as_deref(),
+                width,
+            ));
+        }
+        lines.extend(element.url.clone());
+
+        Ok(lines.join("\n"))
     }
 }
 
 impl Renderer<CoverLetter, String> for TextRenderer {
-    fn render(&self, element: &CoverLetter, config: &Config) -> Result<String, String> {
+    fn render(&self, element: &CoverLetter, config: &Config) -> Result<String, Error> {
         let mut header = String::new();
         if let Some(name) = &element.name {
             header = format!("{}\n", name);
@@ -251,7 +403,12 @@ impl Renderer<CoverLetter, String> for TextRenderer {
             .paragraphs
             .iter()
             .map(|p| {
-                split_string_across_lines(p, config.format_config.text_config.width, None, None)
+                split_string_across_lines(
+                    &plain_text(p),
+                    config.format_config.text_config.width,
+                    None,
+                    None,
+                )
             })
             .collect::<Vec<String>>()
             .join("\n\n");
@@ -270,14 +427,62 @@ fn centered_string(s: &str, width: usize) -> String {
     format!("{s:>width$}", s = s, width = (width / 2) + (s.len() / 2))
 }
 
+/// The least space between two items on the same line.
+const MIN_GAP: usize = 3;
+
+/// Lay out the items in as few lines as fit the width, spreading the items on each line evenly
+/// across it. Two items on a line are aligned to the left and right.
+fn spread_across_lines(items: &[String], width: usize) -> String {
+    let mut lines: Vec<Vec<&str>> = vec![];
+    let mut line_length = 0;
+    for item in items {
+        match lines.last_mut() {
+            Some(line) if line_length + MIN_GAP + item.chars().count() <= width => {
+                line.push(item);
+                line_length += MIN_GAP + item.chars().count();
+            }
+            _ => {
+                lines.push(vec![item]);
+                line_length = item.chars().count();
+            }
+        }
+    }
+
+    lines
+        .iter()
+        .map(|line| {
+            let gaps = line.len().saturating_sub(1);
+            let space = width.saturating_sub(line.iter().map(|x| x.chars().count()).sum());
+            let mut text = String::new();
+            for (i, item) in line.iter().enumerate() {
+                if i > 0 {
+                    // Spread the remainder over the first gaps
+                    let gap = space / gaps + usize::from(i <= space % gaps);
+                    text.push_str(&" ".repeat(gap));
+                }
+                text.push_str(item);
+            }
+            text
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Put `l` on the left and `r` on the right of a line. If they don't both fit on one line, `r` goes
+/// on its own line.
 fn right_and_left_aligned(l: &str, r: Option<&str>, width: usize) -> String {
-    if r.is_some() {
-        format!(
-            "{left}{right:>width$}",
-            left = l,
-            right = r.unwrap(),
-            width = width - l.len()
-        )
+    if let Some(r) = r {
+        let space = width.saturating_sub(l.chars().count());
+        if space > r.chars().count() {
+            format!("{left}{right:>space$}", left = l, right = r, space = space)
+        } else {
+            format!(
+                "{left}\n{right:>width$}",
+                left = l,
+                right = r,
+                width = width
+            )
+        }
     } else {
         l.to_string()
     }
@@ -285,16 +490,56 @@ fn right_and_left_aligned(l: &str, r: Option<&str>, width: usize) -> String {
 
 #[cfg(test)]
 mod test {
-    use crate::config::format_config::{FormatConfig, TextConfig};
+    use crate::config::format_config::{FormatConfig, Section, TextConfig};
     use crate::config::Config;
+    use crate::data::date::ResumeDate;
     use crate::data::{
-        CoverLetter, Education, Objective, OtherExperience, PersonalInfo, ProfessionalExperience,
-        ProjectInfo, Technologies,
+        Certification, CoverLetter, CustomSection, CustomSectionItem, Education, Objective,
+        OtherExperience, PersonalInfo, ProfessionalExperience, ProjectInfo, Resume, TaggedText,
+        Technologies,
     };
     use crate::renderer::text_renderer::TextRenderer;
     use crate::renderer::Renderer;
     use crate::util::date_string;
 
+    #[test]
+    fn test_sections() {
+        let x = Resume {
+            name: String::from("Foo Bar"),
+            objective: Some(Objective {
+                objective: String::from("objective"),
+            }),
+            education: vec![Education {
+                school: String::from("school"),
+                location: String::from("location"),
+                major: String::from("major"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut config = get_config();
+        config.format_config.sections = Some(vec![Section::Education, Section::Experience]);
+        // The resume has no experience, e.g. after a variant removed all of it, so it's left out
+        config.format_config.text_config.content.sections = Some(vec![
+            Section::Education,
+            Section::Experience,
+            Section::Objective,
+        ]);
+
+        let rendered: String = TextRenderer::new().render(&x, &config).unwrap();
+
+        assert_eq!(
+            rendered,
+            "                     Foo Bar
+
+                    EDUCATION
+school                                    location
+major
+
+objective"
+        );
+    }
+
     #[test]
     fn test_personal_info() {
         let x = PersonalInfo {
@@ -310,6 +555,54 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_personal_info_non_ascii() {
+        let x = PersonalInfo {
+            email: String::from("foo@bar.com"),
+            location: Some(String::from("Zürich")),
+            ..Default::default()
+        };
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "foo@bar.com                                 Zürich"
+        );
+        assert_eq!(rendered.chars().count(), 50);
+    }
+
+    #[test]
+    fn test_no_contact_items() {
+        let x = Resume {
+            name: String::from("Foo Bar"),
+            objective: Some(Objective {
+                objective: String::from("objective"),
+            }),
+            ..Default::default()
+        };
+        let rendered: String = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(rendered, "                     Foo Bar\n\nobjective");
+    }
+
+    #[test]
+    fn test_personal_info_multiple_lines() {
+        let x = PersonalInfo {
+            email: String::from("foo@bar.com"),
+            github: String::from("github.com/foo"),
+            phone: Some(String::from("555-555-5555")),
+            location: Some(String::from("Seattle, WA")),
+            linkedin: Some(String::from("linkedin.com/in/foo")),
+            ..Default::default()
+        };
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "github.com/foo       foo@bar.com      555-555-5555\nSeattle, WA                    linkedin.com/in/foo"
+        );
+    }
+
     #[test]
     fn test_objective() {
         let x = Objective {
@@ -320,31 +613,48 @@ mod test {
         assert_eq!(rendered, "Lorem ipsum dolor sit amet, consectetur adipiscing\nelit, sed do eiusmod tempor incididunt ut");
     }
 
+    #[test]
+    fn test_markup() {
+        let x = Objective {
+            objective: String::from(
+                "Cut **build times** by *half* with `sccache` and [more](example.com/a_b)",
+            ),
+        };
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "Cut build times by half with sccache and more\n(example.com/a_b)"
+        );
+    }
+
     #[test]
     fn test_professional_experience() {
         let a = ProfessionalExperience {
             organization: Some(String::from("organizationA")),
             position: String::from("positionA"),
             location: Some(String::from("locationA")),
-            start: String::from("startA"),
-            end: String::from("endA"),
+            start: ResumeDate::new(2020, 1).unwrap(),
+            end: ResumeDate::Present,
             experience: vec![
-                String::from("experienceA1"),
-                String::from("experienceA2"),
-                String::from("experienceA3"),
+                TaggedText::from("experienceA1"),
+                TaggedText::from("experienceA2"),
+                TaggedText::from("experienceA3"),
             ],
+            tags: vec![],
         };
         let b = ProfessionalExperience {
             organization: Some(String::from("organizationB")),
             position: String::from("positionB"),
             location: Some(String::from("locationB")),
-            start: String::from("startB"),
-            end: String::from("endB"),
+            start: ResumeDate::new(2018, 1).unwrap(),
+            end: ResumeDate::new(2019, 12).unwrap(),
             experience: vec![
-                String::from("experienceB1"),
-                String::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
-                String::from("experienceB3"),
+                TaggedText::from("experienceB1"),
+                TaggedText::from("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut"),
+                TaggedText::from("experienceB3"),
             ],
+            tags: vec![],
         };
         let x = vec![a, b];
 
@@ -354,13 +664,13 @@ mod test {
             rendered,
             "                    EXPERIENCE
 organizationA                            locationA
-positionA                            startA - endA
+positionA                       Jan 2020 - Present
 - experienceA1
 - experienceA2
 - experienceA3
 
 organizationB                            locationB
-positionB                            startB - endB
+positionB                      Jan 2018 - Dec 2019
 - experienceB1
 - Lorem ipsum dolor sit amet, consectetur
   adipiscing elit, sed do eiusmod tempor
@@ -411,19 +721,19 @@ positionB                            startB - endB
     #[test]
     fn test_technologies() {
         let tech = vec![
-            String::from("Lorem"),
-            String::from("ipsum"),
-            String::from("dolor"),
-            String::from("sit"),
-            String::from("amet"),
-            String::from("consectetur"),
-            String::from("adipiscing"),
-            String::from("elit"),
-            String::from("sed"),
-            String::from("do"),
-            String::from("eiusmod"),
-            String::from("tempor"),
-            String::from("incididunt"),
+            TaggedText::from("Lorem"),
+            TaggedText::from("ipsum"),
+            TaggedText::from("dolor"),
+            TaggedText::from("sit"),
+            TaggedText::from("amet"),
+            TaggedText::from("consectetur"),
+            TaggedText::from("adipiscing"),
+            TaggedText::from("elit"),
+            TaggedText::from("sed"),
+            TaggedText::from("do"),
+            TaggedText::from("eiusmod"),
+            TaggedText::from("tempor"),
+            TaggedText::from("incididunt"),
         ];
 
         let x = Technologies { technologies: tech };
@@ -441,11 +751,133 @@ positionB                            startB - endB
 
     #[test]
     fn test_education() {
-        let x = Education {
-            school: String::from("school"),
-            location: String::from("location"),
-            major: String::from("major"),
-            graduation: Some(String::from("graduation")),
+        let x = vec![
+            Education {
+                school: String::from("school"),
+                location: String::from("location"),
+                degree: Some(String::from("B.S.")),
+                major: String::from("major"),
+                minor: Some(String::from("minor")),
+                graduation: ResumeDate::new(2017, 6),
+                gpa: Some(String::from("3.8")),
+                honors: vec![String::from("Cum Laude")],
+                coursework: vec![TaggedText::from("Course A"), TaggedText::from("Course B")],
+                extras: Some(vec![TaggedText::from("extra")]),
+                ..Default::default()
+            },
+            Education {
+                school: String::from("other school"),
+                location: String::from("other location"),
+                major: String::from("other major"),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "                    EDUCATION
+school                                    location
+B.S. in major, minor in minor             Jun 2017
+GPA: 3.8, Cum Laude
+Relevant coursework: Course A, Course B
+- extra
+
+other school                        other location
+other major"
+        );
+    }
+
+    #[test]
+    fn test_certifications() {
+        let x = vec![
+            Certification {
+                name: String::from("name"),
+                issuer: String::from("issuer"),
+                issued: ResumeDate::new(2021, 3).unwrap(),
+                expires: ResumeDate::new(2024, 3),
+                credential_id: Some(String::from("ABC-123")),
+                url: Some(String::from("example.com/verify")),
+                ..Default::default()
+            },
+            Certification {
+                name: String::from("other name"),
+                issued: ResumeDate::new(2019, 1).unwrap(),
+                ..Default::default()
+            },
+        ];
+
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "                  CERTIFICATIONS
+name                           Mar 2021 - Mar 2024
+issuer                      Credential ID: ABC-123
+example.com/verify
+
+other name                                Jan 2019"
+        );
+    }
+
+    #[test]
+    fn test_certification_long_name() {
+        let x = Certification {
+            name: String::from("A certification with a name that is longer than the page"),
+            issued: ResumeDate::new(2019, 1).unwrap(),
+            ..Default::default()
+        };
+
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "A certification with a name that is longer than the page
+                                          Jan 2019"
+        );
+    }
+
+    #[test]
+    fn test_custom_section() {
+        let x = CustomSection {
+            title: String::from("Awards"),
+            items: vec![
+                CustomSectionItem {
+                    title: Some(String::from("title")),
+                    location: Some(String::from("location")),
+                    subtitle: Some(String::from("subtitle")),
+                    date: Some(String::from("2021")),
+                    bullets: vec![TaggedText::from("bullet")],
+                    ..Default::default()
+                },
+                CustomSectionItem {
+                    title: Some(String::from("other title")),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let rendered = TextRenderer::new().render(&x, &get_config()).unwrap();
+
+        assert_eq!(
+            rendered,
+            "                      AWARDS
+title                                     location
+subtitle                                      2021
+- bullet
+
+other title"
+        );
+    }
+
+    #[test]
+    fn test_custom_section_long_title() {
+        let x = CustomSectionItem {
+            title: Some(String::from(
+                "A title that is much longer than the width of the page",
+            )),
+            date: Some(String::from("2021")),
             ..Default::default()
         };
 
@@ -453,8 +885,8 @@ positionB                            startB - endB
 
         assert_eq!(
             rendered,
-            "                    UNIVERSITY
-      school - location - major - graduation"
+            "A title that is much longer than the width of the page
+                                              2021"
         );
     }
 
@@ -479,7 +911,10 @@ positionB                            startB - endB
     fn get_config() -> Config {
         Config {
             format_config: FormatConfig {
-                text_config: TextConfig { width: 50 },
+                text_config: TextConfig {
+                    width: 50,
+                    ..Default::default()
+                },
                 ..Default::default()
             },
             ..Default::default()
@@ -0,0 +1,266 @@
+//! A local preview of the rendered resume that reloads whenever the input files change.
+//!
+//! The preview shows the html, markdown and text renderings next to the generated pdf. Everything
+//! is served from localhost without any external assets.
+use crate::config::arguments::{Arguments, OutputFormat};
+use crate::config::Config;
+use crate::data::Resume;
+use crate::error::Error;
+use crate::renderer::html_renderer::HtmlRenderer;
+use crate::renderer::markdown_renderer::MarkdownRenderer;
+use crate::renderer::render_resume;
+use crate::renderer::text_renderer::TextRenderer;
+use crate::renderer::Renderer;
+use crate::util::escape_html;
+use crate::watch::watch_inputs;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Response, Server};
+
+/// Serve the preview on the given port until the process is stopped.
+pub fn serve(args: Arguments, port: u16) -> Result<(), Error> {
+    let preview = Arc::new(Mutex::new(Preview::build(&args, 0)));
+
+    let (_watcher, changes) = watch_inputs(&args)?;
+    let watched = Arc::clone(&preview);
+    thread::spawn(move || {
+        for () in changes {
+            let version = watched.lock().unwrap().version + 1;
+            let rebuilt = Preview::build(&args, version);
+            *watched.lock().unwrap() = rebuilt;
+        }
+    });
+
+    let server = Server::http(("127.0.0.1", port)).map_err(|e| Error::Serve(e.to_string()))?;
+    println!("Serving the preview at http://127.0.0.1:{}/", port);
+    for request in server.incoming_requests() {
+        let page = preview.lock().unwrap().page(request.url());
+        let response = Response::from_data(page.body)
+            .with_status_code(page.status)
+            .with_header(header("Content-Type", page.content_type))
+            .with_header(header("Cache-Control", "no-store"));
+        if let Err(e) = request.respond(response) {
+            eprintln!("An error occurred while responding to a request: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// The rendered resume. Rendering errors are kept as strings so they can be shown in the preview.
+struct Preview {
+    /// Incremented on every rebuild so the browser can tell when to reload.
+    version: u64,
+    html: Result<String, String>,
+    markdown: Result<String, String>,
+    text: Result<String, String>,
+    pdf: Result<PathBuf, String>,
+}
+
+struct Page {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Preview {
+    /// Read the resume, write the selected output files and render the preview.
+    fn build(args: &Arguments, version: u64) -> Preview {
+        let loaded = Config::from_args(args.clone()).and_then(|config| {
+            let resume = Resume::read_from_config_file(&config.args.resume_input, &config)?;
+            Ok((config, resume))
+        });
+        let (config, resume) = match loaded {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return Preview::failed(version, e.to_string());
+            }
+        };
+
+        let report = render_resume(&resume, &config);
+        println!("{}", report);
+        let pdf = report
+            .results
+            .into_iter()
+            .find(|x| x.name == OutputFormat::Pdf.to_string())
+            .map(|x| x.result.map_err(|e| e.to_string()))
+            .unwrap_or_else(|| Err(String::from("The pdf format isn't selected.")));
+
+        Preview {
+            version,
+            html: render_string(&HtmlRenderer::new(), &resume, &config),
+            markdown: render_string(&MarkdownRenderer::new(), &resume, &config),
+            text: render_string(&TextRenderer::new(), &resume, &config),
+            pdf,
+        }
+    }
+
+    fn failed(version: u64, error: String) -> Preview {
+        Preview {
+            version,
+            html: Err(error.clone()),
+            markdown: Err(error.clone()),
+            text: Err(error.clone()),
+            pdf: Err(error),
+        }
+    }
+
+    fn page(&self, url: &str) -> Page {
+        let path = url.split('?').next().unwrap_or_default();
+        match path {
+            "/" => html_page(INDEX.to_owned()),
+            "/version" => Page {
+                status: 200,
+                content_type: "text/plain; charset=utf-8",
+                body: self.version.to_string().into_bytes(),
+            },
+            "/html" => match &self.html {
+                Ok(html) => html_page(html.clone()),
+                Err(e) => error_page(e),
+            },
+            "/markdown" => pre_page("Markdown", &self.markdown),
+            "/text" => pre_page("Text", &self.text),
+            "/pdf" => match self.pdf.as_ref().map(fs::read) {
+                Ok(Ok(pdf)) => Page {
+                    status: 200,
+                    content_type: "application/pdf",
+                    body: pdf,
+                },
+                Ok(Err(e)) => error_page(&e.to_string()),
+                Err(e) => error_page(e),
+            },
+            _ => Page {
+                status: 404,
+                content_type: "text/plain; charset=utf-8",
+                body: b"Not found".to_vec(),
+            },
+        }
+    }
+}
+
+fn render_string<R>(renderer: &R, resume: &Resume, config: &Config) -> Result<String, String>
+where
+    R: Renderer<Resume, String>,
+{
+    renderer.render(resume, config).map_err(|e| e.to_string())
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap()
+}
+
+fn html_page(html: String) -> Page {
+    Page {
+        status: 200,
+        content_type: "text/html; charset=utf-8",
+        body: html.into_bytes(),
+    }
+}
+
+fn pre_page(title: &str, rendered: &Result<String, String>) -> Page {
+    match rendered {
+        Ok(text) => html_page(format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<pre>{}</pre>\n</body>\n</html>\n",
+            title,
+            escape_html(text)
+        )),
+        Err(e) => error_page(e),
+    }
+}
+
+fn error_page(error: &str) -> Page {
+    Page {
+        status: 500,
+        ..html_page(format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Error</title>\n</head>\n<body>\n<pre style=\"color: #b00020\">{}</pre>\n</body>\n</html>\n",
+            escape_html(error)
+        ))
+    }
+}
+
+/// Shows one of the renderings next to the pdf, and reloads both when the version changes.
+const INDEX: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Resume preview</title>
+<style>
+body { margin: 0; height: 100vh; display: flex; flex-direction: column; font-family: sans-serif; }
+nav { padding: 0.5em; border-bottom: 1px solid #ccc; }
+nav a { margin-right: 1em; }
+main { flex: 1; display: flex; }
+iframe { flex: 1; border: none; }
+iframe + iframe { border-left: 1px solid #ccc; }
+</style>
+</head>
+<body>
+<nav>
+<a href="/html" target="rendered">HTML</a>
+<a href="/markdown" target="rendered">Markdown</a>
+<a href="/text" target="rendered">Text</a>
+</nav>
+<main>
+<iframe name="rendered" src="/html"></iframe>
+<iframe name="pdf" src="/pdf"></iframe>
+</main>
+<script>
+let version = null;
+setInterval(() => {
+  fetch("/version").then((r) => r.text()).then((v) => {
+    if (version !== null && v !== version) {
+      document.querySelectorAll("iframe").forEach((f) => f.contentWindow.location.reload());
+    }
+    version = v;
+  }).catch(() => {});
+}, 1000);
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod test {
+    use crate::serve::Preview;
+    use std::path::PathBuf;
+
+    fn preview() -> Preview {
+        Preview {
+            version: 3,
+            html: Ok(String::from("<p>foo</p>")),
+            markdown: Ok(String::from("# Foo & Bar")),
+            text: Err(String::from("<error>")),
+            pdf: Ok(PathBuf::from("tst/does_not_exist.pdf")),
+        }
+    }
+
+    fn body(preview: &Preview, url: &str) -> String {
+        String::from_utf8(preview.page(url).body).unwrap()
+    }
+
+    #[test]
+    fn test_pages() {
+        let p = preview();
+        assert!(body(&p, "/").contains("<iframe name=\"pdf\" src=\"/pdf\"></iframe>"));
+        assert_eq!(body(&p, "/version"), "3");
+        assert_eq!(body(&p, "/html?reload=1"), "<p>foo</p>");
+        assert!(body(&p, "/markdown").contains("<pre># Foo &amp; Bar</pre>"));
+        assert!(body(&p, "/text").contains("&lt;error&gt;"));
+        assert_eq!(p.page("/markdown").status, 200);
+        assert_eq!(p.page("/text").status, 500);
+        assert_eq!(p.page("/pdf").status, 500);
+        assert_eq!(p.page("/pdf").content_type, "text/html; charset=utf-8");
+        assert_eq!(p.page("/foo").status, 404);
+    }
+
+    #[test]
+    fn test_failed() {
+        let p = Preview::failed(1, String::from("parse error"));
+        for url in &["/html", "/markdown", "/text", "/pdf"] {
+            assert!(body(&p, url).contains("parse error"));
+            assert_eq!(p.page(url).status, 500);
+        }
+        assert!(p.page("/").body.starts_with(b"<!DOCTYPE html>"));
+    }
+}
@@ -1,23 +1,68 @@
+use crate::config::arguments::InputFormat;
 use crate::config::Config;
+use crate::data::date::{duration_string, ResumeDate};
+use crate::data::markup::visible_width;
+use crate::error::Error;
 use chrono::{Datelike, Local};
-use serde::Deserialize;
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{DeserializeOwned, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
 use std::fs;
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 
-pub fn string_from_file(file_name: &str) -> Result<String, String> {
-    fs::read_to_string(file_name).map_err(|e| {
-        format!(
-            "An error occurred while trying to open file [{}]: {}",
-            file_name, e
-        )
-    })
+pub fn string_from_file(file_name: &str) -> Result<String, Error> {
+    fs::read_to_string(file_name).map_err(|e| Error::io(Path::new(file_name), e))
 }
 
-pub fn toml_from_string<'de, T>(x: &'de str) -> Result<T, String>
+pub fn toml_from_string<'de, T>(x: &'de str) -> Result<T, Error>
 where
     T: Deserialize<'de>,
 {
-    toml::from_str::<'de, T>(x).map_err(|e| format!("An error occurred while parsing toml: {}", e))
+    Ok(toml::from_str::<'de, T>(x)?)
+}
+
+pub fn json_from_string<'de, T>(x: &'de str) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    Ok(serde_json::from_str::<'de, T>(x)?)
+}
+
+pub fn yaml_from_string<'de, T>(x: &'de str) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    Ok(serde_yaml::from_str::<'de, T>(x)?)
+}
+
+/// Pick the format to parse a file with. An explicitly requested format wins, otherwise the format
+/// is inferred from the file extension, falling back to toml.
+pub fn input_format(file_name: &str, format: Option<InputFormat>) -> InputFormat {
+    format
+        .or_else(|| InputFormat::from_file_name(file_name))
+        .unwrap_or(InputFormat::Toml)
+}
+
+/// Read the file and deserialize it using the format chosen by [input_format].
+pub fn deserialize_file<T>(file_name: &str, format: Option<InputFormat>) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    let s = string_from_file(file_name)?;
+    deserialize_string(&s, input_format(file_name, format)).map_err(|e| e.with_path(file_name))
+}
+
+/// Deserialize the string using the given format.
+pub fn deserialize_string<T>(s: &str, format: InputFormat) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    match format {
+        InputFormat::Toml => toml_from_string(s),
+        InputFormat::Json | InputFormat::JsonResume => json_from_string(s),
+        InputFormat::Yaml => yaml_from_string(s),
+    }
 }
 
 pub fn get_path(dir: Option<&String>, file_name: &str, extension: Option<&String>) -> PathBuf {
@@ -36,27 +81,19 @@ pub fn write_string_to_file(
     dir: Option<&String>,
     file_name: &str,
     extension: Option<&String>,
-) -> Result<PathBuf, String> {
+) -> Result<PathBuf, Error> {
     let file_path = get_path(dir, file_name, extension);
     write_string_to_path(s, &file_path)
 }
 
-pub fn write_string_to_path(s: &str, path: &Path) -> Result<PathBuf, String> {
+pub fn write_string_to_path(s: &str, path: &Path) -> Result<PathBuf, Error> {
+    write_bytes_to_path(s.as_bytes(), path)
+}
+
+pub fn write_bytes_to_path(bytes: &[u8], path: &Path) -> Result<PathBuf, Error> {
     let dir = path.parent().unwrap_or_else(|| Path::new("."));
-    fs::create_dir_all(dir).map_err(|e| {
-        format!(
-            "An error occurred while creating directory [{}]: {}",
-            dir.display(),
-            e
-        )
-    })?;
-    fs::write(path, s).map_err(|e| {
-        format!(
-            "An error occurred while writing file [{}]: {}",
-            path.display(),
-            e
-        )
-    })?;
+    fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    fs::write(path, bytes).map_err(|e| Error::io(path, e))?;
     Ok(path.to_path_buf())
 }
 
@@ -64,20 +101,36 @@ pub fn cover_letter_file_name(config: &Config) -> String {
     if let Some(cover_letter_name) = &config.args.cover_letter_output_name {
         cover_letter_name.clone()
     } else {
-        format!("{}-cover_letter", config.args.output_name)
+        format!("{}-cover_letter", config.args.output_name())
     }
 }
 
 pub fn add_https_to_url(url: &str) -> String {
-    if url.starts_with("https://") {
+    // Urls that already have a scheme, e.g. "http://" or "mailto:", are left alone
+    if url.contains("://") || url.starts_with("mailto:") {
         url.to_owned()
     } else {
         format!("https://{}", url)
     }
 }
 
-pub fn time_range_string(start: &str, end: &str) -> String {
-    format!("{} - {}", start, end)
+/// Format a time range with a chrono format string, optionally followed by its duration.
+pub fn time_range_string(
+    start: &ResumeDate,
+    end: &ResumeDate,
+    date_format: &str,
+    show_duration: bool,
+) -> String {
+    let range = format!(
+        "{} - {}",
+        start.format(date_format),
+        end.format(date_format)
+    );
+    if show_duration {
+        format!("{} ({})", range, duration_string(start, end))
+    } else {
+        range
+    }
 }
 
 pub fn date_string() -> String {
@@ -90,17 +143,71 @@ pub fn date_string() -> String {
     )
 }
 
-/// Escape some special characters by placing a '\' in front
-pub fn escape_special_chars(s: &str) -> String {
-    // Escape '\' first, otherwise it will be escaped later after it's added to escape something else
-    const SPECIAL_CHARS: [char; 10] = ['\\', '&', '%', '$', '#', '_', '{', '}', '~', '^'];
-    let mut s = s.to_owned();
-    SPECIAL_CHARS.iter().for_each(|x| {
-        s = s.replace(&format!("{}", x), &format!("\\{}", x));
+/// The month of the render date, see [date_string].
+pub fn current_month() -> ResumeDate {
+    let now = Local::now();
+    ResumeDate::new(now.year(), now.month()).unwrap_or_default()
+}
+
+/// Escape the characters that have a special meaning in LaTeX text. Typographic Unicode characters
+/// are replaced by their LaTeX equivalents, and characters that pdflatex can't typeset with the
+/// T1 font encoding are replaced by '?', unless `unicode` is set for an engine that reads Unicode.
+pub fn escape_special_chars(s: &str, unicode: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    s.chars().for_each(|c| match c {
+        '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+            escaped.push('\\');
+            escaped.push(c);
+        }
+        // These make accents or line breaks when escaped with a '\'
+        '\\' => escaped.push_str("\\textbackslash{}"),
+        '~' => escaped.push_str("\\textasciitilde{}"),
+        '^' => escaped.push_str("\\textasciicircum{}"),
+        '\u{a0}' => escaped.push('~'),
+        '\u{2013}' => escaped.push_str("--"),
+        '\u{2014}' => escaped.push_str("---"),
+        '\u{2018}' => escaped.push('`'),
+        '\u{2019}' => escaped.push('\''),
+        '\u{201c}' => escaped.push_str("``"),
+        '\u{201d}' => escaped.push_str("''"),
+        '\u{2022}' => escaped.push_str("\\textbullet{}"),
+        '\u{2026}' => escaped.push_str("\\ldots{}"),
+        '\u{20ac}' => escaped.push_str("\\texteuro{}"),
+        // Latin-1 and Latin Extended-A are supported by inputenc
+        c if unicode || c.is_ascii() || ('\u{a1}'..='\u{17f}').contains(&c) => escaped.push(c),
+        _ => escaped.push('?'),
     });
-    s
+    escaped
 }
 
+/// Escape the characters that break the url argument of `\href`.
+pub fn escape_url(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    s.chars().for_each(|c| {
+        if ['%', '#', '{', '}', '\\'].contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    });
+    escaped
+}
+
+/// Escape the characters that have a special meaning in HTML text and attribute values
+pub fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    s.chars().for_each(|c| match c {
+        '&' => escaped.push_str("&amp;"),
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '"' => escaped.push_str("&quot;"),
+        '\'' => escaped.push_str("&#39;"),
+        _ => escaped.push(c),
+    });
+    escaped
+}
+
+/// Wrap the words of the string to the width. Words are measured by their visible width, so inline
+/// markup doesn't count towards the width.
 pub fn split_string_across_lines(
     s: &str,
     width: usize,
@@ -114,7 +221,8 @@ pub fn split_string_across_lines(
     let line_prefix = line_prefix.unwrap_or_default();
     for x in split {
         // Check if adding the next word will take us over the width limit. If so, add a new line.
-        if line_length + x.len() + 1 > width {
+        let word_width = visible_width(x);
+        if line_length + word_width + 1 > width {
             text.push('\n');
             text.push_str(&line_prefix);
             new_line = true;
@@ -129,11 +237,42 @@ pub fn split_string_across_lines(
 
         // Add the next word
         text.push_str(x);
-        line_length += x.len();
+        line_length += word_width;
     }
     text
 }
 
+/// Deserialize either a single table or a list of tables into a list. Used for fields that were a
+/// single table before they became a list, so older resume files keep loading.
+pub fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct OneOrMany<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for OneOrMany<T>
+    where
+        T: Deserialize<'de>,
+    {
+        type Value = Vec<T>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "a table or a list of tables")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            Vec::deserialize(SeqAccessDeserializer::new(seq))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            Ok(vec![T::deserialize(MapAccessDeserializer::new(map))?])
+        }
+    }
+
+    deserializer.deserialize_any(OneOrMany(PhantomData))
+}
+
 pub fn default_true() -> bool {
     true
 }
@@ -142,13 +281,20 @@ pub fn default_false() -> bool {
     false
 }
 
+pub fn default_date_format() -> String {
+    String::from("%b %Y")
+}
+
 #[cfg(test)]
 mod test {
-    use crate::config::arguments::Arguments;
+    use crate::config::arguments::{Arguments, InputFormat};
     use crate::config::Config;
+    use crate::data::date::ResumeDate;
+    use crate::error::Error;
     use crate::util::{
-        add_https_to_url, cover_letter_file_name, escape_special_chars, get_path,
-        split_string_across_lines, string_from_file, time_range_string, toml_from_string,
+        add_https_to_url, cover_letter_file_name, escape_html, escape_special_chars, escape_url,
+        get_path, input_format, json_from_string, split_string_across_lines, string_from_file,
+        time_range_string, toml_from_string, yaml_from_string,
     };
 
     #[test]
@@ -160,7 +306,7 @@ mod test {
     #[test]
     fn test_string_from_file_does_not_exist() {
         let s = string_from_file("tst/does_not_exist.txt");
-        assert!(s.is_err());
+        assert!(matches!(s, Err(Error::Io { .. })));
     }
 
     #[derive(Debug, Serialize, Deserialize, Default)]
@@ -183,6 +329,42 @@ mod test {
         assert_eq!(t.baz.things, String::from("stuff"));
     }
 
+    #[test]
+    fn test_json_from_string() {
+        let t: TestToml =
+            json_from_string(r#"{"foo": "bar", "baz": {"things": "stuff"}}"#).unwrap();
+
+        assert_eq!(t.foo, String::from("bar"));
+        assert_eq!(t.baz.things, String::from("stuff"));
+
+        let e = json_from_string::<TestToml>("foo = \"bar\"").unwrap_err();
+        assert!(matches!(e, Error::Json { .. }));
+    }
+
+    #[test]
+    fn test_yaml_from_string() {
+        let t: TestToml = yaml_from_string("foo: bar\nbaz:\n  things: stuff\n").unwrap();
+
+        assert_eq!(t.foo, String::from("bar"));
+        assert_eq!(t.baz.things, String::from("stuff"));
+
+        let e = yaml_from_string::<TestToml>("foo: [bar").unwrap_err();
+        assert!(matches!(e, Error::Yaml { .. }));
+    }
+
+    #[test]
+    fn test_input_format() {
+        assert_eq!(input_format("foo.toml", None), InputFormat::Toml);
+        assert_eq!(input_format("foo.json", None), InputFormat::Json);
+        assert_eq!(input_format("foo.yaml", None), InputFormat::Yaml);
+        assert_eq!(input_format("foo.yml", None), InputFormat::Yaml);
+        assert_eq!(input_format("foo", None), InputFormat::Toml);
+        assert_eq!(
+            input_format("foo.toml", Some(InputFormat::Json)),
+            InputFormat::Json
+        );
+    }
+
     #[test]
     fn test_get_path() {
         let p = get_path(None, "foo", None);
@@ -201,7 +383,7 @@ mod test {
     fn test_cover_letter_file_name() {
         let args = Arguments {
             resume_input: String::from("foo"),
-            output_name: String::from("bar"),
+            output_name: Some(String::from("bar")),
             ..Default::default()
         };
         let c = Config {
@@ -217,7 +399,7 @@ mod test {
     fn test_cover_letter_file_name_with_arg() {
         let args = Arguments {
             resume_input: String::from("foo"),
-            output_name: String::from("bar"),
+            output_name: Some(String::from("bar")),
             cover_letter_output_name: Some(String::from("baz")),
             ..Default::default()
         };
@@ -237,20 +419,61 @@ mod test {
 
         let url = add_https_to_url("https://example.com");
         assert_eq!(url, "https://example.com");
+
+        let url = add_https_to_url("mailto:foo@bar.com");
+        assert_eq!(url, "mailto:foo@bar.com");
     }
 
     #[test]
     fn test_time_range_string() {
-        let s = time_range_string("foo", "bar");
-        assert_eq!(s, "foo - bar");
+        let start = ResumeDate::new(2020, 1).unwrap();
+        let end = ResumeDate::new(2022, 3).unwrap();
+        let s = time_range_string(&start, &end, "%b %Y", false);
+        assert_eq!(s, "Jan 2020 - Mar 2022");
+        let s = time_range_string(&start, &end, "%Y-%m", true);
+        assert_eq!(s, "2020-01 - 2022-03 (2 yrs 3 mos)");
+        let s = time_range_string(&start, &ResumeDate::Present, "%Y-%m", false);
+        assert_eq!(s, "2020-01 - Present");
     }
 
     #[test]
     fn test_escape_special_chars() {
-        // '\\', '&', '%', '$', '#', '_', '{', '}', '~', '^'
         let input = "\\ & % $ # _ { } ~ ^";
-        let output = escape_special_chars(input);
-        assert_eq!(output, "\\\\ \\& \\% \\$ \\# \\_ \\{ \\} \\~ \\^");
+        let output = escape_special_chars(input, false);
+        assert_eq!(
+            output,
+            "\\textbackslash{} \\& \\% \\$ \\# \\_ \\{ \\} \\textasciitilde{} \\textasciicircum{}"
+        );
+
+        let input = "R&D in C#, 50% of snake_case";
+        let output = escape_special_chars(input, false);
+        assert_eq!(output, "R\\&D in C\\#, 50\\% of snake\\_case");
+
+        let input = "Café – “naïve” … 100\u{a0}€ 🚀 日本";
+        let output = escape_special_chars(input, false);
+        assert_eq!(output, "Café -- ``naïve'' \\ldots{} 100~\\texteuro{} ? ??");
+
+        let output = escape_special_chars(input, true);
+        assert_eq!(
+            output,
+            "Café -- ``naïve'' \\ldots{} 100~\\texteuro{} 🚀 日本"
+        );
+    }
+
+    #[test]
+    fn test_escape_url() {
+        let output = escape_url("https://example.com/a%20b#c");
+        assert_eq!(output, "https://example.com/a\\%20b\\#c");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        let input = "<a href=\"foo\">R&D's</a>";
+        let output = escape_html(input);
+        assert_eq!(
+            output,
+            "&lt;a href=&quot;foo&quot;&gt;R&amp;D&#39;s&lt;/a&gt;"
+        );
     }
 
     #[test]
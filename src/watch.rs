@@ -0,0 +1,138 @@
+//! Rendering the resume again whenever its input files change.
+use crate::config::arguments::Arguments;
+use crate::error::Error;
+use crate::renderer::render;
+use chrono::Local;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Editors often write a file in several steps, so wait for the events to settle before
+/// rendering.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Render the resume, then render it again each time the resume data or format config file is
+/// saved. Failing to read or render the resume doesn't stop the watch: the error is printed and
+/// the next save is rendered as usual.
+pub fn watch(args: Arguments) -> Result<(), Error> {
+    let (_watcher, changes) = watch_inputs(&args)?;
+    rebuild(&args);
+    for () in changes {
+        rebuild(&args);
+    }
+    Ok(())
+}
+
+/// Start watching the resume data and format config files. A message is sent on the returned
+/// channel each time one of them is saved, until the returned watcher is dropped.
+pub fn watch_inputs(
+    args: &Arguments,
+) -> Result<(Debouncer<RecommendedWatcher>, mpsc::Receiver<()>), Error> {
+    let files = watched_files(args)?;
+    let dirs = watched_dirs(&files);
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, move |events: DebounceEventResult| {
+        match events {
+            Ok(events) if is_changed(&events, &files) => {
+                // The receiver is only dropped when the process is exiting
+                let _ = tx.send(());
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("{}", Error::Watch(e)),
+        }
+    })?;
+    // Watch the directories rather than the files themselves. Editors that save by replacing the
+    // file would otherwise end the watch after the first save.
+    for dir in dirs {
+        debouncer
+            .watcher()
+            .watch(&dir, RecursiveMode::NonRecursive)?;
+    }
+    Ok((debouncer, rx))
+}
+
+fn rebuild(args: &Arguments) {
+    println!();
+    println!("[{}] Rendering", Local::now().format("%H:%M:%S"));
+    match render(args) {
+        Ok(report) => println!("{}", report),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Waiting for the next change to try again.");
+        }
+    }
+}
+
+fn watched_files(args: &Arguments) -> Result<Vec<PathBuf>, Error> {
+    let mut files = vec![&args.resume_input];
+    files.extend(&args.format_config);
+    files
+        .into_iter()
+        .map(|x| fs::canonicalize(x).map_err(|e| Error::io(Path::new(x), e)))
+        .collect()
+}
+
+fn watched_dirs(files: &[PathBuf]) -> BTreeSet<PathBuf> {
+    files
+        .iter()
+        .filter_map(|x| x.parent())
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+fn is_changed(events: &[DebouncedEvent], files: &[PathBuf]) -> bool {
+    events.iter().any(|x| files.contains(&x.path))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::arguments::Arguments;
+    use crate::error::Error;
+    use crate::watch::{is_changed, watched_dirs, watched_files};
+    use notify_debouncer_mini::{DebouncedEvent, DebouncedEventKind};
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_watched_files() {
+        let args = Arguments {
+            resume_input: String::from("tst/test_resume.toml"),
+            format_config: Some(String::from("tst/test_format_config.toml")),
+            ..Default::default()
+        };
+        let files = watched_files(&args).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|x| x.is_absolute()));
+        assert!(files[0].ends_with("tst/test_resume.toml"));
+        assert!(files[1].ends_with("tst/test_format_config.toml"));
+
+        let dirs = watched_dirs(&files);
+        assert_eq!(dirs.len(), 1);
+        assert_eq!(dirs.iter().next(), Some(&fs::canonicalize("tst").unwrap()));
+
+        let args = Arguments {
+            resume_input: String::from("tst/does_not_exist.toml"),
+            ..Default::default()
+        };
+        assert!(matches!(watched_files(&args), Err(Error::Io { .. })));
+    }
+
+    #[test]
+    fn test_is_changed() {
+        let files = vec![PathBuf::from("/foo/resume.toml")];
+        let event = |path: &str| DebouncedEvent {
+            path: PathBuf::from(path),
+            kind: DebouncedEventKind::Any,
+        };
+        assert!(is_changed(
+            &[event("/foo/bar.pdf"), event("/foo/resume.toml")],
+            &files
+        ));
+        assert!(!is_changed(&[event("/foo/bar.pdf")], &files));
+        assert!(!is_changed(&[], &files));
+    }
+}